@@ -0,0 +1,159 @@
+//! Reproducible mini-batch sampling over a [`Dataset`].
+
+use super::{Dataset, types::models::ModelConfig};
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+/// Splits datasets into (optionally shuffled) mini-batches.
+///
+/// The sampler owns its random stream, so a seeded sampler produces the
+/// same sequence of batches on every run.  Each call to
+/// [`batches`](BatchSampler::batches) is one epoch drawn from a fresh
+/// permutation; [`next_batch`](BatchSampler::next_batch) walks the same
+/// permutations as an endless stream, for trainers that take one
+/// stochastic step per round.
+#[derive(Debug, Clone)]
+pub struct BatchSampler {
+    batch_size: usize,
+    shuffle: bool,
+    drop_last: bool,
+    seed: Option<u64>,
+    rng: StdRng,
+    /// Current permutation of the stream and the position within it.
+    order: Vec<usize>,
+    cursor: usize,
+}
+
+impl BatchSampler {
+    /// Shuffling sampler of `batch_size` rows; `seed` makes it
+    /// reproducible.
+    pub fn new(batch_size: usize, seed: Option<u64>) -> Result<Self, &'static str> {
+        if batch_size == 0 {
+            return Err("batch_size must be positive");
+        }
+        Ok(BatchSampler {
+            batch_size,
+            shuffle: true,
+            drop_last: false,
+            seed,
+            rng: rng(seed),
+            order: Vec::new(),
+            cursor: 0,
+        })
+    }
+
+    /// Sampler seeded with the `seed` of a `[[models]]` entry.
+    pub fn from_config(
+        config: &ModelConfig,
+        batch_size: usize,
+    ) -> Result<Self, &'static str> {
+        Self::new(batch_size, config.seed)
+    }
+
+    /// Shuffle rows before every epoch (default `true`).
+    pub fn shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Skip the last batch of an epoch when it is smaller than
+    /// `batch_size` (default `false`).
+    pub fn drop_last(mut self, drop_last: bool) -> Self {
+        self.drop_last = drop_last;
+        self
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Independent sampler for stream `stream` (e.g. a node or client
+    /// index): same settings, seed `seed + stream`.
+    pub fn fork(&self, stream: u64) -> Self {
+        let seed = self.seed.map(|s| s.wrapping_add(stream));
+        BatchSampler {
+            seed,
+            rng: rng(seed),
+            order: Vec::new(),
+            cursor: 0,
+            ..*self
+        }
+    }
+
+    /// Number of batches in an epoch over `len` rows.
+    pub fn batch_count(&self, len: usize) -> usize {
+        if self.drop_last {
+            len / self.batch_size
+        } else {
+            len.div_ceil(self.batch_size)
+        }
+    }
+
+    /// One epoch of mini-batches over `dataset`.
+    pub fn batches<'a>(&mut self, dataset: &'a Dataset) -> Batches<'a> {
+        Batches {
+            dataset,
+            order: self.permutation(dataset.len()),
+            batch_size: self.batch_size,
+            drop_last: self.drop_last,
+            position: 0,
+        }
+    }
+
+    /// Row indices of the next batch of the stream over `len` rows.
+    ///
+    /// A new permutation is drawn whenever the previous one is exhausted
+    /// (or was drawn for another length).  With fewer than `batch_size`
+    /// rows every batch holds all of them, even with `drop_last`.
+    pub fn next_batch(&mut self, len: usize) -> Vec<usize> {
+        let remaining = self.order.len().saturating_sub(self.cursor);
+        let short = remaining < self.batch_size && self.drop_last;
+        if self.order.len() != len || remaining == 0 || short {
+            self.order = self.permutation(len);
+            self.cursor = 0;
+        }
+        let end = (self.cursor + self.batch_size).min(self.order.len());
+        let batch = self.order[self.cursor..end].to_vec();
+        self.cursor = end;
+        batch
+    }
+
+    fn permutation(&mut self, len: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..len).collect();
+        if self.shuffle {
+            order.shuffle(&mut self.rng);
+        }
+        order
+    }
+}
+
+fn rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    }
+}
+
+/// Mini-batches of one epoch, as datasets of the selected rows.
+#[derive(Debug, Clone)]
+pub struct Batches<'a> {
+    dataset: &'a Dataset,
+    order: Vec<usize>,
+    batch_size: usize,
+    drop_last: bool,
+    position: usize,
+}
+
+impl Iterator for Batches<'_> {
+    type Item = Dataset;
+
+    fn next(&mut self) -> Option<Dataset> {
+        let end = (self.position + self.batch_size).min(self.order.len());
+        let size = end - self.position;
+        if size == 0 || (self.drop_last && size < self.batch_size) {
+            return None;
+        }
+        let batch = self.dataset.subset(&self.order[self.position..end]);
+        self.position = end;
+        Some(batch)
+    }
+}
@@ -1,7 +1,10 @@
 //! # convective-data :: datasets
 
+pub mod batches;
 pub mod io;
 pub mod torches;
 pub mod vectors;
 pub mod types;
 
+pub use batches::{BatchSampler, Batches};
+pub use vectors::{Dataset, DatasetBuilder};
@@ -0,0 +1,72 @@
+use crate::errors::configs::ConfigError;
+use serde::Deserialize;
+use std::fs;
+
+/// Consensus algorithm selected by a `[[models.consensus]]` entry.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConsensusMethod {
+    /// Decentralized gradient descent.
+    #[default]
+    DGD,
+    /// Gradient tracking (DIGing / NEXT).
+    #[serde(alias = "DIGing", alias = "NEXT")]
+    GradientTracking,
+    /// Exact first-order algorithm.
+    EXTRA,
+    /// Decentralized consensus ADMM.
+    ADMM,
+}
+
+/// Parameters of a distributed (consensus) optimizer.
+///
+/// `params_labels[i]` names `params_values[i]`, e.g.
+/// `["learning_rate", "epsilon"]` / `[0.1, 0.001]`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ConsensusConfig {
+    pub id: String,
+    /// Algorithm to run; [`ConsensusMethod::DGD`] when omitted.
+    #[serde(default)]
+    pub label: ConsensusMethod,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub params_labels: Vec<String>,
+    #[serde(default)]
+    pub params_values: Vec<f64>,
+}
+
+impl ConsensusConfig {
+    /// Value of the parameter labelled `label`.
+    pub fn param(&self, label: &str) -> Option<f64> {
+        self.params_labels
+            .iter()
+            .position(|l| l == label)
+            .and_then(|i| self.params_values.get(i).copied())
+    }
+}
+
+/// A `[[models]]` entry of a distributed experiment.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DistributedModelConfig {
+    pub id: String,
+    #[serde(default)]
+    pub consensus: Vec<ConsensusConfig>,
+}
+
+/// File layout of a distributed `models.toml`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ConsensusTemplate {
+    pub models: Vec<DistributedModelConfig>,
+}
+
+impl ConsensusTemplate {
+    pub fn load_from_toml(file_route: &str) -> Result<Self, ConfigError> {
+        let contents = fs::read_to_string(file_route)?;
+        let template = toml::from_str(&contents)?;
+        Ok(template)
+    }
+
+    /// First consensus optimizer declared in the file.
+    pub fn consensus(&self) -> Option<&ConsensusConfig> {
+        self.models.iter().flat_map(|m| m.consensus.iter()).next()
+    }
+}
@@ -1,4 +1,6 @@
 pub mod configs;
+pub mod consensus;
 pub mod experiments;
 pub mod features;
 pub mod models;
+pub mod topology;
@@ -0,0 +1,73 @@
+use crate::errors::configs::ConfigError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// One weighted, directed entry `W[from][to]` of a mixing matrix.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct VertexConfig {
+    pub from: usize,
+    pub to: usize,
+    pub weight: f64,
+}
+
+/// Communication graph between the nodes of a distributed experiment.
+///
+/// Entries not listed in `vertices` are zero.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TopologyConfig {
+    pub id: String,
+    pub nodes: usize,
+    #[serde(default)]
+    pub vertices: Vec<VertexConfig>,
+}
+
+impl TopologyConfig {
+    /// Dense `nodes × nodes` mixing matrix, row-major.
+    pub fn mixing_matrix(&self) -> Result<Vec<Vec<f64>>, ConfigError> {
+        let mut matrix = vec![vec![0.0; self.nodes]; self.nodes];
+        for v in &self.vertices {
+            if v.from >= self.nodes || v.to >= self.nodes {
+                return Err(ConfigError::ParseError(format!(
+                    "vertex ({}, {}) out of range for {} nodes",
+                    v.from, v.to, self.nodes
+                )));
+            }
+            matrix[v.from][v.to] = v.weight;
+        }
+        Ok(matrix)
+    }
+
+    /// Build a topology from a dense matrix, keeping non-zero entries.
+    pub fn from_matrix(id: &str, matrix: &[Vec<f64>]) -> Self {
+        let vertices = matrix
+            .iter()
+            .enumerate()
+            .flat_map(|(from, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, w)| **w != 0.0)
+                    .map(move |(to, &weight)| VertexConfig { from, to, weight })
+            })
+            .collect();
+
+        TopologyConfig {
+            id: id.to_string(),
+            nodes: matrix.len(),
+            vertices,
+        }
+    }
+}
+
+/// File layout of a `topology.toml`: one or more `[[topology]]` tables.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TopologyTemplate {
+    pub topology: Vec<TopologyConfig>,
+}
+
+impl TopologyTemplate {
+    pub fn load_from_toml(file_route: &str) -> Result<Self, ConfigError> {
+        let contents = fs::read_to_string(file_route)?;
+        let template = toml::from_str(&contents)?;
+        Ok(template)
+    }
+}
@@ -1 +1,125 @@
+//! In-memory tabular dataset backed by plain `Vec`s.
 
+/// Row-major feature matrix paired with a target vector and optional
+/// per-sample weights.
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    features: Vec<Vec<f64>>,
+    target: Vec<f64>,
+    weights: Option<Vec<f64>>,
+}
+
+impl Dataset {
+    pub fn builder() -> DatasetBuilder {
+        DatasetBuilder::new()
+    }
+
+    /// Number of samples (rows).
+    pub fn len(&self) -> usize {
+        self.target.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.target.is_empty()
+    }
+
+    /// Number of features (columns).
+    pub fn feature_count(&self) -> usize {
+        self.features.first().map_or(0, Vec::len)
+    }
+
+    pub fn features(&self) -> &[Vec<f64>] {
+        &self.features
+    }
+
+    pub fn target(&self) -> &[f64] {
+        &self.target
+    }
+
+    /// Per-sample weights, `None` when all samples weigh the same.
+    pub fn weights(&self) -> Option<&[f64]> {
+        self.weights.as_deref()
+    }
+
+    /// Rows at `indices`, in that order.
+    ///
+    /// # Panics
+    ///
+    /// If an index is out of range.
+    pub fn subset(&self, indices: &[usize]) -> Dataset {
+        Dataset {
+            features: indices.iter().map(|&i| self.features[i].clone()).collect(),
+            target: indices.iter().map(|&i| self.target[i]).collect(),
+            weights: self
+                .weights
+                .as_ref()
+                .map(|w| indices.iter().map(|&i| w[i]).collect()),
+        }
+    }
+
+    /// Consume the dataset and return `(features, target)`, dropping any
+    /// sample weights.
+    pub fn into_parts(self) -> (Vec<Vec<f64>>, Vec<f64>) {
+        (self.features, self.target)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DatasetBuilder {
+    features: Option<Vec<Vec<f64>>>,
+    target: Option<Vec<f64>>,
+    weights: Option<Vec<f64>>,
+}
+
+impl DatasetBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn features(mut self, features: Vec<Vec<f64>>) -> Self {
+        self.features = Some(features);
+        self
+    }
+
+    pub fn target(mut self, target: Vec<f64>) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Non-negative weight of every sample.
+    pub fn weights(mut self, weights: Vec<f64>) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    pub fn build(self) -> Result<Dataset, &'static str> {
+        let features = self.features.ok_or("Missing Dataset's features")?;
+        let target = self.target.ok_or("Missing Dataset's target")?;
+
+        if features.len() != target.len() {
+            return Err("Features and target have different number of rows");
+        }
+        let m = features.first().map_or(0, Vec::len);
+        if features.iter().any(|row| row.len() != m) {
+            return Err("Feature rows have different lengths");
+        }
+
+        if let Some(weights) = &self.weights {
+            if weights.len() != target.len() {
+                return Err("Weights and target have different number of rows");
+            }
+            if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+                return Err("Weights must be finite and non-negative");
+            }
+            if !weights.is_empty() && weights.iter().sum::<f64>() <= 0.0 {
+                return Err("Weights must have a positive sum");
+            }
+        }
+
+        Ok(Dataset {
+            features,
+            target,
+            weights: self.weights,
+        })
+    }
+}
@@ -0,0 +1,271 @@
+//! Time-varying and faulty communication topologies.
+//!
+//! A [`DynamicTopology`] hands a trainer the mixing matrix of every round.
+//! [`FaultyTopology`] derives it from a static base matrix `W` by removing
+//! links and nodes, then moving the removed weight onto the diagonal:
+//!
+//! ```text
+//! W_t[i][j] = W[i][j]           if the link i–j and both nodes are up
+//!           = 0                 otherwise                     (i ≠ j)
+//! W_t[i][i] = 1 − Σ_{j≠i} W_t[i][j]
+//! ```
+//!
+//! so a symmetric, doubly-stochastic `W` stays symmetric and doubly
+//! stochastic every round.  Inactive nodes get the identity row: they
+//! neither send nor receive and keep their parameters.
+
+use crate::{errors::GraphError, graph::Graph};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+/// Mixing matrix and participating nodes of one round.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoundTopology {
+    pub round: usize,
+    /// Row-stochastic mixing matrix, row-major; also symmetric and doubly
+    /// stochastic whenever the base matrix is.
+    pub mixing: Vec<Vec<f64>>,
+    /// `active[i]` is false when node `i` dropped out or straggled.
+    pub active: Vec<bool>,
+}
+
+impl RoundTopology {
+    pub fn active_count(&self) -> usize {
+        self.active.iter().filter(|&&a| a).count()
+    }
+}
+
+/// Source of per-round mixing matrices.
+pub trait DynamicTopology: std::fmt::Debug + Send {
+    fn node_count(&self) -> usize;
+
+    /// Topology of communication round `round`.
+    ///
+    /// Trainers call this once per round, in increasing order.
+    fn at(&mut self, round: usize) -> RoundTopology;
+}
+
+// ---------------------------------------------------------------------------
+// Fault configuration
+// ---------------------------------------------------------------------------
+
+/// The link `from`–`to` (both directions) is down for rounds
+/// `start..end`, or from `start` on when `end` is omitted.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LinkFailure {
+    pub from: usize,
+    pub to: usize,
+    pub start: usize,
+    pub end: Option<usize>,
+}
+
+/// Node `node` is down for rounds `start..end`, or from `start` on.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NodeFailure {
+    pub node: usize,
+    pub start: usize,
+    pub end: Option<usize>,
+}
+
+/// Node `node` needs `delay + 1` rounds per update, so it only
+/// participates in every `(delay + 1)`-th round.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Straggler {
+    pub node: usize,
+    pub delay: usize,
+}
+
+/// Network faults applied on top of a static topology.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FaultConfig {
+    /// Probability that each link is up in a given round (default 1).
+    #[serde(default = "FaultConfig::default_edge_probability")]
+    pub edge_probability: f64,
+    /// Probability that each node drops out of a given round (default 0).
+    #[serde(default)]
+    pub dropout_probability: f64,
+    #[serde(default)]
+    pub link_failures: Vec<LinkFailure>,
+    #[serde(default)]
+    pub node_failures: Vec<NodeFailure>,
+    #[serde(default)]
+    pub stragglers: Vec<Straggler>,
+    /// Seed of the random faults; drawn from the OS when omitted.
+    pub seed: Option<u64>,
+}
+
+impl Default for FaultConfig {
+    fn default() -> Self {
+        FaultConfig {
+            edge_probability: 1.0,
+            dropout_probability: 0.0,
+            link_failures: Vec::new(),
+            node_failures: Vec::new(),
+            stragglers: Vec::new(),
+            seed: None,
+        }
+    }
+}
+
+impl FaultConfig {
+    fn default_edge_probability() -> f64 {
+        1.0
+    }
+
+    fn validate(&self, n: usize) -> Result<(), GraphError> {
+        if !(0.0..=1.0).contains(&self.edge_probability) {
+            return Err(GraphError::InvalidParameter(
+                "edge_probability must be in [0, 1]",
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.dropout_probability) {
+            return Err(GraphError::InvalidParameter(
+                "dropout_probability must be in [0, 1]",
+            ));
+        }
+        let nodes = self
+            .link_failures
+            .iter()
+            .flat_map(|l| [l.from, l.to])
+            .chain(self.node_failures.iter().map(|f| f.node))
+            .chain(self.stragglers.iter().map(|s| s.node));
+        for node in nodes {
+            if node >= n {
+                return Err(GraphError::NodeOutOfRange { node, nodes: n });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn in_window(round: usize, start: usize, end: Option<usize>) -> bool {
+    round >= start && end.is_none_or(|end| round < end)
+}
+
+// ---------------------------------------------------------------------------
+// Topologies
+// ---------------------------------------------------------------------------
+
+/// The same mixing matrix every round.
+#[derive(Debug, Clone)]
+pub struct StaticTopology {
+    mixing: Vec<Vec<f64>>,
+}
+
+impl StaticTopology {
+    pub fn new(graph: &Graph) -> Self {
+        StaticTopology {
+            mixing: graph.to_rows(),
+        }
+    }
+}
+
+impl DynamicTopology for StaticTopology {
+    fn node_count(&self) -> usize {
+        self.mixing.len()
+    }
+
+    fn at(&mut self, round: usize) -> RoundTopology {
+        RoundTopology {
+            round,
+            mixing: self.mixing.clone(),
+            active: vec![true; self.mixing.len()],
+        }
+    }
+}
+
+/// Static mixing matrix degraded by a [`FaultConfig`].
+#[derive(Debug)]
+pub struct FaultyTopology {
+    base: Graph,
+    faults: FaultConfig,
+    rng: StdRng,
+}
+
+impl FaultyTopology {
+    /// Apply `faults` to the mixing matrix `base`.
+    pub fn new(base: Graph, faults: FaultConfig) -> Result<Self, GraphError> {
+        faults.validate(base.node_count())?;
+        let rng = match faults.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+        Ok(FaultyTopology { base, faults, rng })
+    }
+
+    pub fn base(&self) -> &Graph {
+        &self.base
+    }
+
+    pub fn faults(&self) -> &FaultConfig {
+        &self.faults
+    }
+
+    fn node_up(&mut self, node: usize, round: usize) -> bool {
+        let failed = self
+            .faults
+            .node_failures
+            .iter()
+            .any(|f| f.node == node && in_window(round, f.start, f.end));
+        let straggling = self
+            .faults
+            .stragglers
+            .iter()
+            .any(|s| s.node == node && round % (s.delay + 1) != 0);
+        let dropped = self.faults.dropout_probability > 0.0
+            && self.rng.random_bool(self.faults.dropout_probability);
+        !(failed || straggling || dropped)
+    }
+
+    fn link_up(&mut self, i: usize, j: usize, round: usize) -> bool {
+        let failed = self.faults.link_failures.iter().any(|l| {
+            ((l.from == i && l.to == j) || (l.from == j && l.to == i))
+                && in_window(round, l.start, l.end)
+        });
+        let sampled_out = self.faults.edge_probability < 1.0
+            && !self.rng.random_bool(self.faults.edge_probability);
+        !(failed || sampled_out)
+    }
+}
+
+impl DynamicTopology for FaultyTopology {
+    fn node_count(&self) -> usize {
+        self.base.node_count()
+    }
+
+    fn at(&mut self, round: usize) -> RoundTopology {
+        let n = self.base.node_count();
+        let active: Vec<bool> = (0..n).map(|i| self.node_up(i, round)).collect();
+        let mut mixing = self.base.to_rows();
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                if mixing[i][j] == 0.0 && mixing[j][i] == 0.0 {
+                    continue;
+                }
+                // Sample every link, even between inactive nodes, so the
+                // random stream does not depend on the dropouts.
+                let up = self.link_up(i, j, round);
+                if !(up && active[i] && active[j]) {
+                    mixing[i][j] = 0.0;
+                    mixing[j][i] = 0.0;
+                }
+            }
+        }
+        for (i, row) in mixing.iter_mut().enumerate() {
+            let off_diagonal: f64 = row
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, w)| w)
+                .sum();
+            row[i] = (1.0 - off_diagonal).max(0.0);
+        }
+
+        RoundTopology {
+            round,
+            mixing,
+            active,
+        }
+    }
+}
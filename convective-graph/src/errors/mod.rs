@@ -0,0 +1,39 @@
+//! # convective-graph :: errors
+
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum GraphError {
+    #[error("Graph has no nodes")]
+    Empty,
+
+    #[error("Matrix is not square: {rows}x{cols}")]
+    NotSquare { rows: usize, cols: usize },
+
+    #[error("Node {node} out of range for {nodes} nodes")]
+    NodeOutOfRange { node: usize, nodes: usize },
+
+    #[error("Invalid weight {weight} at ({row}, {col})")]
+    InvalidWeight { row: usize, col: usize, weight: f64 },
+
+    #[error("Matrix is not symmetric")]
+    NotSymmetric,
+
+    #[error("Row {row} sums to {sum}, expected 1")]
+    NotRowStochastic { row: usize, sum: f64 },
+
+    #[error("Column {col} sums to {sum}, expected 1")]
+    NotColumnStochastic { col: usize, sum: f64 },
+
+    #[error("Graph is not connected")]
+    Disconnected,
+
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(&'static str),
+
+    #[error("Invalid topology: {0}")]
+    Topology(String),
+
+    #[error("Mixing matrix has no spectral gap (|λ₂| = {slem})")]
+    NoSpectralGap { slem: f64 },
+}
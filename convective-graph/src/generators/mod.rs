@@ -0,0 +1,123 @@
+//! Generators for common communication topologies.
+//!
+//! Every generator returns an undirected [`Graph`] with unit edge weights
+//! and no self-loops; turn it into a mixing matrix with a
+//! [`WeightScheme`](crate::weights::WeightScheme).
+
+use crate::{errors::GraphError, graph::Graph};
+use nalgebra::DMatrix;
+use rand::Rng;
+
+/// Cycle `0 – 1 – … – (n−1) – 0`.
+pub fn ring(n: usize) -> Result<Graph, GraphError> {
+    let edges: Vec<_> = match n {
+        0 | 1 => Vec::new(),
+        2 => vec![(0, 1, 1.0)],
+        _ => (0..n).map(|i| (i, (i + 1) % n, 1.0)).collect(),
+    };
+    Graph::from_edges(n, &edges, true)
+}
+
+/// Node `0` linked to every other node.
+pub fn star(n: usize) -> Result<Graph, GraphError> {
+    let edges: Vec<_> = (1..n).map(|i| (0, i, 1.0)).collect();
+    Graph::from_edges(n, &edges, true)
+}
+
+/// Every pair of nodes linked.
+pub fn complete(n: usize) -> Result<Graph, GraphError> {
+    Graph::from_matrix(DMatrix::from_fn(
+        n,
+        n,
+        |i, j| if i == j { 0.0 } else { 1.0 },
+    ))
+}
+
+/// `rows × cols` lattice; node `r * cols + c` sits at row `r`, column `c`.
+pub fn grid(rows: usize, cols: usize) -> Result<Graph, GraphError> {
+    let mut edges = Vec::new();
+    for r in 0..rows {
+        for c in 0..cols {
+            let node = r * cols + c;
+            if c + 1 < cols {
+                edges.push((node, node + 1, 1.0));
+            }
+            if r + 1 < rows {
+                edges.push((node, node + cols, 1.0));
+            }
+        }
+    }
+    Graph::from_edges(rows * cols, &edges, true)
+}
+
+/// Erdős–Rényi `G(n, p)`: each pair is linked independently with
+/// probability `p`.
+///
+/// The result may be disconnected; check [`Graph::is_connected`].
+pub fn erdos_renyi<R: Rng + ?Sized>(
+    n: usize,
+    p: f64,
+    rng: &mut R,
+) -> Result<Graph, GraphError> {
+    if !(0.0..=1.0).contains(&p) {
+        return Err(GraphError::InvalidParameter("p must be in [0, 1]"));
+    }
+    let mut edges = Vec::new();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if rng.random_bool(p) {
+                edges.push((i, j, 1.0));
+            }
+        }
+    }
+    Graph::from_edges(n, &edges, true)
+}
+
+/// Watts–Strogatz small-world graph.
+///
+/// Starts from a ring lattice where each node is linked to its `k` nearest
+/// neighbours (`k` even), then rewires every lattice edge `(i, i + s)` with
+/// probability `beta` to a uniformly chosen node, avoiding self-loops and
+/// duplicate edges.
+pub fn small_world<R: Rng + ?Sized>(
+    n: usize,
+    k: usize,
+    beta: f64,
+    rng: &mut R,
+) -> Result<Graph, GraphError> {
+    if k % 2 != 0 || k >= n {
+        return Err(GraphError::InvalidParameter("k must be even and below n"));
+    }
+    if !(0.0..=1.0).contains(&beta) {
+        return Err(GraphError::InvalidParameter("beta must be in [0, 1]"));
+    }
+
+    let mut linked = DMatrix::zeros(n, n);
+    for i in 0..n {
+        for s in 1..=k / 2 {
+            let j = (i + s) % n;
+            linked[(i, j)] = 1.0;
+            linked[(j, i)] = 1.0;
+        }
+    }
+    for s in 1..=k / 2 {
+        for i in 0..n {
+            let j = (i + s) % n;
+            if linked[(i, j)] == 0.0 || !rng.random_bool(beta) {
+                continue;
+            }
+            let free: Vec<usize> = (0..n)
+                .filter(|&t| t != i && linked[(i, t)] == 0.0)
+                .collect();
+            if free.is_empty() {
+                continue;
+            }
+            let t = free[rng.random_range(0..free.len())];
+            linked[(i, j)] = 0.0;
+            linked[(j, i)] = 0.0;
+            linked[(i, t)] = 1.0;
+            linked[(t, i)] = 1.0;
+        }
+    }
+    Graph::from_matrix(linked)
+}
@@ -0,0 +1,207 @@
+//! Weighted communication graph of a decentralized training run.
+//!
+//! Entry `(i, j)` of the weight matrix is the weight node `i` gives to the
+//! information it receives from node `j`; this matches the convention of
+//! the mixing matrices consumed by the consensus optimizers, so a
+//! topology's mixing matrix can be wrapped directly with
+//! [`Graph::from_rows`].  Diagonal entries are self-loops: they take part
+//! in stochasticity checks but not in degrees or Laplacians.
+
+/// Conversions from and to `nalgebra-sparse` matrices.
+pub mod sparse;
+
+use crate::errors::GraphError;
+use convective_data::datasets::types::topology::TopologyConfig;
+use nalgebra::{DMatrix, DVector};
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph {
+    weights: DMatrix<f64>,
+}
+
+impl Graph {
+    /// Wrap a dense, square, non-negative weight matrix.
+    pub fn from_matrix(weights: DMatrix<f64>) -> Result<Self, GraphError> {
+        let (rows, cols) = weights.shape();
+        if rows != cols {
+            return Err(GraphError::NotSquare { rows, cols });
+        }
+        if rows == 0 {
+            return Err(GraphError::Empty);
+        }
+        for col in 0..cols {
+            for row in 0..rows {
+                let weight = weights[(row, col)];
+                if !weight.is_finite() || weight < 0.0 {
+                    return Err(GraphError::InvalidWeight { row, col, weight });
+                }
+            }
+        }
+        Ok(Graph { weights })
+    }
+
+    /// Build from row-major `Vec<Vec<f64>>`, e.g. a topology mixing matrix.
+    pub fn from_rows(rows: &[Vec<f64>]) -> Result<Self, GraphError> {
+        let n = rows.len();
+        if let Some(row) = rows.iter().find(|r| r.len() != n) {
+            return Err(GraphError::NotSquare {
+                rows: n,
+                cols: row.len(),
+            });
+        }
+        Self::from_matrix(DMatrix::from_fn(n, n, |i, j| rows[i][j]))
+    }
+
+    /// Build an `n`-node graph from `(from, to, weight)` entries `w[from][to]`,
+    /// the same convention as a topology's vertices.
+    ///
+    /// With `undirected`, every edge also sets the reverse entry.  Repeated
+    /// edges overwrite earlier ones.
+    pub fn from_edges(
+        n: usize,
+        edges: &[(usize, usize, f64)],
+        undirected: bool,
+    ) -> Result<Self, GraphError> {
+        let mut weights = DMatrix::zeros(n, n);
+        for &(from, to, weight) in edges {
+            if let Some(&node) = [from, to].iter().find(|&&node| node >= n) {
+                return Err(GraphError::NodeOutOfRange { node, nodes: n });
+            }
+            weights[(from, to)] = weight;
+            if undirected {
+                weights[(to, from)] = weight;
+            }
+        }
+        Self::from_matrix(weights)
+    }
+
+    /// Build from the mixing matrix of a `[[topology]]` table.
+    pub fn from_topology(topology: &TopologyConfig) -> Result<Self, GraphError> {
+        let rows = topology
+            .mixing_matrix()
+            .map_err(|e| GraphError::Topology(e.to_string()))?;
+        Self::from_rows(&rows)
+    }
+
+    /// `[[topology]]` table with one vertex per non-zero weight.
+    pub fn to_topology(&self, id: &str) -> TopologyConfig {
+        TopologyConfig::from_matrix(id, &self.to_rows())
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.weights.nrows()
+    }
+
+    pub fn weights(&self) -> &DMatrix<f64> {
+        &self.weights
+    }
+
+    pub fn into_weights(self) -> DMatrix<f64> {
+        self.weights
+    }
+
+    /// Row-major copy of the weight matrix.
+    pub fn to_rows(&self) -> Vec<Vec<f64>> {
+        self.weights
+            .row_iter()
+            .map(|row| row.iter().copied().collect())
+            .collect()
+    }
+
+    /// Off-diagonal non-zero entries as `(i, j, w_ij)`.
+    pub fn edges(&self) -> Vec<(usize, usize, f64)> {
+        let n = self.node_count();
+        (0..n)
+            .flat_map(|i| (0..n).map(move |j| (i, j)))
+            .filter(|&(i, j)| i != j && self.weights[(i, j)] > 0.0)
+            .map(|(i, j)| (i, j, self.weights[(i, j)]))
+            .collect()
+    }
+
+    /// Nodes `j ≠ i` with `w_ij > 0`.
+    pub fn neighbours(&self, i: usize) -> Vec<usize> {
+        (0..self.node_count())
+            .filter(|&j| j != i && self.weights[(i, j)] > 0.0)
+            .collect()
+    }
+
+    /// Binary adjacency pattern, without self-loops.
+    pub fn adjacency(&self) -> DMatrix<f64> {
+        DMatrix::from_fn(self.node_count(), self.node_count(), |i, j| {
+            if i != j && self.weights[(i, j)] > 0.0 {
+                1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
+    /// Weighted degree `d_i = Σ_{j≠i} w_ij`.
+    pub fn degrees(&self) -> DVector<f64> {
+        DVector::from_fn(self.node_count(), |i, _| {
+            self.weights.row(i).sum() - self.weights[(i, i)]
+        })
+    }
+
+    /// Whether `|w_ij − w_ji| ≤ tol` for every pair.
+    pub fn is_symmetric(&self, tol: f64) -> bool {
+        is_symmetric(&self.weights, tol)
+    }
+
+    /// Whether every node reaches every other, ignoring edge direction.
+    pub fn is_connected(&self) -> bool {
+        let n = self.node_count();
+        let mut seen = vec![false; n];
+        let mut queue = VecDeque::from([0]);
+        seen[0] = true;
+        while let Some(i) = queue.pop_front() {
+            for (j, visited) in seen.iter_mut().enumerate() {
+                let linked = self.weights[(i, j)] > 0.0 || self.weights[(j, i)] > 0.0;
+                if linked && !*visited {
+                    *visited = true;
+                    queue.push_back(j);
+                }
+            }
+        }
+        seen.into_iter().all(|s| s)
+    }
+
+    /// Combinatorial Laplacian `L = D − A` of the weighted graph.
+    pub fn laplacian(&self) -> DMatrix<f64> {
+        let n = self.node_count();
+        let degrees = self.degrees();
+        DMatrix::from_fn(n, n, |i, j| {
+            if i == j {
+                degrees[i]
+            } else {
+                -self.weights[(i, j)]
+            }
+        })
+    }
+
+    /// Normalized Laplacian `I − D^{-1/2} A D^{-1/2}`.
+    ///
+    /// Rows and columns of isolated nodes are zero.
+    pub fn normalized_laplacian(&self) -> DMatrix<f64> {
+        let n = self.node_count();
+        let inv_sqrt: Vec<f64> = self
+            .degrees()
+            .iter()
+            .map(|&d| if d > 0.0 { 1.0 / d.sqrt() } else { 0.0 })
+            .collect();
+        DMatrix::from_fn(n, n, |i, j| {
+            if i == j {
+                if inv_sqrt[i] > 0.0 { 1.0 } else { 0.0 }
+            } else {
+                -self.weights[(i, j)] * inv_sqrt[i] * inv_sqrt[j]
+            }
+        })
+    }
+}
+
+/// Whether a square matrix satisfies `|m_ij − m_ji| ≤ tol`.
+pub fn is_symmetric(m: &DMatrix<f64>, tol: f64) -> bool {
+    m.is_square()
+        && (0..m.nrows()).all(|i| (0..i).all(|j| (m[(i, j)] - m[(j, i)]).abs() <= tol))
+}
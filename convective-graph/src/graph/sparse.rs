@@ -0,0 +1,26 @@
+use super::Graph;
+use crate::errors::GraphError;
+use nalgebra::DMatrix;
+use nalgebra_sparse::{CooMatrix, CsrMatrix};
+
+impl Graph {
+    /// Build from a sparse weight matrix; missing entries are zero.
+    pub fn from_sparse(weights: &CsrMatrix<f64>) -> Result<Self, GraphError> {
+        Self::from_matrix(DMatrix::from(weights))
+    }
+
+    /// Weight matrix in CSR format, keeping only non-zero entries.
+    pub fn to_sparse(&self) -> CsrMatrix<f64> {
+        let n = self.node_count();
+        let mut coo = CooMatrix::new(n, n);
+        for j in 0..n {
+            for i in 0..n {
+                let w = self.weights[(i, j)];
+                if w != 0.0 {
+                    coo.push(i, j, w);
+                }
+            }
+        }
+        CsrMatrix::from(&coo)
+    }
+}
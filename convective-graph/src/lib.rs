@@ -0,0 +1,32 @@
+//! convective-graph
+//!
+//! Spectral and Graph Theory for the convective-rs framework.
+
+/// Error types
+pub mod errors;
+
+/// Time-varying and faulty topologies
+pub mod dynamic;
+
+/// Weighted communication graphs
+pub mod graph;
+
+/// Ring, star, complete, grid, Erdős–Rényi and small-world generators
+pub mod generators;
+
+/// Eigenvalues, algebraic connectivity and mixing-matrix checks
+pub mod spectral;
+
+/// Mixing-matrix weight schemes
+pub mod weights;
+
+pub use dynamic::{
+    DynamicTopology, FaultConfig, FaultyTopology, RoundTopology, StaticTopology,
+};
+pub use errors::GraphError;
+pub use graph::Graph;
+pub use spectral::{
+    MixingProperties, algebraic_connectivity, mixing_properties, spectral_gap,
+    symmetric_eigenvalues,
+};
+pub use weights::WeightScheme;
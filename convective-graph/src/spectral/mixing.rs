@@ -0,0 +1,127 @@
+use super::{SYMMETRY_TOLERANCE, second_largest_modulus};
+use crate::{errors::GraphError, graph::is_symmetric};
+use nalgebra::DMatrix;
+use serde::Serialize;
+
+/// Properties of a mixing matrix relevant to consensus convergence.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MixingProperties {
+    pub nonnegative: bool,
+    /// Rows sum to one: every node takes a convex combination.
+    pub row_stochastic: bool,
+    /// Columns sum to one: the network average is preserved.
+    pub column_stochastic: bool,
+    pub doubly_stochastic: bool,
+    pub symmetric: bool,
+    /// Second-largest eigenvalue modulus `|λ₂(W)|`.
+    pub slem: f64,
+    /// `1 − |λ₂(W)|`.
+    pub spectral_gap: f64,
+}
+
+impl MixingProperties {
+    /// Whether DGD-type methods reach consensus on the average: `W` is
+    /// non-negative, symmetric, doubly stochastic and has a positive
+    /// spectral gap.
+    pub fn supports_consensus(&self) -> bool {
+        self.nonnegative
+            && self.symmetric
+            && self.doubly_stochastic
+            && self.spectral_gap > 0.0
+    }
+}
+
+/// Whether every row of `mixing` sums to one within `tol`.
+pub fn is_row_stochastic(mixing: &DMatrix<f64>, tol: f64) -> bool {
+    first_bad_row(mixing, tol).is_none()
+}
+
+/// Whether every column of `mixing` sums to one within `tol`.
+pub fn is_column_stochastic(mixing: &DMatrix<f64>, tol: f64) -> bool {
+    first_bad_column(mixing, tol).is_none()
+}
+
+/// Whether `mixing` is both row and column stochastic within `tol`.
+pub fn is_doubly_stochastic(mixing: &DMatrix<f64>, tol: f64) -> bool {
+    is_row_stochastic(mixing, tol) && is_column_stochastic(mixing, tol)
+}
+
+/// Check every property of a square mixing matrix.
+///
+/// `tol` applies to the row / column sums; symmetry uses
+/// [`SYMMETRY_TOLERANCE`].
+pub fn mixing_properties(
+    mixing: &DMatrix<f64>,
+    tol: f64,
+) -> Result<MixingProperties, GraphError> {
+    let (rows, cols) = mixing.shape();
+    if rows != cols {
+        return Err(GraphError::NotSquare { rows, cols });
+    }
+    if rows == 0 {
+        return Err(GraphError::Empty);
+    }
+
+    let row_stochastic = is_row_stochastic(mixing, tol);
+    let column_stochastic = is_column_stochastic(mixing, tol);
+    let slem = second_largest_modulus(mixing)?;
+    Ok(MixingProperties {
+        nonnegative: mixing.iter().all(|&w| w >= 0.0),
+        row_stochastic,
+        column_stochastic,
+        doubly_stochastic: row_stochastic && column_stochastic,
+        symmetric: is_symmetric(mixing, SYMMETRY_TOLERANCE),
+        slem,
+        spectral_gap: 1.0 - slem,
+    })
+}
+
+/// Validate the assumptions of DGD convergence, reporting the first
+/// violation.
+pub fn validate_consensus(mixing: &DMatrix<f64>, tol: f64) -> Result<(), GraphError> {
+    let (rows, cols) = mixing.shape();
+    if rows != cols {
+        return Err(GraphError::NotSquare { rows, cols });
+    }
+    if rows == 0 {
+        return Err(GraphError::Empty);
+    }
+    for col in 0..cols {
+        for row in 0..rows {
+            let weight = mixing[(row, col)];
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(GraphError::InvalidWeight { row, col, weight });
+            }
+        }
+    }
+    if !is_symmetric(mixing, SYMMETRY_TOLERANCE) {
+        return Err(GraphError::NotSymmetric);
+    }
+    if let Some((row, sum)) = first_bad_row(mixing, tol) {
+        return Err(GraphError::NotRowStochastic { row, sum });
+    }
+    if let Some((col, sum)) = first_bad_column(mixing, tol) {
+        return Err(GraphError::NotColumnStochastic { col, sum });
+    }
+    let slem = second_largest_modulus(mixing)?;
+    if slem >= 1.0 - tol {
+        return Err(GraphError::NoSpectralGap { slem });
+    }
+    Ok(())
+}
+
+fn first_bad_row(mixing: &DMatrix<f64>, tol: f64) -> Option<(usize, f64)> {
+    mixing
+        .row_iter()
+        .map(|row| row.sum())
+        .enumerate()
+        .find(|(_, sum)| (sum - 1.0).abs() > tol)
+}
+
+fn first_bad_column(mixing: &DMatrix<f64>, tol: f64) -> Option<(usize, f64)> {
+    mixing
+        .column_iter()
+        .map(|col| col.sum())
+        .enumerate()
+        .find(|(_, sum)| (sum - 1.0).abs() > tol)
+}
@@ -0,0 +1,87 @@
+//! Spectral quantities of communication graphs and mixing matrices.
+//!
+//! The convergence rate of consensus methods is governed by two numbers:
+//!
+//! - the *algebraic connectivity* `λ₂(L)`, second-smallest eigenvalue of
+//!   the graph Laplacian, which is positive iff the graph is connected;
+//! - the *spectral gap* `1 − |λ₂(W)|` of the mixing matrix, where
+//!   `|λ₂(W)|` is its second-largest eigenvalue modulus (SLEM).  Disagreement
+//!   contracts by a factor `|λ₂(W)|` per round.
+
+/// Stochasticity and symmetry checks for mixing matrices.
+pub mod mixing;
+
+pub use mixing::{
+    MixingProperties, is_column_stochastic, is_doubly_stochastic, is_row_stochastic,
+    mixing_properties, validate_consensus,
+};
+
+use crate::{
+    errors::GraphError,
+    graph::{Graph, is_symmetric},
+};
+use nalgebra::{DMatrix, SymmetricEigen};
+
+/// Tolerance used by the symmetry checks of this module.
+pub const SYMMETRY_TOLERANCE: f64 = 1e-9;
+
+/// Eigenvalues of a symmetric matrix, in ascending order.
+pub fn symmetric_eigenvalues(m: &DMatrix<f64>) -> Result<Vec<f64>, GraphError> {
+    let (rows, cols) = m.shape();
+    if rows != cols {
+        return Err(GraphError::NotSquare { rows, cols });
+    }
+    if !is_symmetric(m, SYMMETRY_TOLERANCE) {
+        return Err(GraphError::NotSymmetric);
+    }
+    let mut values: Vec<f64> = SymmetricEigen::new(m.clone())
+        .eigenvalues
+        .iter()
+        .copied()
+        .collect();
+    values.sort_by(f64::total_cmp);
+    Ok(values)
+}
+
+/// Eigenvalue moduli of a square matrix, in descending order.
+pub fn eigenvalue_moduli(m: &DMatrix<f64>) -> Result<Vec<f64>, GraphError> {
+    let (rows, cols) = m.shape();
+    if rows != cols {
+        return Err(GraphError::NotSquare { rows, cols });
+    }
+    let mut moduli: Vec<f64> = if is_symmetric(m, SYMMETRY_TOLERANCE) {
+        symmetric_eigenvalues(m)?.iter().map(|v| v.abs()).collect()
+    } else {
+        m.complex_eigenvalues().iter().map(|c| c.norm()).collect()
+    };
+    moduli.sort_by(|a, b| b.total_cmp(a));
+    Ok(moduli)
+}
+
+/// Laplacian eigenvalues of an undirected graph, in ascending order.
+pub fn laplacian_spectrum(graph: &Graph) -> Result<Vec<f64>, GraphError> {
+    symmetric_eigenvalues(&graph.laplacian())
+}
+
+/// Algebraic connectivity `λ₂(L)` of an undirected graph.
+///
+/// Zero for a single node and for disconnected graphs.
+pub fn algebraic_connectivity(graph: &Graph) -> Result<f64, GraphError> {
+    let spectrum = laplacian_spectrum(graph)?;
+    Ok(spectrum.get(1).copied().unwrap_or(0.0).max(0.0))
+}
+
+/// Second-largest eigenvalue modulus `|λ₂(W)|` of a mixing matrix.
+///
+/// Zero for a single node.
+pub fn second_largest_modulus(mixing: &DMatrix<f64>) -> Result<f64, GraphError> {
+    Ok(eigenvalue_moduli(mixing)?.get(1).copied().unwrap_or(0.0))
+}
+
+/// Spectral gap `1 − |λ₂(W)|` of a mixing matrix.
+///
+/// Positive for the mixing matrix of a connected graph with
+/// self-loops; zero or negative values mean consensus is not guaranteed.
+pub fn spectral_gap(mixing: &DMatrix<f64>) -> Result<f64, GraphError> {
+    Ok(1.0 - second_largest_modulus(mixing)?)
+}
@@ -0,0 +1,222 @@
+//! Mixing-matrix weight schemes.
+//!
+//! A scheme turns the edge pattern of an undirected [`Graph`] into a
+//! symmetric, doubly-stochastic mixing matrix `W` with `W_ij > 0` only for
+//! linked nodes and self-loops.  Edge weights of the input graph are
+//! ignored; only its (symmetrised) support is used.
+
+use crate::{errors::GraphError, graph::Graph, spectral::symmetric_eigenvalues};
+use convective_data::datasets::types::topology::TopologyConfig;
+use nalgebra::{DMatrix, SymmetricEigen};
+use serde::Deserialize;
+
+/// Default number of subgradient iterations of
+/// [`WeightScheme::FastestMixing`].
+pub const FASTEST_MIXING_ITERATIONS: usize = 200;
+
+/// How edge weights of a mixing matrix are chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum WeightScheme {
+    /// `W_ij = 1 / (1 + max(d_i, d_j))` for every edge.
+    MetropolisHastings,
+    /// `W_ij = 1 / (1 + d_max)` for every edge.
+    MaxDegree,
+    /// `(I + W_MH) / 2`: Metropolis–Hastings with extra self-weight, whose
+    /// eigenvalues are all non-negative.
+    LazyMetropolis,
+    /// Approximate fastest-mixing weights (Boyd, Diaconis & Xiao, 2004):
+    /// projected subgradient descent on the second-largest eigenvalue
+    /// modulus, starting from Metropolis–Hastings.
+    FastestMixing { iterations: usize },
+}
+
+impl WeightScheme {
+    /// [`FastestMixing`](WeightScheme::FastestMixing) with
+    /// [`FASTEST_MIXING_ITERATIONS`] iterations.
+    pub fn fastest_mixing() -> Self {
+        WeightScheme::FastestMixing {
+            iterations: FASTEST_MIXING_ITERATIONS,
+        }
+    }
+
+    /// Mixing matrix of `graph` under this scheme.
+    pub fn mixing_matrix(&self, graph: &Graph) -> DMatrix<f64> {
+        match *self {
+            WeightScheme::MetropolisHastings => metropolis_hastings(graph),
+            WeightScheme::MaxDegree => max_degree(graph),
+            WeightScheme::LazyMetropolis => lazy(&metropolis_hastings(graph)),
+            WeightScheme::FastestMixing { iterations } => {
+                fastest_mixing(graph, iterations)
+            }
+        }
+    }
+
+    /// Weighted graph of `graph` under this scheme.
+    pub fn apply(&self, graph: &Graph) -> Result<Graph, GraphError> {
+        Graph::from_matrix(self.mixing_matrix(graph))
+    }
+
+    /// Mixing matrix of `graph` in the layout of a `[[topology]]` table.
+    pub fn topology(
+        &self,
+        graph: &Graph,
+        id: &str,
+    ) -> Result<TopologyConfig, GraphError> {
+        Ok(self.apply(graph)?.to_topology(id))
+    }
+}
+
+/// Metropolis–Hastings weights `W_ij = 1 / (1 + max(d_i, d_j))`.
+pub fn metropolis_hastings(graph: &Graph) -> DMatrix<f64> {
+    let edges = undirected_edges(graph);
+    let degrees = edge_degrees(graph.node_count(), &edges);
+    let weights: Vec<f64> = edges
+        .iter()
+        .map(|&(i, j)| 1.0 / (1.0 + degrees[i].max(degrees[j]) as f64))
+        .collect();
+    from_edge_weights(graph.node_count(), &edges, &weights)
+}
+
+/// Max-degree weights `W_ij = 1 / (1 + d_max)`.
+pub fn max_degree(graph: &Graph) -> DMatrix<f64> {
+    let edges = undirected_edges(graph);
+    let degrees = edge_degrees(graph.node_count(), &edges);
+    let d_max = degrees.iter().copied().max().unwrap_or(0);
+    let weights = vec![1.0 / (1.0 + d_max as f64); edges.len()];
+    from_edge_weights(graph.node_count(), &edges, &weights)
+}
+
+/// Lazy version `(I + W) / 2` of a mixing matrix.
+pub fn lazy(mixing: &DMatrix<f64>) -> DMatrix<f64> {
+    let n = mixing.nrows();
+    (DMatrix::identity(n, n) + mixing) * 0.5
+}
+
+/// Approximate fastest-mixing weights.
+///
+/// Writes `W = I − Σ_l w_l (e_i − e_j)(e_i − e_j)ᵀ` over the edges
+/// `l = (i, j)` and minimises `max(λ₂(W), −λₙ(W))` with projected
+/// subgradient steps of size `1 / √(k + 1)`, keeping `w ≥ 0` and
+/// `Σ_{l∋i} w_l ≤ 1`.  Returns the best iterate, which is never worse than
+/// the Metropolis–Hastings start.
+pub fn fastest_mixing(graph: &Graph, iterations: usize) -> DMatrix<f64> {
+    let n = graph.node_count();
+    let edges = undirected_edges(graph);
+    let degrees = edge_degrees(n, &edges);
+    let mut weights: Vec<f64> = edges
+        .iter()
+        .map(|&(i, j)| 1.0 / (1.0 + degrees[i].max(degrees[j]) as f64))
+        .collect();
+
+    let mut best = from_edge_weights(n, &edges, &weights);
+    let mut best_slem = slem(&best);
+    if n < 2 || edges.is_empty() {
+        return best;
+    }
+
+    for k in 0..iterations {
+        let mixing = from_edge_weights(n, &edges, &weights);
+        let eigen = SymmetricEigen::new(mixing);
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| eigen.eigenvalues[b].total_cmp(&eigen.eigenvalues[a]));
+        let (second, last) = (order[1], order[n - 1]);
+
+        // Subgradient of λ₂ is −(u_i − u_j)², of −λₙ is +(u_i − u_j)².
+        let (index, sign) = if eigen.eigenvalues[second] >= -eigen.eigenvalues[last] {
+            (second, -1.0)
+        } else {
+            (last, 1.0)
+        };
+        let u = eigen.eigenvectors.column(index);
+        let gradient: Vec<f64> = edges
+            .iter()
+            .map(|&(i, j)| sign * (u[i] - u[j]).powi(2))
+            .collect();
+        let norm = gradient.iter().map(|g| g * g).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            break;
+        }
+
+        let step = 1.0 / ((k + 1) as f64).sqrt();
+        for (w, g) in weights.iter_mut().zip(&gradient) {
+            *w = (*w - step * g / norm).max(0.0);
+        }
+        project_node_sums(n, &edges, &mut weights);
+
+        let candidate = from_edge_weights(n, &edges, &weights);
+        let candidate_slem = slem(&candidate);
+        if candidate_slem < best_slem {
+            best = candidate;
+            best_slem = candidate_slem;
+        }
+    }
+    best
+}
+
+/// Scale the edges of every node whose incident weights exceed one.
+///
+/// Scaling only lowers weights, so a single pass leaves every node
+/// feasible.
+fn project_node_sums(n: usize, edges: &[(usize, usize)], weights: &mut [f64]) {
+    for node in 0..n {
+        let sum: f64 = edges
+            .iter()
+            .zip(weights.iter())
+            .filter(|&(&(i, j), _)| i == node || j == node)
+            .map(|(_, w)| w)
+            .sum();
+        if sum > 1.0 {
+            for (&(i, j), w) in edges.iter().zip(weights.iter_mut()) {
+                if i == node || j == node {
+                    *w /= sum;
+                }
+            }
+        }
+    }
+}
+
+/// Second-largest eigenvalue modulus of a symmetric matrix.
+fn slem(mixing: &DMatrix<f64>) -> f64 {
+    let values = symmetric_eigenvalues(mixing).unwrap_or_default();
+    match values.len() {
+        0 | 1 => 0.0,
+        n => values[n - 2].abs().max(values[0].abs()),
+    }
+}
+
+/// Pairs `i < j` linked in either direction.
+fn undirected_edges(graph: &Graph) -> Vec<(usize, usize)> {
+    let n = graph.node_count();
+    let w = graph.weights();
+    (0..n)
+        .flat_map(|i| ((i + 1)..n).map(move |j| (i, j)))
+        .filter(|&(i, j)| w[(i, j)] > 0.0 || w[(j, i)] > 0.0)
+        .collect()
+}
+
+fn edge_degrees(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut degrees = vec![0; n];
+    for &(i, j) in edges {
+        degrees[i] += 1;
+        degrees[j] += 1;
+    }
+    degrees
+}
+
+/// Symmetric matrix with the given edge weights and `W_ii = 1 − Σ_j W_ij`.
+fn from_edge_weights(
+    n: usize,
+    edges: &[(usize, usize)],
+    weights: &[f64],
+) -> DMatrix<f64> {
+    let mut mixing = DMatrix::zeros(n, n);
+    for (&(i, j), &w) in edges.iter().zip(weights) {
+        mixing[(i, j)] = w;
+        mixing[(j, i)] = w;
+    }
+    for i in 0..n {
+        // Guard against `-1e-17` round-off on rows whose edges sum to one.
+        mixing[(i, i)] = (1.0 - mixing.row(i).sum()).max(0.0);
+    }
+    mixing
+}
@@ -6,8 +6,8 @@
 //! For the torch backend (requires libtorch):
 //!   cargo run --example distributed_case_1_train --features torch
 
-use atelier_data::datasets;
-use atelier_dcml::{
+use convective_data::datasets;
+use convective_ml::{
     ComputeBackend, Model, TorchBackend, functions, models, optimizers, processes,
 };
 use std::error::Error;
@@ -52,7 +52,7 @@ fn main() -> Result<(), Box<dyn Error + 'static>> {
     // --- Initialise tracing (optional, logs to stdout) ---
     tracing_subscriber::fmt::init();
 
-    println!("\n=== atelier-dcml : singular training (TorchBackend) ===\n");
+    println!("\n=== convective-ml : singular training (TorchBackend) ===\n");
 
     // --- Step 1: Synthetic data ---
     let n_samples = 200;
@@ -65,7 +65,7 @@ fn main() -> Result<(), Box<dyn Error + 'static>> {
     );
 
     // --- Step 2: Model ---
-    let model = models::LinearModel::<atelier_dcml::TorchBackend>::builder(n_features)
+    let model = models::LinearModel::<convective_ml::TorchBackend>::builder(n_features)
         .id("model_00".to_string())
         .glorot_uniform_init();
 
@@ -89,7 +89,7 @@ fn main() -> Result<(), Box<dyn Error + 'static>> {
     println!("Step 4: loss     id={:?}\n", loss.id);
 
     // --- Step 5: Build trainer & run ---
-    let mut trainer = processes::Singular::<atelier_dcml::TorchBackend>::builder()
+    let mut trainer = processes::Singular::<convective_ml::TorchBackend>::builder()
         .dataset(dataset)
         .model(model)
         .loss(loss)
@@ -102,18 +102,17 @@ fn main() -> Result<(), Box<dyn Error + 'static>> {
     trainer.train(epochs)?;
 
     // --- Step 6: Save model ---
-    let model_path = "/tmp/atelier_singular_model.json";
+    let model_path = "/tmp/convective_singular_model.json";
     trainer.save_model(model_path)?;
     println!("\nStep 6: model saved to {model_path}");
 
     // --- Step 7: Quick inference sanity check ---
     let model_ref = trainer.model();
     let sample_features = TorchBackend::from_row_vecs(&[vec![0.5; n_features]]);
-    let logits = model_ref.forward(&sample_features);
+    let prob = model_ref.predict_proba(&sample_features)[0][1];
+    let label = model_ref.predict(&sample_features, 0.5)[0];
 
-    let prob = 1.0 / (1.0 + -logits.exp());
-
-    println!("Step 7: inference  logit={:.4}  p={:.4}", logits, prob);
+    println!("Step 7: inference  p={prob:.4}  label={label}");
 
     println!("\n=== done ===");
     Ok(())
@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AnalysisError {
+    #[error("Empty feature matrix")]
+    EmptyInput,
+
+    #[error("Length mismatch: expected {expected}, got {got}")]
+    LengthMismatch { expected: usize, got: usize },
+
+    #[error("Invalid configuration: {message}")]
+    InvalidConfig { message: String },
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
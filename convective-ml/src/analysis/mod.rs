@@ -0,0 +1,359 @@
+//! Feature quality analytics.
+//!
+//! Given a feature matrix and one or more label vectors (e.g. from
+//! [`labels`](crate::labels) at several horizons),
+//! [`analyze`](crate::analysis::analyze) reports per feature:
+//!
+//! - **IC**: Spearman rank correlation with the labels at each horizon.
+//! - **IC stability**: mean, dispersion and information ratio of the IC
+//!   computed over rolling windows.
+//! - **Mutual information** with the labels (equal-frequency binning), which
+//!   also picks up non-monotonic relationships.
+//! - **Missing / zero rates**: share of non-finite values, and of exact
+//!   zeros (what `compute_all_features` emits for unavailable sources).
+//!
+//! and, across features, the Spearman correlation matrix with clusters of
+//! redundant features.  Reports export to JSON and CSV.
+
+pub mod errors;
+pub mod stats;
+
+pub use errors::AnalysisError;
+
+use serde::{Deserialize, Serialize};
+
+// ---------------------------------------------------------------------------
+// Inputs
+// ---------------------------------------------------------------------------
+
+/// Labels for one horizon, aligned with the feature matrix rows.
+#[derive(Debug, Clone)]
+pub struct HorizonLabels {
+    pub horizon: usize,
+    pub values: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnalysisConfig {
+    /// Rows per rolling IC window.
+    pub ic_window: usize,
+    /// Rows between the starts of consecutive IC windows.
+    pub ic_step: usize,
+    /// `|ρ|` at or above which two features are considered redundant.
+    pub redundancy_threshold: f64,
+    /// Number of equal-frequency bins for mutual information.
+    pub mi_bins: usize,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self {
+            ic_window: 250,
+            ic_step: 50,
+            redundancy_threshold: 0.9,
+            mi_bins: 10,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Report
+// ---------------------------------------------------------------------------
+
+/// Rolling IC summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IcStability {
+    pub windows: usize,
+    pub mean: Option<f64>,
+    pub std: Option<f64>,
+    /// `mean / std` of the rolling IC.
+    pub information_ratio: Option<f64>,
+    /// Share of windows whose IC has the same sign as the full-sample IC.
+    pub sign_consistency: Option<f64>,
+}
+
+/// Statistics of one feature against the labels of one horizon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizonStats {
+    pub horizon: usize,
+    /// Rows where both feature and label are available.
+    pub samples: usize,
+    pub ic: Option<f64>,
+    pub stability: IcStability,
+    pub mutual_information: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureStats {
+    pub name: String,
+    pub missing_rate: f64,
+    pub zero_rate: f64,
+    pub horizons: Vec<HorizonStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureReport {
+    pub features: Vec<FeatureStats>,
+    /// Pairwise Spearman correlation, indexed like `features`.
+    pub correlation: Vec<Vec<Option<f64>>>,
+    /// Groups of mutually redundant features (single linkage on `|ρ|`).
+    pub clusters: Vec<Vec<String>>,
+}
+
+/// Flat CSV row: one per feature and horizon.
+#[derive(Debug, Serialize)]
+struct CsvRow<'a> {
+    feature: &'a str,
+    horizon: usize,
+    samples: usize,
+    ic: Option<f64>,
+    ic_mean: Option<f64>,
+    ic_std: Option<f64>,
+    ic_ir: Option<f64>,
+    ic_sign_consistency: Option<f64>,
+    mutual_information: f64,
+    missing_rate: f64,
+    zero_rate: f64,
+    cluster: usize,
+}
+
+impl FeatureReport {
+    /// Write the full report as JSON.
+    pub fn to_json(&self, path: &str) -> Result<(), AnalysisError> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Write the per-feature / per-horizon table as CSV.
+    pub fn to_csv(&self, path: &str) -> Result<(), AnalysisError> {
+        let mut writer = csv::Writer::from_path(path)?;
+        for f in &self.features {
+            let cluster = self
+                .clusters
+                .iter()
+                .position(|c| c.contains(&f.name))
+                .unwrap_or(0);
+            for h in &f.horizons {
+                writer.serialize(CsvRow {
+                    feature: &f.name,
+                    horizon: h.horizon,
+                    samples: h.samples,
+                    ic: h.ic,
+                    ic_mean: h.stability.mean,
+                    ic_std: h.stability.std,
+                    ic_ir: h.stability.information_ratio,
+                    ic_sign_consistency: h.stability.sign_consistency,
+                    mutual_information: h.mutual_information,
+                    missing_rate: f.missing_rate,
+                    zero_rate: f.zero_rate,
+                    cluster,
+                })?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the correlation matrix as CSV (header row = feature names).
+    pub fn correlation_to_csv(&self, path: &str) -> Result<(), AnalysisError> {
+        let mut writer = csv::Writer::from_path(path)?;
+        let names: Vec<&str> = self.features.iter().map(|f| f.name.as_str()).collect();
+
+        let mut header = vec![""];
+        header.extend(&names);
+        writer.write_record(&header)?;
+
+        for (name, row) in names.iter().zip(&self.correlation) {
+            let mut record = vec![name.to_string()];
+            record.extend(
+                row.iter()
+                    .map(|c| c.map_or(String::new(), |c| c.to_string())),
+            );
+            writer.write_record(&record)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Analysis
+// ---------------------------------------------------------------------------
+
+/// Build a [`FeatureReport`] for `features` (rows × columns named `names`).
+pub fn analyze(
+    features: &[Vec<f64>],
+    names: &[&str],
+    labels: &[HorizonLabels],
+    config: &AnalysisConfig,
+) -> Result<FeatureReport, AnalysisError> {
+    if features.is_empty() {
+        return Err(AnalysisError::EmptyInput);
+    }
+    if config.ic_window < 3 || config.ic_step == 0 || config.mi_bins < 2 {
+        return Err(AnalysisError::InvalidConfig {
+            message: "requires ic_window >= 3, ic_step >= 1 and mi_bins >= 2".to_string(),
+        });
+    }
+
+    let n = features.len();
+    let m = names.len();
+    if let Some(row) = features.iter().find(|row| row.len() != m) {
+        return Err(AnalysisError::LengthMismatch {
+            expected: m,
+            got: row.len(),
+        });
+    }
+    if let Some(h) = labels.iter().find(|h| h.values.len() != n) {
+        return Err(AnalysisError::LengthMismatch {
+            expected: n,
+            got: h.values.len(),
+        });
+    }
+
+    let columns: Vec<Vec<f64>> = (0..m)
+        .map(|j| features.iter().map(|row| row[j]).collect())
+        .collect();
+
+    let stats = columns
+        .iter()
+        .zip(names)
+        .map(|(col, name)| FeatureStats {
+            name: name.to_string(),
+            missing_rate: col.iter().filter(|v| !v.is_finite()).count() as f64 / n as f64,
+            zero_rate: col.iter().filter(|&&v| v == 0.0).count() as f64 / n as f64,
+            horizons: labels
+                .iter()
+                .map(|h| horizon_stats(col, h, config))
+                .collect(),
+        })
+        .collect();
+
+    let correlation = correlation_matrix(&columns);
+    let clusters = redundancy_clusters(&correlation, config.redundancy_threshold)
+        .into_iter()
+        .map(|c| c.into_iter().map(|j| names[j].to_string()).collect())
+        .collect();
+
+    Ok(FeatureReport {
+        features: stats,
+        correlation,
+        clusters,
+    })
+}
+
+/// Rows where both the feature and the label are usable, in order.
+fn paired(feature: &[f64], labels: &[Option<f64>]) -> (Vec<f64>, Vec<f64>) {
+    feature
+        .iter()
+        .zip(labels)
+        .filter_map(|(&x, y)| match y {
+            Some(y) if x.is_finite() && y.is_finite() => Some((x, *y)),
+            _ => None,
+        })
+        .unzip()
+}
+
+fn horizon_stats(
+    feature: &[f64],
+    labels: &HorizonLabels,
+    config: &AnalysisConfig,
+) -> HorizonStats {
+    let (x, y) = paired(feature, &labels.values);
+    let ic = stats::spearman(&x, &y);
+
+    let rolling: Vec<f64> = (0..x.len().saturating_sub(config.ic_window - 1))
+        .step_by(config.ic_step)
+        .filter_map(|s| {
+            let e = s + config.ic_window;
+            stats::spearman(&x[s..e], &y[s..e])
+        })
+        .collect();
+
+    let stability = if rolling.is_empty() {
+        IcStability {
+            windows: 0,
+            mean: None,
+            std: None,
+            information_ratio: None,
+            sign_consistency: None,
+        }
+    } else {
+        let (mean, std) = stats::mean_std(&rolling);
+        let sign = ic.map_or(0.0, f64::signum);
+        let agree = rolling.iter().filter(|r| r.signum() == sign).count();
+        IcStability {
+            windows: rolling.len(),
+            mean: Some(mean),
+            std: Some(std),
+            information_ratio: (std > 0.0).then(|| mean / std),
+            sign_consistency: ic.map(|_| agree as f64 / rolling.len() as f64),
+        }
+    };
+
+    HorizonStats {
+        horizon: labels.horizon,
+        samples: x.len(),
+        ic,
+        stability,
+        mutual_information: stats::mutual_information(&x, &y, config.mi_bins),
+    }
+}
+
+fn correlation_matrix(columns: &[Vec<f64>]) -> Vec<Vec<Option<f64>>> {
+    let m = columns.len();
+    let mut corr = vec![vec![None; m]; m];
+    for i in 0..m {
+        for j in i..m {
+            let (x, y): (Vec<f64>, Vec<f64>) = columns[i]
+                .iter()
+                .zip(&columns[j])
+                .filter(|(a, b)| a.is_finite() && b.is_finite())
+                .map(|(a, b)| (*a, *b))
+                .unzip();
+            let rho = if i == j {
+                Some(1.0)
+            } else {
+                stats::spearman(&x, &y)
+            };
+            corr[i][j] = rho;
+            corr[j][i] = rho;
+        }
+    }
+    corr
+}
+
+/// Connected components of the graph `|ρᵢⱼ| ≥ threshold`.
+fn redundancy_clusters(corr: &[Vec<Option<f64>>], threshold: f64) -> Vec<Vec<usize>> {
+    let m = corr.len();
+    let mut parent: Vec<usize> = (0..m).collect();
+
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for (i, row) in corr.iter().enumerate() {
+        for (j, rho) in row.iter().enumerate().skip(i + 1) {
+            if rho.is_some_and(|r| r.abs() >= threshold) {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut index = vec![usize::MAX; m];
+    for i in 0..m {
+        let r = root(&mut parent, i);
+        if index[r] == usize::MAX {
+            index[r] = clusters.len();
+            clusters.push(Vec::new());
+        }
+        clusters[index[r]].push(i);
+    }
+    clusters
+}
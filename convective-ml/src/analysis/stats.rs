@@ -0,0 +1,118 @@
+//! Statistical primitives used by the feature reports.
+
+/// Average ranks (1-based) with ties sharing their mean rank.
+pub fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut idx: Vec<usize> = (0..values.len()).collect();
+    idx.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut out = vec![0.0; values.len()];
+    let mut i = 0;
+    while i < idx.len() {
+        let mut j = i;
+        while j + 1 < idx.len() && values[idx[j + 1]] == values[idx[i]] {
+            j += 1;
+        }
+        let rank = (i + j) as f64 / 2.0 + 1.0;
+        for &k in &idx[i..=j] {
+            out[k] = rank;
+        }
+        i = j + 1;
+    }
+    out
+}
+
+/// Pearson correlation; `None` if either input is constant or too short.
+pub fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+    let n = x.len().min(y.len());
+    if n < 2 {
+        return None;
+    }
+    let nf = n as f64;
+    let mx = x[..n].iter().sum::<f64>() / nf;
+    let my = y[..n].iter().sum::<f64>() / nf;
+
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (a, b) in x.iter().zip(y) {
+        let (dx, dy) = (a - mx, b - my);
+        sxy += dx * dy;
+        sxx += dx * dx;
+        syy += dy * dy;
+    }
+
+    if sxx > 0.0 && syy > 0.0 {
+        Some(sxy / (sxx * syy).sqrt())
+    } else {
+        None
+    }
+}
+
+/// Spearman rank correlation.
+pub fn spearman(x: &[f64], y: &[f64]) -> Option<f64> {
+    pearson(&ranks(x), &ranks(y))
+}
+
+/// Equal-frequency bin index of each value (`bins` quantile buckets).
+///
+/// Variables with at most `bins` distinct values are treated as discrete
+/// and keep one bucket per value.
+pub fn discretize(values: &[f64], bins: usize) -> Vec<usize> {
+    let mut distinct: Vec<f64> = values.to_vec();
+    distinct.sort_by(f64::total_cmp);
+    distinct.dedup();
+
+    if distinct.len() <= bins {
+        return values
+            .iter()
+            .map(|v| distinct.partition_point(|d| d < v))
+            .collect();
+    }
+
+    let n = values.len() as f64;
+    ranks(values)
+        .into_iter()
+        .map(|r| (((r - 1.0) / n * bins as f64) as usize).min(bins - 1))
+        .collect()
+}
+
+/// Mutual information (nats) between two discretised variables.
+pub fn mutual_information(x: &[f64], y: &[f64], bins: usize) -> f64 {
+    let n = x.len().min(y.len());
+    if n == 0 || bins == 0 {
+        return 0.0;
+    }
+    let bx = discretize(&x[..n], bins);
+    let by = discretize(&y[..n], bins);
+    let kx = bx.iter().max().map_or(0, |m| m + 1);
+    let ky = by.iter().max().map_or(0, |m| m + 1);
+
+    let mut joint = vec![vec![0.0_f64; ky]; kx];
+    let mut px = vec![0.0_f64; kx];
+    let mut py = vec![0.0_f64; ky];
+    let w = 1.0 / n as f64;
+    for (&i, &j) in bx.iter().zip(&by) {
+        joint[i][j] += w;
+        px[i] += w;
+        py[j] += w;
+    }
+
+    let mut mi = 0.0;
+    for (i, row) in joint.iter().enumerate() {
+        for (j, &pij) in row.iter().enumerate() {
+            if pij > 0.0 {
+                mi += pij * (pij / (px[i] * py[j])).ln();
+            }
+        }
+    }
+    mi.max(0.0)
+}
+
+/// Mean and population standard deviation.
+pub fn mean_std(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (f64::NAN, f64::NAN);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, var.sqrt())
+}
@@ -0,0 +1,72 @@
+//! Tick and volume imbalance bars.
+//!
+//! A bar closes at the first trade `T` such that
+//!
+//! ```text
+//! |θ_T| = |Σₜ bₜ vₜ| ≥ E[T] · |E[b v]|
+//! ```
+//!
+//! where `bₜ` is the aggressor sign and `vₜ` the trade measure.  `E[T]` is an
+//! EWMA of past bar lengths and `E[b v]` an EWMA of the signed measure over
+//! trades.
+
+use super::{AdaptiveConfig, BarMeasure, BarRule};
+use atelier_data::trades::Trade;
+
+#[derive(Debug, Clone)]
+pub struct ImbalanceRule {
+    measure: BarMeasure,
+    config: AdaptiveConfig,
+    /// EWMA of bar lengths, `E[T]`.
+    expected_ticks: f64,
+    /// EWMA of the signed measure, `E[b v]`.
+    expected_imbalance: Option<f64>,
+    /// Signed flow of the current bar, `θ`.
+    theta: f64,
+    ticks: usize,
+}
+
+impl ImbalanceRule {
+    pub fn new(measure: BarMeasure, config: AdaptiveConfig) -> Self {
+        Self {
+            measure,
+            expected_ticks: config.expected_ticks,
+            config,
+            expected_imbalance: None,
+            theta: 0.0,
+            ticks: 0,
+        }
+    }
+
+    /// Current closing threshold `E[T] · |E[b v]|`.
+    pub fn threshold(&self) -> f64 {
+        self.expected_ticks * self.expected_imbalance.unwrap_or(0.0).abs()
+    }
+}
+
+impl BarRule for ImbalanceRule {
+    fn update(&mut self, trade: &Trade, sign: f64) -> bool {
+        let signed = sign * self.measure.of(trade);
+        self.theta += signed;
+        self.ticks += 1;
+
+        let alpha = 2.0 / (self.config.ticks_span as f64 + 1.0);
+        self.expected_imbalance = Some(match self.expected_imbalance {
+            Some(prev) => alpha * signed + (1.0 - alpha) * prev,
+            None => signed,
+        });
+
+        if self.config.max_ticks.is_some_and(|max| self.ticks >= max) {
+            return true;
+        }
+        self.ticks >= self.config.min_ticks && self.theta.abs() >= self.threshold()
+    }
+
+    fn reset(&mut self) {
+        let alpha = 2.0 / (self.config.bars_span as f64 + 1.0);
+        self.expected_ticks =
+            alpha * self.ticks as f64 + (1.0 - alpha) * self.expected_ticks;
+        self.theta = 0.0;
+        self.ticks = 0;
+    }
+}
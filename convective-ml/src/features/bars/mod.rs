@@ -0,0 +1,268 @@
+//! Information-driven bars.
+//!
+//! Fixed-time snapshots sample the market at a constant clock rate, which
+//! over-samples quiet periods and under-samples bursts of activity.  The
+//! bars in this module (López de Prado, *Advances in Financial Machine
+//! Learning*, ch. 2) instead close whenever a measure of trading activity
+//! crosses a threshold:
+//!
+//! - **Standard bars**: a fixed number of trades (tick), traded amount
+//!   (volume) or traded notional (dollar).
+//! - **Imbalance bars**: the signed flow `θ = Σ bₜ vₜ` exceeds its expected
+//!   value `E[T] · |E[b v]|`.
+//! - **Run bars**: the longest one-sided run `θ = max(Σ buys, Σ sells)`
+//!   exceeds `E[T] · max(E[v · 1{b=1}], E[v · 1{b=-1}])`.
+//!
+//! Imbalance and run thresholds are adaptive: `E[T]` and the flow
+//! expectations are exponentially-weighted averages updated as bars close.
+//!
+//! The sampler consumes a sequence of fine-grained [`MarketSnapshot`]s and
+//! re-emits them as coarser snapshots, one per bar, so the output plugs
+//! directly into [`compute_all_features`](super::compute_market::compute_all_features).
+
+pub mod imbalance;
+pub mod runs;
+pub mod standard;
+
+pub use imbalance::ImbalanceRule;
+pub use runs::RunRule;
+pub use standard::StandardRule;
+
+use crate::features::FeatureError;
+use atelier_data::{snapshot::MarketSnapshot, trades::Trade};
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Activity measure that a bar accumulates per trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarMeasure {
+    /// Every trade counts as 1.
+    Tick,
+    /// Trade amount (base units).
+    Volume,
+    /// Trade notional (price × amount).
+    Dollar,
+}
+
+impl BarMeasure {
+    /// Contribution `vₜ` of a single trade.
+    pub fn of(&self, trade: &Trade) -> f64 {
+        match self {
+            BarMeasure::Tick => 1.0,
+            BarMeasure::Volume => trade.amount,
+            BarMeasure::Dollar => trade.price * trade.amount,
+        }
+    }
+}
+
+/// Parameters of the adaptive thresholds used by imbalance and run bars.
+#[derive(Debug, Clone)]
+pub struct AdaptiveConfig {
+    /// Initial guess for the expected number of trades per bar, `E[T]`.
+    pub expected_ticks: f64,
+    /// Span of the EWMA used for `E[T]` (in bars).
+    pub bars_span: usize,
+    /// Span of the EWMA used for the flow expectations (in trades).
+    pub ticks_span: usize,
+    /// A bar never closes with fewer trades than this.
+    pub min_ticks: usize,
+    /// A bar always closes once it reaches this many trades.
+    pub max_ticks: Option<usize>,
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        Self {
+            expected_ticks: 100.0,
+            bars_span: 20,
+            ticks_span: 1_000,
+            min_ticks: 1,
+            max_ticks: None,
+        }
+    }
+}
+
+impl AdaptiveConfig {
+    fn validate(&self) -> Result<(), FeatureError> {
+        if self.expected_ticks <= 0.0 {
+            return Err(FeatureError::InvalidConfig {
+                message: "expected_ticks must be positive".to_string(),
+            });
+        }
+        if self.bars_span == 0 || self.ticks_span == 0 {
+            return Err(FeatureError::InvalidConfig {
+                message: "EWMA spans must be positive".to_string(),
+            });
+        }
+        if self
+            .max_ticks
+            .is_some_and(|max| max < self.min_ticks.max(1))
+        {
+            return Err(FeatureError::InvalidConfig {
+                message: "max_ticks must be >= min_ticks".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Bar sampling scheme.
+#[derive(Debug, Clone)]
+pub enum BarType {
+    /// Close every `threshold` trades.
+    Tick { threshold: usize },
+    /// Close once the traded amount reaches `threshold`.
+    Volume { threshold: f64 },
+    /// Close once the traded notional reaches `threshold`.
+    Dollar { threshold: f64 },
+    /// Tick imbalance bars (`vₜ = 1`).
+    TickImbalance(AdaptiveConfig),
+    /// Volume imbalance bars (`vₜ = amount`).
+    VolumeImbalance(AdaptiveConfig),
+    /// Tick run bars (`vₜ = 1`).
+    TickRun(AdaptiveConfig),
+    /// Volume run bars (`vₜ = amount`).
+    VolumeRun(AdaptiveConfig),
+}
+
+// ---------------------------------------------------------------------------
+// Rules
+// ---------------------------------------------------------------------------
+
+/// Per-trade closing rule of a bar.
+pub trait BarRule: std::fmt::Debug + Send {
+    /// Account for one trade with aggressor sign `sign ∈ {-1, +1}`.
+    ///
+    /// Returns `true` when the current bar must close after this trade.
+    fn update(&mut self, trade: &Trade, sign: f64) -> bool;
+
+    /// Reset per-bar accumulators after a bar closed.
+    fn reset(&mut self);
+}
+
+impl BarType {
+    /// Build the closing rule for this bar type.
+    pub fn rule(&self) -> Result<Box<dyn BarRule>, FeatureError> {
+        let rule: Box<dyn BarRule> = match self {
+            BarType::Tick { threshold } => {
+                Box::new(StandardRule::new(BarMeasure::Tick, *threshold as f64)?)
+            }
+            BarType::Volume { threshold } => {
+                Box::new(StandardRule::new(BarMeasure::Volume, *threshold)?)
+            }
+            BarType::Dollar { threshold } => {
+                Box::new(StandardRule::new(BarMeasure::Dollar, *threshold)?)
+            }
+            BarType::TickImbalance(cfg) => {
+                cfg.validate()?;
+                Box::new(ImbalanceRule::new(BarMeasure::Tick, cfg.clone()))
+            }
+            BarType::VolumeImbalance(cfg) => {
+                cfg.validate()?;
+                Box::new(ImbalanceRule::new(BarMeasure::Volume, cfg.clone()))
+            }
+            BarType::TickRun(cfg) => {
+                cfg.validate()?;
+                Box::new(RunRule::new(BarMeasure::Tick, cfg.clone()))
+            }
+            BarType::VolumeRun(cfg) => {
+                cfg.validate()?;
+                Box::new(RunRule::new(BarMeasure::Volume, cfg.clone()))
+            }
+        };
+        Ok(rule)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Trade signing
+// ---------------------------------------------------------------------------
+
+/// Aggressor sign of a trade.
+///
+/// Uses the exchange-reported side when available and falls back to the
+/// tick rule (sign of the price change, carrying the previous sign on
+/// unchanged prices) otherwise.
+#[derive(Debug, Clone)]
+pub struct TradeSigner {
+    prev_price: Option<f64>,
+    prev_sign: f64,
+}
+
+impl Default for TradeSigner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TradeSigner {
+    pub fn new() -> Self {
+        Self {
+            prev_price: None,
+            prev_sign: 1.0,
+        }
+    }
+
+    pub fn sign(&mut self, trade: &Trade) -> f64 {
+        let sign = match trade.side.as_str() {
+            "Buy" => 1.0,
+            "Sell" => -1.0,
+            _ => match self.prev_price {
+                Some(prev) if trade.price > prev => 1.0,
+                Some(prev) if trade.price < prev => -1.0,
+                _ => self.prev_sign,
+            },
+        };
+        self.prev_price = Some(trade.price);
+        self.prev_sign = sign;
+        sign
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sampling
+// ---------------------------------------------------------------------------
+
+/// Re-sample a snapshot sequence into information-driven bars.
+///
+/// Each emitted bar is a [`MarketSnapshot`] that carries:
+///
+/// - the orderbook, funding rate and open interest of the snapshot in
+///   which the bar closed (the latest known state),
+/// - every trade accumulated since the previous bar closed,
+/// - every liquidation from the snapshots spanned by the bar.
+///
+/// Trades left in an unfinished bar at the end of the sequence are
+/// dropped.
+pub fn sample_bars(
+    snapshots: &[MarketSnapshot],
+    bar_type: &BarType,
+) -> Result<Vec<MarketSnapshot>, FeatureError> {
+    let mut rule = bar_type.rule()?;
+    let mut signer = TradeSigner::new();
+
+    let mut bars = Vec::new();
+    let mut pending_trades: Vec<Trade> = Vec::new();
+    let mut pending_liqs = Vec::new();
+
+    for snap in snapshots {
+        pending_liqs.extend(snap.liquidations.iter().cloned());
+
+        for trade in &snap.trades {
+            let sign = signer.sign(trade);
+            pending_trades.push(trade.clone());
+
+            if rule.update(trade, sign) {
+                let mut bar = snap.clone();
+                bar.trades = std::mem::take(&mut pending_trades);
+                bar.liquidations = std::mem::take(&mut pending_liqs);
+                bars.push(bar);
+                rule.reset();
+            }
+        }
+    }
+
+    Ok(bars)
+}
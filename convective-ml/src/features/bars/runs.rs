@@ -0,0 +1,86 @@
+//! Tick and volume run bars.
+//!
+//! A bar closes at the first trade `T` such that
+//!
+//! ```text
+//! θ_T = max(Σ_{b=1} vₜ, Σ_{b=-1} vₜ)
+//!     ≥ E[T] · max(E[v · 1{b=1}], E[v · 1{b=-1}])
+//! ```
+//!
+//! i.e. when one side has been consistently more active than expected.
+
+use super::{AdaptiveConfig, BarMeasure, BarRule};
+use atelier_data::trades::Trade;
+
+#[derive(Debug, Clone)]
+pub struct RunRule {
+    measure: BarMeasure,
+    config: AdaptiveConfig,
+    /// EWMA of bar lengths, `E[T]`.
+    expected_ticks: f64,
+    /// EWMA of the buy-side measure per trade, `E[v · 1{b=1}]`.
+    expected_buy: Option<f64>,
+    /// EWMA of the sell-side measure per trade, `E[v · 1{b=-1}]`.
+    expected_sell: Option<f64>,
+    buy_run: f64,
+    sell_run: f64,
+    ticks: usize,
+}
+
+impl RunRule {
+    pub fn new(measure: BarMeasure, config: AdaptiveConfig) -> Self {
+        Self {
+            measure,
+            expected_ticks: config.expected_ticks,
+            config,
+            expected_buy: None,
+            expected_sell: None,
+            buy_run: 0.0,
+            sell_run: 0.0,
+            ticks: 0,
+        }
+    }
+
+    /// Current closing threshold.
+    pub fn threshold(&self) -> f64 {
+        let buy = self.expected_buy.unwrap_or(0.0);
+        let sell = self.expected_sell.unwrap_or(0.0);
+        self.expected_ticks * buy.max(sell)
+    }
+}
+
+fn ewma(prev: Option<f64>, value: f64, alpha: f64) -> f64 {
+    match prev {
+        Some(prev) => alpha * value + (1.0 - alpha) * prev,
+        None => value,
+    }
+}
+
+impl BarRule for RunRule {
+    fn update(&mut self, trade: &Trade, sign: f64) -> bool {
+        let v = self.measure.of(trade);
+        let (buy, sell) = if sign > 0.0 { (v, 0.0) } else { (0.0, v) };
+        self.buy_run += buy;
+        self.sell_run += sell;
+        self.ticks += 1;
+
+        let alpha = 2.0 / (self.config.ticks_span as f64 + 1.0);
+        self.expected_buy = Some(ewma(self.expected_buy, buy, alpha));
+        self.expected_sell = Some(ewma(self.expected_sell, sell, alpha));
+
+        if self.config.max_ticks.is_some_and(|max| self.ticks >= max) {
+            return true;
+        }
+        self.ticks >= self.config.min_ticks
+            && self.buy_run.max(self.sell_run) >= self.threshold()
+    }
+
+    fn reset(&mut self) {
+        let alpha = 2.0 / (self.config.bars_span as f64 + 1.0);
+        self.expected_ticks =
+            alpha * self.ticks as f64 + (1.0 - alpha) * self.expected_ticks;
+        self.buy_run = 0.0;
+        self.sell_run = 0.0;
+        self.ticks = 0;
+    }
+}
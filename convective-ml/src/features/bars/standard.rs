@@ -0,0 +1,39 @@
+//! Tick, volume and dollar bars with a fixed threshold.
+
+use super::{BarMeasure, BarRule};
+use crate::features::FeatureError;
+use atelier_data::trades::Trade;
+
+/// Closes a bar once the accumulated measure reaches a fixed threshold.
+#[derive(Debug, Clone)]
+pub struct StandardRule {
+    measure: BarMeasure,
+    threshold: f64,
+    accumulated: f64,
+}
+
+impl StandardRule {
+    pub fn new(measure: BarMeasure, threshold: f64) -> Result<Self, FeatureError> {
+        if threshold <= 0.0 || !threshold.is_finite() {
+            return Err(FeatureError::InvalidConfig {
+                message: format!("bar threshold must be positive, got {threshold}"),
+            });
+        }
+        Ok(Self {
+            measure,
+            threshold,
+            accumulated: 0.0,
+        })
+    }
+}
+
+impl BarRule for StandardRule {
+    fn update(&mut self, trade: &Trade, _sign: f64) -> bool {
+        self.accumulated += self.measure.of(trade);
+        self.accumulated >= self.threshold
+    }
+
+    fn reset(&mut self) {
+        self.accumulated = 0.0;
+    }
+}
@@ -5,8 +5,7 @@
 //! aggressive order flow.
 
 use crate::features::{Feature, FeatureCategory, FeatureError, MarketConfig};
-use convective_data::utils;
-// use atelier_data::{snapshot::MarketSnapshot};
+use atelier_data::snapshot::MarketSnapshot;
 use std::any::Any;
 
 // ---------------------------------------------------------------------------
@@ -42,7 +41,7 @@ impl Feature for PriceImpactFeature {
     fn compute(
         &self,
         snap: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         let ob = snap
             .orderbook
@@ -62,7 +61,7 @@ impl Feature for PriceImpactFeature {
         let total_impact: f64 = snap.trades.iter().map(|t| t.price - mid).sum();
 
         let avg = total_impact / snap.trades.len() as f64;
-        Ok(utils::truncate_to_decimal(avg, 8))
+        Ok(config.precision.value(avg))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -104,7 +103,7 @@ impl Feature for TradeFlowToxicityFeature {
     fn compute(
         &self,
         snap: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         if snap.trades.is_empty() {
             return Ok(0.0);
@@ -127,7 +126,7 @@ impl Feature for TradeFlowToxicityFeature {
         }
 
         let toxicity = (buy_vol - sell_vol).abs() / total;
-        Ok(utils::truncate_to_decimal(toxicity, 8))
+        Ok(config.precision.value(toxicity))
     }
 
     fn as_any(&self) -> &dyn Any {
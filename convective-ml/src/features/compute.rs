@@ -15,7 +15,11 @@ pub fn compute_features(
     _output_format: FeaturesOutput,
 ) -> Result<Vec<Vec<f64>>, FeatureError> {
     let selector = FeatureSelector::new(feature_names)?;
-    let config = OrderbookConfig { depth, bps };
+    let config = OrderbookConfig {
+        depth,
+        bps,
+        ..Default::default()
+    };
 
     let mut feature_matrix = Vec::new();
 
@@ -32,6 +36,7 @@ pub fn compute_features_with_config(
     feature_names: &[&str],
     config: &OrderbookConfig,
 ) -> Result<Vec<Vec<f64>>, FeatureError> {
+    config.validate()?;
     let selector = FeatureSelector::new(feature_names)?;
 
     orderbooks
@@ -46,6 +51,7 @@ pub fn compute_single_orderbook(
     feature_names: &[&str],
     config: &OrderbookConfig,
 ) -> Result<Vec<f64>, FeatureError> {
+    config.validate()?;
     let selector = FeatureSelector::new(feature_names)?;
     selector.compute_values(ob, config)
 }
@@ -41,6 +41,8 @@ pub fn compute_all_features(
     snapshots: &[MarketSnapshot],
     config: &MarketConfig,
 ) -> Result<Vec<Vec<f64>>, FeatureError> {
+    config.validate()?;
+
     // Pre-instantiate features (zero-size structs, no heap alloc)
     let spread = SpreadFeature;
     let midprice = MidpriceFeature;
@@ -61,6 +63,8 @@ pub fn compute_all_features(
     let ob_config = OrderbookConfig {
         depth: config.depth,
         bps: config.bps,
+        precision: config.precision,
+        numeric_mode: config.numeric_mode,
     };
     let market_config = config.clone();
 
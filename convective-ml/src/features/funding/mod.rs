@@ -35,10 +35,10 @@ impl Feature for FundingRateFeature {
     fn compute(
         &self,
         fr: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         // Scale to bps for better numerical range in downstream models
-        Ok(fr.funding_rate * 10_000.0)
+        Ok(config.rate_precision.value(fr.funding_rate * 10_000.0))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -1,4 +1,7 @@
-use crate::features::errors::FeatureError;
+use crate::features::{
+    errors::FeatureError,
+    precision::{NumericMode, Precision},
+};
 use serde::{Deserialize, Serialize};
 use std::any::Any;
 
@@ -55,6 +58,10 @@ pub enum FeatureCategory {
 pub struct OrderbookConfig {
     pub depth: usize,
     pub bps: f64,
+    /// Rounding policy applied to every output.
+    pub precision: Precision,
+    /// Arithmetic used for price-based features.
+    pub numeric_mode: NumericMode,
 }
 
 impl Default for OrderbookConfig {
@@ -62,10 +69,19 @@ impl Default for OrderbookConfig {
         Self {
             depth: 5,
             bps: 0.001, // 10 bps
+            precision: Precision::default(),
+            numeric_mode: NumericMode::default(),
         }
     }
 }
 
+impl OrderbookConfig {
+    /// Check the precision policy.
+    pub fn validate(&self) -> Result<(), FeatureError> {
+        self.precision.validate()
+    }
+}
+
 /// Configuration for multi-source / market-snapshot features.
 ///
 /// Features that compute over `MarketSnapshot` use this config.
@@ -75,6 +91,12 @@ pub struct MarketConfig {
     pub depth: usize,
     /// Basis-point tolerance for price-band features.
     pub bps: f64,
+    /// Rounding policy applied to every output except the rates.
+    pub precision: Precision,
+    /// Rounding policy for funding rate and OI change; untouched by default.
+    pub rate_precision: Precision,
+    /// Arithmetic used for price-based features.
+    pub numeric_mode: NumericMode,
 }
 
 impl Default for MarketConfig {
@@ -82,6 +104,17 @@ impl Default for MarketConfig {
         Self {
             depth: 5,
             bps: 0.001,
+            precision: Precision::default(),
+            rate_precision: Precision::None,
+            numeric_mode: NumericMode::default(),
         }
     }
 }
+
+impl MarketConfig {
+    /// Check both precision policies.
+    pub fn validate(&self) -> Result<(), FeatureError> {
+        self.precision.validate()?;
+        self.rate_precision.validate()
+    }
+}
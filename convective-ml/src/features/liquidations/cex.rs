@@ -5,7 +5,7 @@
 //! liquidation activity within a synchronization period.
 
 use crate::features::{Feature, FeatureCategory, FeatureError, MarketConfig};
-use atelier_data::{datasets, liquidations::Liquidation};
+use atelier_data::liquidations::Liquidation;
 use std::any::Any;
 
 // ---------------------------------------------------------------------------
@@ -39,14 +39,14 @@ impl Feature for LiquidationPressureFeature {
     fn compute(
         &self,
         liqs: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         if liqs.is_empty() {
             return Ok(0.0);
         }
 
         let notional: f64 = liqs.iter().map(|l| l.price * l.amount).sum();
-        Ok(datasets::truncate_to_decimal(notional, 8))
+        Ok(config.precision.value(notional))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -86,7 +86,7 @@ impl Feature for LiquidationImbalanceFeature {
     fn compute(
         &self,
         liqs: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         if liqs.is_empty() {
             return Ok(0.0);
@@ -109,7 +109,7 @@ impl Feature for LiquidationImbalanceFeature {
         }
 
         let imb = (buy_vol - sell_vol) / total;
-        Ok(datasets::truncate_to_decimal(imb, 8))
+        Ok(config.precision.value(imb))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -1,3 +1,4 @@
+pub mod bars;
 pub mod composite;
 pub mod compute;
 pub mod compute_market;
@@ -7,6 +8,7 @@ pub mod interface;
 pub mod liquidations;
 pub mod open_interest;
 pub mod orderbook;
+pub mod precision;
 pub mod registry;
 pub mod selector;
 pub mod trades;
@@ -14,6 +16,7 @@ pub mod trades;
 pub use compute::*;
 pub use errors::*;
 pub use interface::*;
+pub use precision::{NumericMode, Precision};
 pub use registry::*;
 pub use selector::*;
 
@@ -34,7 +34,7 @@ impl Feature for OIChangeFeature {
     fn compute(
         &self,
         oi_pair: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         let [prev, curr] = *oi_pair;
 
@@ -49,7 +49,7 @@ impl Feature for OIChangeFeature {
             });
         }
 
-        Ok((curr - prev) / prev * 100.0)
+        Ok(config.rate_precision.value((curr - prev) / prev * 100.0))
     }
 
     fn as_any(&self) -> &dyn Any {
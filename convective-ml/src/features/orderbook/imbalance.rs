@@ -1,5 +1,5 @@
 use crate::features::{Feature, FeatureCategory, FeatureError, OrderbookConfig};
-use atelier_data::{datasets, orderbooks::Orderbook};
+use atelier_data::orderbooks::Orderbook;
 use std::any::Any;
 
 #[derive(Debug, Clone)]
@@ -25,7 +25,7 @@ impl Feature for ImbalanceFeature {
     fn compute(
         &self,
         ob: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         if ob.bids.is_empty() || ob.asks.is_empty() {
             return Err(FeatureError::EmptyOrderbook);
@@ -37,7 +37,7 @@ impl Feature for ImbalanceFeature {
         }
 
         let imbalance = ob.asks[0].volume / total_volume;
-        Ok(datasets::truncate_to_decimal(imbalance, 8))
+        Ok(config.precision.value(imbalance))
     }
 
     fn as_any(&self) -> &dyn Any {
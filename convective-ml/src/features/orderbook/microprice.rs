@@ -9,8 +9,11 @@
 //! This pulls the estimate toward the side with *less* resting liquidity,
 //! reflecting the idea that the thinner side is more likely to be consumed.
 
-use crate::features::{Feature, FeatureCategory, FeatureError, OrderbookConfig};
-use atelier_data::{datasets, orderbooks::Orderbook};
+use crate::features::{
+    Feature, FeatureCategory, FeatureError, NumericMode, OrderbookConfig,
+    precision::to_decimal,
+};
+use atelier_data::orderbooks::Orderbook;
 use std::any::Any;
 
 #[derive(Debug, Clone)]
@@ -36,7 +39,7 @@ impl Feature for MicropriceFeature {
     fn compute(
         &self,
         ob: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         if ob.bids.is_empty() || ob.asks.is_empty() {
             return Err(FeatureError::EmptyOrderbook);
@@ -52,9 +55,20 @@ impl Feature for MicropriceFeature {
             return Err(FeatureError::ZeroVolume);
         }
 
-        let microprice =
-            bid_price * (ask_size / total_size) + ask_price * (bid_size / total_size);
-        Ok(datasets::truncate_to_decimal(microprice, 8))
+        match config.numeric_mode {
+            NumericMode::Float => {
+                let microprice = bid_price * (ask_size / total_size)
+                    + ask_price * (bid_size / total_size);
+                Ok(config.precision.price(microprice))
+            }
+            NumericMode::Decimal => {
+                let (bid_size, ask_size) = (to_decimal(bid_size)?, to_decimal(ask_size)?);
+                let microprice = (to_decimal(bid_price)? * ask_size
+                    + to_decimal(ask_price)? * bid_size)
+                    / (bid_size + ask_size);
+                config.precision.price_decimal(microprice)
+            }
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
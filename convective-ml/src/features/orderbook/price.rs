@@ -1,5 +1,9 @@
-use crate::features::{Feature, FeatureCategory, FeatureError, OrderbookConfig};
-use atelier_data::{datasets, orderbooks::Orderbook};
+use crate::features::{
+    Feature, FeatureCategory, FeatureError, NumericMode, OrderbookConfig,
+    precision::to_decimal,
+};
+use atelier_data::orderbooks::Orderbook;
+use rust_decimal::Decimal;
 use std::any::Any;
 
 #[derive(Debug, Clone)]
@@ -25,14 +29,24 @@ impl Feature for MidpriceFeature {
     fn compute(
         &self,
         ob: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         if ob.bids.is_empty() || ob.asks.is_empty() {
             return Err(FeatureError::EmptyOrderbook);
         }
 
-        let midprice = (ob.asks[0].price + ob.bids[0].price) / 2.0;
-        Ok(datasets::truncate_to_decimal(midprice, 8))
+        match config.numeric_mode {
+            NumericMode::Float => {
+                let midprice = (ob.asks[0].price + ob.bids[0].price) / 2.0;
+                Ok(config.precision.price(midprice))
+            }
+            NumericMode::Decimal => {
+                let midprice = (to_decimal(ob.asks[0].price)?
+                    + to_decimal(ob.bids[0].price)?)
+                    / Decimal::TWO;
+                config.precision.price_decimal(midprice)
+            }
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -63,7 +77,7 @@ impl Feature for WeightedMidpriceFeature {
     fn compute(
         &self,
         ob: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         if ob.bids.is_empty() || ob.asks.is_empty() {
             return Err(FeatureError::EmptyOrderbook);
@@ -74,10 +88,24 @@ impl Feature for WeightedMidpriceFeature {
             return Err(FeatureError::ZeroVolume);
         }
 
-        let w_midprice = ((ob.bids[0].price * ob.bids[0].volume)
-            + (ob.asks[0].price * ob.asks[0].volume))
-            / total_volume;
-        Ok(datasets::truncate_to_decimal(w_midprice, 8))
+        match config.numeric_mode {
+            NumericMode::Float => {
+                let w_midprice = ((ob.bids[0].price * ob.bids[0].volume)
+                    + (ob.asks[0].price * ob.asks[0].volume))
+                    / total_volume;
+                Ok(config.precision.price(w_midprice))
+            }
+            NumericMode::Decimal => {
+                let (bid_v, ask_v) = (
+                    to_decimal(ob.bids[0].volume)?,
+                    to_decimal(ob.asks[0].volume)?,
+                );
+                let w_midprice = (to_decimal(ob.bids[0].price)? * bid_v
+                    + to_decimal(ob.asks[0].price)? * ask_v)
+                    / (bid_v + ask_v);
+                config.precision.price_decimal(w_midprice)
+            }
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -1,5 +1,8 @@
-use crate::features::{Feature, FeatureCategory, FeatureError, OrderbookConfig};
-use atelier_data::{datasets, orderbooks::Orderbook};
+use crate::features::{
+    Feature, FeatureCategory, FeatureError, NumericMode, OrderbookConfig,
+    precision::to_decimal,
+};
+use atelier_data::orderbooks::Orderbook;
 use std::any::Any;
 
 #[derive(Debug, Clone)]
@@ -25,14 +28,23 @@ impl Feature for SpreadFeature {
     fn compute(
         &self,
         ob: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         if ob.bids.is_empty() || ob.asks.is_empty() {
             return Err(FeatureError::EmptyOrderbook);
         }
 
-        let spread = ob.asks[0].price - ob.bids[0].price;
-        Ok(datasets::truncate_to_decimal(spread, 8))
+        match config.numeric_mode {
+            NumericMode::Float => {
+                let spread = ob.asks[0].price - ob.bids[0].price;
+                Ok(config.precision.price(spread))
+            }
+            NumericMode::Decimal => {
+                let spread =
+                    to_decimal(ob.asks[0].price)? - to_decimal(ob.bids[0].price)?;
+                config.precision.price_decimal(spread)
+            }
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -1,5 +1,9 @@
-use crate::features::{Feature, FeatureCategory, FeatureError, OrderbookConfig};
-use atelier_data::{datasets, orderbooks::Orderbook};
+use crate::features::{
+    Feature, FeatureCategory, FeatureError, NumericMode, OrderbookConfig,
+    precision::to_decimal,
+};
+use atelier_data::orderbooks::Orderbook;
+use rust_decimal::Decimal;
 use std::any::Any;
 
 #[derive(Debug, Clone)]
@@ -43,14 +47,33 @@ impl Feature for VWAPFeature {
         let ask_levels = ob.asks.iter().take(depth);
         let all_levels = bid_levels.chain(ask_levels);
 
-        let (sum_p_v, sum_v) = all_levels.fold((0.0, 0.0), |(acc_p_v, acc_v), level| {
-            (acc_p_v + level.price * level.volume, acc_v + level.volume)
-        });
-
-        if sum_v > 0.0 {
-            Ok(datasets::truncate_to_decimal(sum_p_v / sum_v, 8))
-        } else {
-            Err(FeatureError::ZeroVolume)
+        match config.numeric_mode {
+            NumericMode::Float => {
+                let (sum_p_v, sum_v) =
+                    all_levels.fold((0.0, 0.0), |(acc_p_v, acc_v), level| {
+                        (acc_p_v + level.price * level.volume, acc_v + level.volume)
+                    });
+
+                if sum_v > 0.0 {
+                    Ok(config.precision.price(sum_p_v / sum_v))
+                } else {
+                    Err(FeatureError::ZeroVolume)
+                }
+            }
+            NumericMode::Decimal => {
+                let (mut sum_p_v, mut sum_v) = (Decimal::ZERO, Decimal::ZERO);
+                for level in all_levels {
+                    let volume = to_decimal(level.volume)?;
+                    sum_p_v += to_decimal(level.price)? * volume;
+                    sum_v += volume;
+                }
+
+                if sum_v > Decimal::ZERO {
+                    config.precision.price_decimal(sum_p_v / sum_v)
+                } else {
+                    Err(FeatureError::ZeroVolume)
+                }
+            }
         }
     }
 
@@ -110,7 +133,7 @@ impl Feature for TAVFeature {
             .sum();
 
         let tav = bid_volume + ask_volume;
-        Ok(datasets::truncate_to_decimal(tav, 8))
+        Ok(config.precision.value(tav))
     }
 
     fn as_any(&self) -> &dyn Any {
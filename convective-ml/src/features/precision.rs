@@ -0,0 +1,138 @@
+//! Output precision policy for feature values.
+//!
+//! Feature outputs used to be truncated to 8 decimals unconditionally,
+//! which silently zeroes small quantities (funding rates, BTC-denominated
+//! volumes) and biases everything else toward zero.  [`Precision`] makes the
+//! post-processing explicit and configurable per run through
+//! [`OrderbookConfig`](super::OrderbookConfig) and
+//! [`MarketConfig`](super::MarketConfig).  Funding rate and OI change,
+//! which were never truncated, follow `MarketConfig::rate_precision`
+//! instead and stay untouched by default.
+//!
+//! [`NumericMode::Decimal`] additionally computes price-based features
+//! (spread, midprices, VWAP) in exact `rust_decimal` arithmetic, avoiding
+//! binary floating-point artefacts such as
+//! `100000.1 - 100000.0 = 0.10000000000582077`.
+
+use crate::features::FeatureError;
+use rust_decimal::{Decimal, RoundingStrategy, prelude::ToPrimitive};
+use std::str::FromStr;
+
+/// Post-processing applied to every feature output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Precision {
+    /// Return values untouched.
+    None,
+    /// Round half away from zero to `n` decimal places.
+    Round(u32),
+    /// Truncate toward zero to `n` decimal places.
+    Truncate(u32),
+    /// Snap price-denominated outputs to the nearest multiple of the tick
+    /// size; other outputs are returned untouched.
+    TickSize(f64),
+}
+
+impl Default for Precision {
+    /// `Truncate(8)`, the historical behaviour.
+    fn default() -> Self {
+        Precision::Truncate(8)
+    }
+}
+
+/// Arithmetic used for price-based features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumericMode {
+    /// Native `f64` arithmetic.
+    #[default]
+    Float,
+    /// Exact decimal arithmetic via `rust_decimal`.
+    Decimal,
+}
+
+impl Precision {
+    /// Apply the policy to a dimensionless or volume-denominated value.
+    pub fn value(&self, x: f64) -> f64 {
+        match self {
+            Precision::TickSize(_) => x,
+            _ => self.price(x),
+        }
+    }
+
+    /// Apply the policy to a price-denominated value.
+    pub fn price(&self, x: f64) -> f64 {
+        if !x.is_finite() {
+            return x;
+        }
+        match *self {
+            Precision::None => x,
+            Precision::Round(n) => {
+                let multiplier = 10_f64.powi(n as i32);
+                (x * multiplier).round() / multiplier
+            }
+            Precision::Truncate(n) => {
+                let multiplier = 10_f64.powi(n as i32);
+                (x * multiplier).trunc() / multiplier
+            }
+            Precision::TickSize(tick) if tick > 0.0 => (x / tick).round() * tick,
+            Precision::TickSize(_) => x,
+        }
+    }
+
+    /// Apply the policy to an exact price-denominated value.
+    pub fn price_decimal(&self, d: Decimal) -> Result<f64, FeatureError> {
+        let d = match *self {
+            Precision::None => d,
+            Precision::Round(n) => {
+                d.round_dp_with_strategy(n, RoundingStrategy::MidpointAwayFromZero)
+            }
+            Precision::Truncate(n) => d.trunc_with_scale(n),
+            Precision::TickSize(tick) if tick > 0.0 => {
+                let tick = to_decimal(tick)?;
+                (d / tick).round() * tick
+            }
+            Precision::TickSize(_) => d,
+        };
+        from_decimal(d)
+    }
+
+    /// Check the policy parameters.
+    pub fn validate(&self) -> Result<(), FeatureError> {
+        match *self {
+            Precision::TickSize(tick) if !(tick > 0.0 && tick.is_finite()) => {
+                Err(FeatureError::InvalidConfig {
+                    message: format!("tick size must be positive, got {tick}"),
+                })
+            }
+            Precision::Round(n) | Precision::Truncate(n) if n > 28 => {
+                Err(FeatureError::InvalidConfig {
+                    message: format!("at most 28 decimal places supported, got {n}"),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Convert an `f64` to its shortest round-trip decimal representation.
+///
+/// `100000.1_f64` becomes exactly `100000.1`, not the binary expansion
+/// `100000.100000000005820766091346740722656250`.
+pub fn to_decimal(x: f64) -> Result<Decimal, FeatureError> {
+    if !x.is_finite() {
+        return Err(FeatureError::ComputationError {
+            message: format!("cannot represent {x} as a decimal"),
+        });
+    }
+    Decimal::from_str(&x.to_string())
+        .or_else(|_| Decimal::from_scientific(&format!("{x:e}")))
+        .map_err(|e| FeatureError::ComputationError {
+            message: format!("decimal conversion of {x} failed: {e}"),
+        })
+}
+
+/// Convert a decimal back to `f64`.
+pub fn from_decimal(d: Decimal) -> Result<f64, FeatureError> {
+    d.to_f64().ok_or_else(|| FeatureError::ComputationError {
+        message: format!("decimal {d} does not fit in f64"),
+    })
+}
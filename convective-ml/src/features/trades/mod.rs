@@ -4,7 +4,7 @@
 //! synchronization period) and return a scalar `f64`.
 
 use crate::features::{Feature, FeatureCategory, FeatureError, MarketConfig};
-use atelier_data::{datasets, trades::Trade};
+use atelier_data::trades::Trade;
 use std::any::Any;
 
 // ---------------------------------------------------------------------------
@@ -37,13 +37,13 @@ impl Feature for TradeIntensityFeature {
     fn compute(
         &self,
         trades: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         if trades.is_empty() {
             return Ok(0.0);
         }
         let total: f64 = trades.iter().map(|t| t.amount).sum();
-        Ok(datasets::truncate_to_decimal(total, 8))
+        Ok(config.precision.value(total))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -81,7 +81,7 @@ impl Feature for TradeDirectionImbalanceFeature {
     fn compute(
         &self,
         trades: &Self::Input,
-        _config: &Self::Config,
+        config: &Self::Config,
     ) -> Result<Self::Output, FeatureError> {
         if trades.is_empty() {
             return Ok(0.0);
@@ -104,7 +104,7 @@ impl Feature for TradeDirectionImbalanceFeature {
         }
 
         let imb = (buy_vol - sell_vol) / total;
-        Ok(datasets::truncate_to_decimal(imb, 8))
+        Ok(config.precision.value(imb))
     }
 
     fn as_any(&self) -> &dyn Any {
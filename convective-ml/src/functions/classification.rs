@@ -0,0 +1,559 @@
+//! Classification losses beyond plain cross-entropy: hinge, class-weighted
+//! and focal cross-entropy, and softmax cross-entropy for multiclass
+//! models.
+//!
+//! Binary targets are labels in `{0, 1}` as for
+//! [`CrossEntropy`](crate::functions::CrossEntropy); the margin losses map
+//! them to signs `s = 2y − 1`.  The nalgebra backend uses closed-form
+//! derivatives, the torch backend autograd.  All of them take an optional
+//! [`Regularization`].
+
+use super::{
+    cost::{
+        RegType, Regularization, Regularized, build_regularization, pointwise_hessian,
+        pointwise_output, regularized, sample_scale, sigmoid, softplus,
+    },
+    interface::{LossFunction, LossOutput, Samples},
+};
+use crate::models::{backend::NalgebraBackend, softmax::softmax_rows};
+
+#[cfg(feature = "torch")]
+use super::cost::{autograd_output, reduce};
+#[cfg(feature = "torch")]
+use crate::models::backend::TorchBackend;
+
+// ---------------------------------------------------------------------------
+// Hinge
+// ---------------------------------------------------------------------------
+
+/// Hinge loss `max(0, 1 − s z)` of a linear SVM; pair it with an L2
+/// [`Regularization`] for the usual soft-margin objective.
+///
+/// At the kink `s z = 1` the nalgebra backend uses the subgradient `0`.
+#[derive(Debug)]
+pub struct Hinge {
+    pub id: String,
+    pub regularization: Option<Regularization>,
+}
+
+impl Hinge {
+    pub fn builder<'a>() -> HingeBuilder<'a> {
+        HingeBuilder::new()
+    }
+}
+
+regularized!(Hinge);
+
+impl LossFunction<NalgebraBackend> for Hinge {
+    fn weighted_loss_and_gradients(
+        &self,
+        features: &nalgebra::DMatrix<f64>,
+        logits: &nalgebra::DMatrix<f64>,
+        targets: &nalgebra::DMatrix<f64>,
+        sample_weights: Option<&nalgebra::DMatrix<f64>>,
+        weights: &mut nalgebra::DMatrix<f64>,
+        _bias: &mut nalgebra::DMatrix<f64>,
+    ) -> LossOutput<NalgebraBackend> {
+        pointwise_output(
+            self,
+            features,
+            logits,
+            targets,
+            sample_weights,
+            weights,
+            |z, y| {
+                let s = 2.0 * y - 1.0;
+                let margin = 1.0 - s * z;
+                if margin > 0.0 {
+                    (margin, -s)
+                } else {
+                    (0.0, 0.0)
+                }
+            },
+        )
+    }
+}
+
+#[cfg(feature = "torch")]
+impl LossFunction<TorchBackend> for Hinge {
+    fn weighted_loss_and_gradients(
+        &self,
+        _features: &tch::Tensor,
+        logits: &tch::Tensor,
+        targets: &tch::Tensor,
+        sample_weights: Option<&tch::Tensor>,
+        weights: &mut tch::Tensor,
+        bias: &mut tch::Tensor,
+    ) -> LossOutput<TorchBackend> {
+        let signs = targets * 2.0 - 1.0;
+        let per_sample = ((signs * logits).neg() + 1.0).clamp_min(0.0);
+        autograd_output(self, reduce(&per_sample, sample_weights), weights, bias)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// WeightedCrossEntropy
+// ---------------------------------------------------------------------------
+
+/// Class-weighted binary cross-entropy (with logits):
+///
+/// ```text
+/// −a₊ y ln σ(z) − a₋ (1 − y) ln(1 − σ(z))
+/// ```
+///
+/// Raising `pos_weight` above `neg_weight` trades precision for recall on
+/// the positive class, e.g. `pos_weight = negatives / positives` balances
+/// an imbalanced dataset.
+#[derive(Debug)]
+pub struct WeightedCrossEntropy {
+    pub id: String,
+    pub pos_weight: f64,
+    pub neg_weight: f64,
+    pub regularization: Option<Regularization>,
+}
+
+impl WeightedCrossEntropy {
+    pub fn builder<'a>() -> WeightedCrossEntropyBuilder<'a> {
+        WeightedCrossEntropyBuilder::new()
+    }
+}
+
+regularized!(WeightedCrossEntropy);
+
+impl LossFunction<NalgebraBackend> for WeightedCrossEntropy {
+    fn weighted_loss_and_gradients(
+        &self,
+        features: &nalgebra::DMatrix<f64>,
+        logits: &nalgebra::DMatrix<f64>,
+        targets: &nalgebra::DMatrix<f64>,
+        sample_weights: Option<&nalgebra::DMatrix<f64>>,
+        weights: &mut nalgebra::DMatrix<f64>,
+        _bias: &mut nalgebra::DMatrix<f64>,
+    ) -> LossOutput<NalgebraBackend> {
+        let (pos, neg) = (self.pos_weight, self.neg_weight);
+        // −ln σ(z) = softplus(−z),  −ln(1 − σ(z)) = softplus(z)
+        pointwise_output(
+            self,
+            features,
+            logits,
+            targets,
+            sample_weights,
+            weights,
+            |z, y| {
+                let p = sigmoid(z);
+                (
+                    pos * y * softplus(-z) + neg * (1.0 - y) * softplus(z),
+                    neg * (1.0 - y) * p - pos * y * (1.0 - p),
+                )
+            },
+        )
+    }
+
+    /// Cross-entropy curvature `σ(z)(1 − σ(z))` scaled by the class weight
+    /// of every sample.
+    fn hessian(
+        &self,
+        samples: &Samples<NalgebraBackend>,
+        logits: &nalgebra::DMatrix<f64>,
+        _weights: &nalgebra::DMatrix<f64>,
+    ) -> Option<nalgebra::DMatrix<f64>> {
+        let curvature = logits.iter().zip(samples.targets.iter()).map(|(&z, &y)| {
+            let p = sigmoid(z);
+            (self.pos_weight * y + self.neg_weight * (1.0 - y)) * p * (1.0 - p)
+        });
+        Some(pointwise_hessian(
+            samples,
+            curvature,
+            self.regularization.as_ref(),
+        ))
+    }
+}
+
+#[cfg(feature = "torch")]
+impl LossFunction<TorchBackend> for WeightedCrossEntropy {
+    fn weighted_loss_and_gradients(
+        &self,
+        _features: &tch::Tensor,
+        logits: &tch::Tensor,
+        targets: &tch::Tensor,
+        sample_weights: Option<&tch::Tensor>,
+        weights: &mut tch::Tensor,
+        bias: &mut tch::Tensor,
+    ) -> LossOutput<TorchBackend> {
+        let positive = targets * self.pos_weight * logits.neg().softplus();
+        let negative = (targets.neg() + 1.0) * self.neg_weight * logits.softplus();
+        let per_sample = positive + negative;
+        autograd_output(self, reduce(&per_sample, sample_weights), weights, bias)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FocalLoss
+// ---------------------------------------------------------------------------
+
+/// Focal loss (Lin et al., 2017), which down-weights well-classified
+/// samples:
+///
+/// ```text
+/// −α_t (1 − p_t)^γ ln p_t,   p_t = σ(s z)
+/// ```
+///
+/// with `α_t = α` for positives and `1 − α` for negatives, or `1` without
+/// `alpha`.  `gamma = 0` and no `alpha` is plain cross-entropy.
+#[derive(Debug)]
+pub struct FocalLoss {
+    pub id: String,
+    pub gamma: f64,
+    pub alpha: Option<f64>,
+    pub regularization: Option<Regularization>,
+}
+
+impl FocalLoss {
+    pub fn builder<'a>() -> FocalLossBuilder<'a> {
+        FocalLossBuilder::new()
+    }
+
+    /// Class-balancing factor `α_t` of a sample with target `y`.
+    fn alpha_t(&self, y: f64) -> f64 {
+        self.alpha.map_or(1.0, |a| a * y + (1.0 - a) * (1.0 - y))
+    }
+}
+
+regularized!(FocalLoss);
+
+impl LossFunction<NalgebraBackend> for FocalLoss {
+    fn weighted_loss_and_gradients(
+        &self,
+        features: &nalgebra::DMatrix<f64>,
+        logits: &nalgebra::DMatrix<f64>,
+        targets: &nalgebra::DMatrix<f64>,
+        sample_weights: Option<&nalgebra::DMatrix<f64>>,
+        weights: &mut nalgebra::DMatrix<f64>,
+        _bias: &mut nalgebra::DMatrix<f64>,
+    ) -> LossOutput<NalgebraBackend> {
+        let gamma = self.gamma;
+        // ∂ℓ/∂z = −α_t s (1 − p_t)^γ (1 − p_t − γ p_t ln p_t)
+        pointwise_output(
+            self,
+            features,
+            logits,
+            targets,
+            sample_weights,
+            weights,
+            |z, y| {
+                let s = 2.0 * y - 1.0;
+                let alpha = self.alpha_t(y);
+                let p = sigmoid(s * z);
+                let ln_p = -softplus(-s * z);
+                let focus = (1.0 - p).powf(gamma);
+                (
+                    -alpha * focus * ln_p,
+                    -alpha * s * focus * (1.0 - p - gamma * p * ln_p),
+                )
+            },
+        )
+    }
+}
+
+#[cfg(feature = "torch")]
+impl LossFunction<TorchBackend> for FocalLoss {
+    fn weighted_loss_and_gradients(
+        &self,
+        _features: &tch::Tensor,
+        logits: &tch::Tensor,
+        targets: &tch::Tensor,
+        sample_weights: Option<&tch::Tensor>,
+        weights: &mut tch::Tensor,
+        bias: &mut tch::Tensor,
+    ) -> LossOutput<TorchBackend> {
+        let margins = (targets * 2.0 - 1.0) * logits;
+        // 1 − p_t = σ(−s z)
+        let focus = margins.neg().sigmoid().pow_tensor_scalar(self.gamma);
+        let mut per_sample = (focus * margins.log_sigmoid()).neg();
+        if let Some(alpha) = self.alpha {
+            per_sample = per_sample * (targets * (2.0 * alpha - 1.0) + (1.0 - alpha));
+        }
+        autograd_output(self, reduce(&per_sample, sample_weights), weights, bias)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SoftmaxCrossEntropy
+// ---------------------------------------------------------------------------
+
+/// Multinomial cross-entropy (with logits) of a
+/// [`SoftmaxModel`](crate::models::SoftmaxModel):
+///
+/// ```text
+/// −ln softmax(z)_y = logsumexp(z) − z_y
+/// ```
+///
+/// Logits are *(n × k)* and targets are class indices `0..k`.
+///
+/// # Panics
+///
+/// If a target is not a class index.
+#[derive(Debug)]
+pub struct SoftmaxCrossEntropy {
+    pub id: String,
+    pub regularization: Option<Regularization>,
+}
+
+impl SoftmaxCrossEntropy {
+    pub fn builder<'a>() -> SoftmaxCrossEntropyBuilder<'a> {
+        SoftmaxCrossEntropyBuilder::new()
+    }
+}
+
+regularized!(SoftmaxCrossEntropy);
+
+/// Class index encoded by target `y`.
+fn class_index(y: f64, classes: usize) -> usize {
+    assert!(
+        y >= 0.0 && y.fract() == 0.0 && (y as usize) < classes,
+        "target {y} is not a class index below {classes}"
+    );
+    y as usize
+}
+
+impl LossFunction<NalgebraBackend> for SoftmaxCrossEntropy {
+    fn weighted_loss_and_gradients(
+        &self,
+        features: &nalgebra::DMatrix<f64>,
+        logits: &nalgebra::DMatrix<f64>,
+        targets: &nalgebra::DMatrix<f64>,
+        sample_weights: Option<&nalgebra::DMatrix<f64>>,
+        weights: &mut nalgebra::DMatrix<f64>,
+        _bias: &mut nalgebra::DMatrix<f64>,
+    ) -> LossOutput<NalgebraBackend> {
+        let (n, k) = logits.shape();
+        let scale = sample_scale(n, sample_weights);
+
+        // delta = C (P − Y)   (n, k), with Y the one-hot targets
+        let mut delta = softmax_rows(logits);
+        let mut loss_value = 0.0;
+        for (i, &y) in targets.iter().enumerate() {
+            let class = class_index(y, k);
+            let row = logits.row(i);
+            let max = row.max();
+            let log_sum = max + row.iter().map(|z| (z - max).exp()).sum::<f64>().ln();
+            loss_value += scale[i] * (log_sum - row[class]);
+            delta[(i, class)] -= 1.0;
+            delta.row_mut(i).scale_mut(scale[i]);
+        }
+
+        // dW = Xᵀ δ   (m, k),   db = 1ᵀ δ   (1, k)
+        let mut weight_grad = features.transpose() * &delta;
+        Regularized::<NalgebraBackend>::regularize_gradient(
+            self,
+            weights,
+            &mut weight_grad,
+        );
+        let bias_grad = nalgebra::DMatrix::from_fn(1, k, |_, j| delta.column(j).sum());
+
+        LossOutput {
+            loss_value: loss_value
+                + Regularized::<NalgebraBackend>::regularize(self, weights),
+            weight_grad,
+            bias_grad,
+        }
+    }
+}
+
+#[cfg(feature = "torch")]
+impl LossFunction<TorchBackend> for SoftmaxCrossEntropy {
+    fn weighted_loss_and_gradients(
+        &self,
+        _features: &tch::Tensor,
+        logits: &tch::Tensor,
+        targets: &tch::Tensor,
+        sample_weights: Option<&tch::Tensor>,
+        weights: &mut tch::Tensor,
+        bias: &mut tch::Tensor,
+    ) -> LossOutput<TorchBackend> {
+        let classes = targets.flatten(0, -1).to_kind(tch::Kind::Int64);
+        let per_sample = logits.cross_entropy_loss::<&tch::Tensor>(
+            &classes,
+            None,
+            tch::Reduction::None,
+            -100,
+            0.0,
+        );
+        autograd_output(self, reduce(&per_sample, sample_weights), weights, bias)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Builders
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+pub struct HingeBuilder<'a> {
+    id: Option<&'a str>,
+    regularization: Option<(RegType, f64, f64)>,
+}
+
+impl<'a> HingeBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Penalise the weights (see [`Regularization`]).
+    pub fn regularization(mut self, kind: RegType, c: f64, lambda: f64) -> Self {
+        self.regularization = Some((kind, c, lambda));
+        self
+    }
+
+    pub fn build(self) -> Result<Hinge, &'static str> {
+        let id = self.id.ok_or("Missing id value")?;
+        Ok(Hinge {
+            id: id.to_string(),
+            regularization: build_regularization(self.regularization)?,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct WeightedCrossEntropyBuilder<'a> {
+    id: Option<&'a str>,
+    pos_weight: Option<f64>,
+    neg_weight: Option<f64>,
+    regularization: Option<(RegType, f64, f64)>,
+}
+
+impl<'a> WeightedCrossEntropyBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Weight of the positive class (default `1.0`).
+    pub fn pos_weight(mut self, weight: f64) -> Self {
+        self.pos_weight = Some(weight);
+        self
+    }
+
+    /// Weight of the negative class (default `1.0`).
+    pub fn neg_weight(mut self, weight: f64) -> Self {
+        self.neg_weight = Some(weight);
+        self
+    }
+
+    /// Penalise the weights (see [`Regularization`]).
+    pub fn regularization(mut self, kind: RegType, c: f64, lambda: f64) -> Self {
+        self.regularization = Some((kind, c, lambda));
+        self
+    }
+
+    pub fn build(self) -> Result<WeightedCrossEntropy, &'static str> {
+        let id = self.id.ok_or("Missing id value")?;
+        let pos_weight = self.pos_weight.unwrap_or(1.0);
+        let neg_weight = self.neg_weight.unwrap_or(1.0);
+        if [pos_weight, neg_weight]
+            .iter()
+            .any(|w| !w.is_finite() || *w < 0.0)
+        {
+            return Err("Class weights must be finite and non-negative");
+        }
+        Ok(WeightedCrossEntropy {
+            id: id.to_string(),
+            pos_weight,
+            neg_weight,
+            regularization: build_regularization(self.regularization)?,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FocalLossBuilder<'a> {
+    id: Option<&'a str>,
+    gamma: Option<f64>,
+    alpha: Option<f64>,
+    regularization: Option<(RegType, f64, f64)>,
+}
+
+impl<'a> FocalLossBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Focusing exponent `γ ≥ 0` (default `2.0`).
+    pub fn gamma(mut self, gamma: f64) -> Self {
+        self.gamma = Some(gamma);
+        self
+    }
+
+    /// Positive-class balance `α ∈ [0, 1]` (default: none).
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.alpha = Some(alpha);
+        self
+    }
+
+    /// Penalise the weights (see [`Regularization`]).
+    pub fn regularization(mut self, kind: RegType, c: f64, lambda: f64) -> Self {
+        self.regularization = Some((kind, c, lambda));
+        self
+    }
+
+    pub fn build(self) -> Result<FocalLoss, &'static str> {
+        let id = self.id.ok_or("Missing id value")?;
+        let gamma = self.gamma.unwrap_or(2.0);
+        if !gamma.is_finite() || gamma < 0.0 {
+            return Err("Focal gamma must be non-negative");
+        }
+        if self.alpha.is_some_and(|a| !(0.0..=1.0).contains(&a)) {
+            return Err("Focal alpha must be in [0, 1]");
+        }
+        Ok(FocalLoss {
+            id: id.to_string(),
+            gamma,
+            alpha: self.alpha,
+            regularization: build_regularization(self.regularization)?,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SoftmaxCrossEntropyBuilder<'a> {
+    id: Option<&'a str>,
+    regularization: Option<(RegType, f64, f64)>,
+}
+
+impl<'a> SoftmaxCrossEntropyBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Penalise the weights (see [`Regularization`]).
+    pub fn regularization(mut self, kind: RegType, c: f64, lambda: f64) -> Self {
+        self.regularization = Some((kind, c, lambda));
+        self
+    }
+
+    pub fn build(self) -> Result<SoftmaxCrossEntropy, &'static str> {
+        let id = self.id.ok_or("Missing id value")?;
+        Ok(SoftmaxCrossEntropy {
+            id: id.to_string(),
+            regularization: build_regularization(self.regularization)?,
+        })
+    }
+}
@@ -1,24 +1,285 @@
-//! Cross-entropy loss with per-backend gradient computation.
+//! Cross-entropy loss with per-backend gradient computation, plus the
+//! regularisation and per-sample helpers shared by every loss.
 
-use super::interface::{LossFunction, LossOutput};
-use crate::models::backend::NalgebraBackend;
+use super::interface::{LossFunction, LossOutput, Samples};
+use crate::models::backend::{ComputeBackend, NalgebraBackend};
+use serde::{Deserialize, Serialize};
 
 // ---------------------------------------------------------------------------
-// Regularisation helpers (kept for future use, backend-agnostic)
+// Regularisation
 // ---------------------------------------------------------------------------
 
-pub trait Regularized {
-    fn id(&mut self, id: String);
-    fn regularize(&self, weights: &[f64], operation: &RegType, params: &[f64]) -> f64;
+/// Weight penalty added to a loss and its gradients.
+///
+/// The bias is never penalised.
+pub trait Regularized<B: ComputeBackend> {
+    /// Penalty `R(w)` of the weight tensor.
+    fn regularize(&self, weights: &B::Tensor) -> f64;
+
+    /// Add `∇R(w)` to `weight_grad`.
+    fn regularize_gradient(&self, weights: &B::Tensor, weight_grad: &mut B::Tensor);
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RegType {
     L1,
     L2,
     Elasticnet,
 }
 
+/// Penalty of type `kind` with strength `c` and L1 ratio `lambda`:
+///
+/// ```text
+/// L1          c ‖w‖₁
+/// L2          c ‖w‖₂²
+/// Elasticnet  c (λ ‖w‖₁ + (1 − λ) ‖w‖₂²)
+/// ```
+///
+/// The L1 term uses the subgradient `sign(w)`, which is zero at `w = 0`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Regularization {
+    pub kind: RegType,
+    pub c: f64,
+    /// Elastic-net mix; ignored by `L1` and `L2`.
+    pub lambda: f64,
+}
+
+impl Regularization {
+    pub fn new(kind: RegType, c: f64, lambda: f64) -> Result<Self, &'static str> {
+        if c.is_nan() || c < 0.0 {
+            return Err("Regularization strength must be non-negative");
+        }
+        if !(0.0..=1.0).contains(&lambda) {
+            return Err("Elastic-net mix must be in [0, 1]");
+        }
+        Ok(Regularization { kind, c, lambda })
+    }
+
+    /// Weights of the `(‖w‖₁, ‖w‖₂²)` terms.
+    pub(crate) fn coefficients(&self) -> (f64, f64) {
+        match self.kind {
+            RegType::L1 => (self.c, 0.0),
+            RegType::L2 => (0.0, self.c),
+            RegType::Elasticnet => (self.c * self.lambda, self.c * (1.0 - self.lambda)),
+        }
+    }
+}
+
+impl Regularized<NalgebraBackend> for Regularization {
+    fn regularize(&self, weights: &nalgebra::DMatrix<f64>) -> f64 {
+        let (l1, l2) = self.coefficients();
+        l1 * weights.iter().map(|w| w.abs()).sum::<f64>() + l2 * weights.norm_squared()
+    }
+
+    fn regularize_gradient(
+        &self,
+        weights: &nalgebra::DMatrix<f64>,
+        weight_grad: &mut nalgebra::DMatrix<f64>,
+    ) {
+        let (l1, l2) = self.coefficients();
+        for (g, &w) in weight_grad.iter_mut().zip(weights.iter()) {
+            let sign = if w == 0.0 { 0.0 } else { w.signum() };
+            *g += l1 * sign + 2.0 * l2 * w;
+        }
+    }
+}
+
+#[cfg(feature = "torch")]
+impl Regularized<TorchBackend> for Regularization {
+    fn regularize(&self, weights: &tch::Tensor) -> f64 {
+        let (l1, l2) = self.coefficients();
+        let w = weights.detach().to_kind(tch::Kind::Double);
+        l1 * w.abs().sum(tch::Kind::Double).double_value(&[])
+            + l2 * (&w * &w).sum(tch::Kind::Double).double_value(&[])
+    }
+
+    fn regularize_gradient(&self, weights: &tch::Tensor, weight_grad: &mut tch::Tensor) {
+        let (l1, l2) = self.coefficients();
+        // Outside autograd: the penalty gradient is closed-form, like the
+        // nalgebra path.  `sign(0) = 0` matches its L1 subgradient.
+        let w = weights.detach();
+        *weight_grad = tch::no_grad(|| &*weight_grad + w.sign() * l1 + &w * (2.0 * l2));
+    }
+}
+
+/// Implement [`Regularized`] on every backend for losses with a
+/// `regularization: Option<Regularization>` field.
+macro_rules! regularized {
+    ($ty:ty) => {
+        impl<B> $crate::functions::cost::Regularized<B> for $ty
+        where
+            B: $crate::models::backend::ComputeBackend,
+            $crate::functions::cost::Regularization:
+                $crate::functions::cost::Regularized<B>,
+        {
+            fn regularize(&self, weights: &B::Tensor) -> f64 {
+                self.regularization.as_ref().map_or(0.0, |reg| {
+                    $crate::functions::cost::Regularized::<B>::regularize(reg, weights)
+                })
+            }
+
+            fn regularize_gradient(
+                &self,
+                weights: &B::Tensor,
+                weight_grad: &mut B::Tensor,
+            ) {
+                if let Some(reg) = &self.regularization {
+                    $crate::functions::cost::Regularized::<B>::regularize_gradient(
+                        reg,
+                        weights,
+                        weight_grad,
+                    );
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use regularized;
+
+// ---------------------------------------------------------------------------
+// Per-sample helpers
+// ---------------------------------------------------------------------------
+
+/// Coefficient of every sample in the mean loss: `1 / n`, or `wᵢ / Σ w`
+/// with sample weights (all zero when the weights sum to zero).
+pub(crate) fn sample_scale(
+    n: usize,
+    sample_weights: Option<&nalgebra::DMatrix<f64>>,
+) -> Vec<f64> {
+    match sample_weights {
+        None => vec![1.0 / n as f64; n],
+        Some(w) => {
+            let total = w.sum();
+            if total > 0.0 {
+                w.iter().map(|w| w / total).collect()
+            } else {
+                vec![0.0; n]
+            }
+        }
+    }
+}
+
+/// Closed-form output of a loss that is a (weighted) mean of a pointwise
+/// term: `pointwise(z, y)` returns `(ℓ, ∂ℓ/∂z)` for logit `z` and target
+/// `y`, and the chain rule through `z = Xw + b` gives
+/// `∇w = Xᵀ δ`, `∇b = Σ δ` with `δᵢ = cᵢ ∂ℓᵢ/∂zᵢ`.
+pub(crate) fn pointwise_output<L: Regularized<NalgebraBackend>>(
+    loss: &L,
+    features: &nalgebra::DMatrix<f64>,
+    logits: &nalgebra::DMatrix<f64>,
+    targets: &nalgebra::DMatrix<f64>,
+    sample_weights: Option<&nalgebra::DMatrix<f64>>,
+    weights: &nalgebra::DMatrix<f64>,
+    pointwise: impl Fn(f64, f64) -> (f64, f64),
+) -> LossOutput<NalgebraBackend> {
+    let scale = sample_scale(logits.len(), sample_weights);
+    let mut loss_value = 0.0;
+    let mut delta = nalgebra::DMatrix::zeros(logits.len(), 1);
+    for (i, (&z, &y)) in logits.iter().zip(targets.iter()).enumerate() {
+        let (value, slope) = pointwise(z, y);
+        loss_value += scale[i] * value;
+        delta[i] = scale[i] * slope;
+    }
+
+    let mut weight_grad = features.transpose() * &delta;
+    loss.regularize_gradient(weights, &mut weight_grad);
+    let bias_grad = nalgebra::DMatrix::from_element(1, 1, delta.sum());
+
+    LossOutput {
+        loss_value: loss_value + loss.regularize(weights),
+        weight_grad,
+        bias_grad,
+    }
+}
+
+/// Hessian of a pointwise loss with curvature `∂²ℓᵢ/∂zᵢ²`:
+/// `[X 1]ᵀ diag(cᵢ ∂²ℓᵢ/∂zᵢ²) [X 1]`, plus `2 c₂ I` on the weight block for
+/// an L2 / elastic-net penalty.  The L1 term has no curvature away from
+/// zero and is left out.
+pub(crate) fn pointwise_hessian(
+    samples: &Samples<NalgebraBackend>,
+    curvature: impl Iterator<Item = f64>,
+    regularization: Option<&Regularization>,
+) -> nalgebra::DMatrix<f64> {
+    let (n, m) = samples.features.shape();
+    let scale = sample_scale(n, samples.sample_weights.as_ref());
+    let design = samples.features.clone().insert_column(m, 1.0);
+    let mut weighted = design.clone();
+    for (i, h) in curvature.enumerate() {
+        weighted.row_mut(i).scale_mut(scale[i] * h);
+    }
+    let mut hessian = design.transpose() * weighted;
+    if let Some(reg) = regularization {
+        let (_, l2) = reg.coefficients();
+        for k in 0..m {
+            hessian[(k, k)] += 2.0 * l2;
+        }
+    }
+    hessian
+}
+
+/// Mean of the per-sample losses, or `Σ wᵢ ℓᵢ / Σ wᵢ` with sample weights.
+#[cfg(feature = "torch")]
+pub(crate) fn reduce(
+    per_sample: &tch::Tensor,
+    sample_weights: Option<&tch::Tensor>,
+) -> tch::Tensor {
+    match sample_weights {
+        None => per_sample.mean(tch::Kind::Float),
+        Some(w) => {
+            // The floor keeps an all-zero batch at zero loss instead of NaN.
+            (per_sample * w).sum(tch::Kind::Float)
+                / w.sum(tch::Kind::Float).clamp_min(1e-12)
+        }
+    }
+}
+
+/// Loss value and autograd gradients of the reduced `loss` tensor, with
+/// the regularisation of `loss_fn` added in closed form.
+#[cfg(feature = "torch")]
+pub(crate) fn autograd_output<L: Regularized<TorchBackend>>(
+    loss_fn: &L,
+    loss: tch::Tensor,
+    weights: &mut tch::Tensor,
+    bias: &mut tch::Tensor,
+) -> LossOutput<TorchBackend> {
+    // Clear accumulated gradients from previous iteration
+    // (must happen BEFORE backward, not after — .grad()
+    // returns a handle to the same storage that zero_grad
+    // would wipe).
+    weights.zero_grad();
+    bias.zero_grad();
+
+    // NOTE: do NOT call .set_requires_grad(true) on the
+    // loss — that would promote it to a leaf tensor and
+    // sever the autograd chain back to weights / bias.
+    let loss_value = loss.double_value(&[]) + loss_fn.regularize(weights);
+
+    // Backward pass — populates .grad() on weights and bias
+    loss.backward();
+
+    let mut weight_grad = weights.grad();
+    loss_fn.regularize_gradient(weights, &mut weight_grad);
+    let bias_grad = bias.grad();
+
+    LossOutput {
+        loss_value,
+        weight_grad,
+        bias_grad,
+    }
+}
+
+/// `σ(z) = 1 / (1 + e⁻ᶻ)`.
+pub(crate) fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+/// Numerically-stable `ln(1 + eᶻ)`.
+pub(crate) fn softplus(z: f64) -> f64 {
+    z.max(0.0) + (-z.abs()).exp().ln_1p()
+}
+
 // ---------------------------------------------------------------------------
 // CrossEntropy struct
 // ---------------------------------------------------------------------------
@@ -27,9 +288,13 @@ pub enum RegType {
 ///
 /// The struct itself is backend-agnostic.  Gradient computation is provided
 /// by per-backend `impl LossFunction<B>` blocks below.
+///
+/// An optional [`Regularization`] is added to the loss value and to the
+/// weight gradient on both backends.
 #[derive(Debug)]
 pub struct CrossEntropy {
     pub id: String,
+    pub regularization: Option<Regularization>,
 }
 
 impl CrossEntropy {
@@ -38,65 +303,52 @@ impl CrossEntropy {
     }
 }
 
-impl Regularized for CrossEntropy {
-    fn id(&mut self, id: String) {
-        self.id = id;
-    }
-
-    fn regularize(&self, weights: &[f64], operation: &RegType, params: &[f64]) -> f64 {
-        let r_c = params[0];
-        let r_lambda = params[1];
-        let l1: f64 = weights.iter().map(|w| w.abs()).sum();
-        let l2: f64 = weights.iter().map(|w| w * w).sum();
-
-        match operation {
-            RegType::L1 => r_c * r_lambda * l1,
-            RegType::L2 => r_c * r_lambda * l2,
-            RegType::Elasticnet => r_c * (r_lambda * l1 + (1.0 - r_lambda) * l2),
-        }
-    }
-}
+regularized!(CrossEntropy);
 
 // =========================================================================
 // Nalgebra implementation
 // =========================================================================
 
 impl LossFunction<NalgebraBackend> for CrossEntropy {
-    fn loss_and_gradients(
+    fn weighted_loss_and_gradients(
         &self,
         features: &nalgebra::DMatrix<f64>,
         logits: &nalgebra::DMatrix<f64>,
         targets: &nalgebra::DMatrix<f64>,
-        _weights: &mut nalgebra::DMatrix<f64>,
+        sample_weights: Option<&nalgebra::DMatrix<f64>>,
+        weights: &mut nalgebra::DMatrix<f64>,
         _bias: &mut nalgebra::DMatrix<f64>,
     ) -> LossOutput<NalgebraBackend> {
-        let n = features.nrows() as f64;
-
-        // --- Loss: numerically-stable BCE with logits ---
-        // loss_i = max(x, 0) - x * y + ln(1 + exp(-|x|))
-        let loss_value: f64 = logits
-            .iter()
-            .zip(targets.iter())
-            .map(|(&x, &y)| x.max(0.0) - x * y + (1.0 + (-x.abs()).exp()).ln())
-            .sum::<f64>()
-            / n;
-
-        // --- Gradients: closed-form for logistic regression ---
-        // sigmoid(logits)
-        let y_hat = logits.map(|x| 1.0 / (1.0 + (-x).exp()));
-        // delta = y_hat - targets   (n, 1)
-        let delta = &y_hat - targets;
-        // dw = Xᵀ δ / n             (m, 1)
-        let weight_grad = features.transpose() * &delta / n;
-        // db = mean(δ)              (1, 1)
-        let bias_grad =
-            nalgebra::DMatrix::from_element(1, 1, delta.iter().sum::<f64>() / n);
-
-        LossOutput {
-            loss_value,
-            weight_grad,
-            bias_grad,
-        }
+        // loss_i = ln(1 + exp(x)) - x * y   (numerically-stable form)
+        // dloss_i / dx = sigmoid(x) - y
+        pointwise_output(
+            self,
+            features,
+            logits,
+            targets,
+            sample_weights,
+            weights,
+            |x, y| (softplus(x) - x * y, sigmoid(x) - y),
+        )
+    }
+
+    /// `[X 1]ᵀ S [X 1]` with `S = diag(cᵢ σ(xᵢ)(1 − σ(xᵢ)))` (see
+    /// [`pointwise_hessian`]).
+    fn hessian(
+        &self,
+        samples: &Samples<NalgebraBackend>,
+        logits: &nalgebra::DMatrix<f64>,
+        _weights: &nalgebra::DMatrix<f64>,
+    ) -> Option<nalgebra::DMatrix<f64>> {
+        let curvature = logits.iter().map(|&x| {
+            let p = sigmoid(x);
+            p * (1.0 - p)
+        });
+        Some(pointwise_hessian(
+            samples,
+            curvature,
+            self.regularization.as_ref(),
+        ))
     }
 }
 
@@ -109,46 +361,25 @@ use crate::models::backend::TorchBackend;
 
 #[cfg(feature = "torch")]
 impl LossFunction<TorchBackend> for CrossEntropy {
-    fn loss_and_gradients(
+    fn weighted_loss_and_gradients(
         &self,
         _features: &tch::Tensor,
         logits: &tch::Tensor,
         targets: &tch::Tensor,
+        sample_weights: Option<&tch::Tensor>,
         weights: &mut tch::Tensor,
         bias: &mut tch::Tensor,
     ) -> LossOutput<TorchBackend> {
-        // Clear accumulated gradients from previous iteration
-        // (must happen BEFORE backward, not after — .grad()
-        // returns a handle to the same storage that zero_grad
-        // would wipe).
-        weights.zero_grad();
-        bias.zero_grad();
-
-        // Compute BCE with logits (numerically stable,
-        // autograd-tracked).
-        // NOTE: do NOT call .set_requires_grad(true) on the
-        // loss — that would promote it to a leaf tensor and
-        // sever the autograd chain back to weights / bias.
-        let loss = logits.binary_cross_entropy_with_logits::<&tch::Tensor>(
+        // Per-sample BCE with logits (numerically stable,
+        // autograd-tracked), reduced afterwards so sample weights
+        // are normalised by their sum.
+        let per_sample = logits.binary_cross_entropy_with_logits::<&tch::Tensor>(
             targets,
             None,
             None,
-            tch::Reduction::Mean,
+            tch::Reduction::None,
         );
-
-        let loss_value = loss.double_value(&[]);
-
-        // Backward pass — populates .grad() on weights and bias
-        loss.backward();
-
-        let weight_grad = weights.grad();
-        let bias_grad = bias.grad();
-
-        LossOutput {
-            loss_value,
-            weight_grad,
-            bias_grad,
-        }
+        autograd_output(self, reduce(&per_sample, sample_weights), weights, bias)
     }
 }
 
@@ -159,6 +390,7 @@ impl LossFunction<TorchBackend> for CrossEntropy {
 #[derive(Debug)]
 pub struct CrossEntropyBuilder<'a> {
     id: Option<&'a str>,
+    regularization: Option<(RegType, f64, f64)>,
 }
 
 impl<'a> Default for CrossEntropyBuilder<'a> {
@@ -169,7 +401,10 @@ impl<'a> Default for CrossEntropyBuilder<'a> {
 
 impl<'a> CrossEntropyBuilder<'a> {
     pub fn new() -> Self {
-        CrossEntropyBuilder { id: None }
+        CrossEntropyBuilder {
+            id: None,
+            regularization: None,
+        }
     }
 
     pub fn id(mut self, id: &'a str) -> Self {
@@ -177,8 +412,27 @@ impl<'a> CrossEntropyBuilder<'a> {
         self
     }
 
+    /// Penalise the weights with strength `c` and elastic-net mix `lambda`
+    /// (see [`Regularization`]).
+    pub fn regularization(mut self, kind: RegType, c: f64, lambda: f64) -> Self {
+        self.regularization = Some((kind, c, lambda));
+        self
+    }
+
     pub fn build(self) -> Result<CrossEntropy, &'static str> {
         let id = self.id.ok_or("Missing id value")?;
-        Ok(CrossEntropy { id: id.to_string() })
+        Ok(CrossEntropy {
+            id: id.to_string(),
+            regularization: build_regularization(self.regularization)?,
+        })
     }
 }
+
+/// Validated penalty from a builder's `(kind, c, lambda)` setting.
+pub(crate) fn build_regularization(
+    regularization: Option<(RegType, f64, f64)>,
+) -> Result<Option<Regularization>, &'static str> {
+    regularization
+        .map(|(kind, c, lambda)| Regularization::new(kind, c, lambda))
+        .transpose()
+}
@@ -0,0 +1,172 @@
+//! Negative log-likelihood of a generalized linear model.
+//!
+//! [`GlmLoss`] fits a [`LinearModel`](crate::models::LinearModel) of the
+//! same [`Family`](crate::models::Family) by maximum likelihood.  With the
+//! canonical links used here the per-sample derivative is always `μ − y`,
+//! and the Hessian is `[X 1]ᵀ diag(cᵢ V(μᵢ)) [X 1]` with `V` the family's
+//! variance function, so a [`Newton`](crate::optimizers::Newton) solver
+//! runs iteratively reweighted least squares.
+
+use super::{
+    cost::{
+        RegType, Regularization, build_regularization, pointwise_hessian,
+        pointwise_output, regularized, softplus,
+    },
+    interface::{LossFunction, LossOutput, Samples},
+};
+use crate::models::{backend::NalgebraBackend, linear::Family};
+
+#[cfg(feature = "torch")]
+use super::cost::{autograd_output, reduce};
+#[cfg(feature = "torch")]
+use crate::models::backend::TorchBackend;
+
+/// Per-sample negative log-likelihood, up to terms constant in `η`:
+///
+/// ```text
+/// Binomial   ln(1 + e^η) − y η
+/// Gaussian   ½ (η − y)²
+/// Poisson    e^η − y η
+/// ```
+#[derive(Debug)]
+pub struct GlmLoss {
+    pub id: String,
+    pub family: Family,
+    pub regularization: Option<Regularization>,
+}
+
+impl GlmLoss {
+    pub fn builder<'a>() -> GlmLossBuilder<'a> {
+        GlmLossBuilder::new()
+    }
+}
+
+regularized!(GlmLoss);
+
+/// `(ℓ, ∂ℓ/∂η)` of one sample.
+fn negative_log_likelihood(family: Family, eta: f64, y: f64) -> (f64, f64) {
+    let mu = family.inverse_link(eta);
+    let value = match family {
+        Family::Binomial => softplus(eta) - y * eta,
+        Family::Gaussian => 0.5 * (eta - y).powi(2),
+        Family::Poisson => mu - y * eta,
+    };
+    (value, mu - y)
+}
+
+/// Variance function `V(μ) = ∂²ℓ/∂η²`.
+fn variance(family: Family, eta: f64) -> f64 {
+    let mu = family.inverse_link(eta);
+    match family {
+        Family::Binomial => mu * (1.0 - mu),
+        Family::Gaussian => 1.0,
+        Family::Poisson => mu,
+    }
+}
+
+impl LossFunction<NalgebraBackend> for GlmLoss {
+    fn weighted_loss_and_gradients(
+        &self,
+        features: &nalgebra::DMatrix<f64>,
+        logits: &nalgebra::DMatrix<f64>,
+        targets: &nalgebra::DMatrix<f64>,
+        sample_weights: Option<&nalgebra::DMatrix<f64>>,
+        weights: &mut nalgebra::DMatrix<f64>,
+        _bias: &mut nalgebra::DMatrix<f64>,
+    ) -> LossOutput<NalgebraBackend> {
+        let family = self.family;
+        pointwise_output(
+            self,
+            features,
+            logits,
+            targets,
+            sample_weights,
+            weights,
+            |eta, y| negative_log_likelihood(family, eta, y),
+        )
+    }
+
+    fn hessian(
+        &self,
+        samples: &Samples<NalgebraBackend>,
+        logits: &nalgebra::DMatrix<f64>,
+        _weights: &nalgebra::DMatrix<f64>,
+    ) -> Option<nalgebra::DMatrix<f64>> {
+        let curvature = logits.iter().map(|&eta| variance(self.family, eta));
+        Some(pointwise_hessian(
+            samples,
+            curvature,
+            self.regularization.as_ref(),
+        ))
+    }
+}
+
+#[cfg(feature = "torch")]
+impl LossFunction<TorchBackend> for GlmLoss {
+    fn weighted_loss_and_gradients(
+        &self,
+        _features: &tch::Tensor,
+        logits: &tch::Tensor,
+        targets: &tch::Tensor,
+        sample_weights: Option<&tch::Tensor>,
+        weights: &mut tch::Tensor,
+        bias: &mut tch::Tensor,
+    ) -> LossOutput<TorchBackend> {
+        let per_sample = match self.family {
+            Family::Binomial => logits.binary_cross_entropy_with_logits::<&tch::Tensor>(
+                targets,
+                None,
+                None,
+                tch::Reduction::None,
+            ),
+            Family::Gaussian => (logits - targets).square() * 0.5,
+            Family::Poisson => logits.exp() - targets * logits,
+        };
+        autograd_output(self, reduce(&per_sample, sample_weights), weights, bias)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+pub struct GlmLossBuilder<'a> {
+    id: Option<&'a str>,
+    family: Option<Family>,
+    regularization: Option<(RegType, f64, f64)>,
+}
+
+impl<'a> GlmLossBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Target distribution; should match the model's
+    /// [`family`](crate::models::LinearModelBuilder::family).
+    pub fn family(mut self, family: Family) -> Self {
+        self.family = Some(family);
+        self
+    }
+
+    /// Penalise the weights (see [`Regularization`]).
+    pub fn regularization(mut self, kind: RegType, c: f64, lambda: f64) -> Self {
+        self.regularization = Some((kind, c, lambda));
+        self
+    }
+
+    pub fn build(self) -> Result<GlmLoss, &'static str> {
+        let id = self.id.ok_or("Missing id value")?;
+        let family = self.family.ok_or("Missing GLM family")?;
+        Ok(GlmLoss {
+            id: id.to_string(),
+            family,
+            regularization: build_regularization(self.regularization)?,
+        })
+    }
+}
@@ -1,6 +1,46 @@
 //! Loss function trait, generic over [`crate::models::backend::ComputeBackend`].
 
 use crate::models::backend::ComputeBackend;
+use convective_data::datasets::Dataset;
+
+/// Tensors of a dataset: features, targets and optional sample weights.
+#[derive(Debug)]
+pub struct Samples<B: ComputeBackend> {
+    /// Feature matrix *(n × m)*.
+    pub features: B::Tensor,
+    /// Targets *(n × 1)* or *(n,)*.
+    pub targets: B::Tensor,
+    /// Non-negative weight of every sample, same shape as `targets`.
+    pub sample_weights: Option<B::Tensor>,
+}
+
+impl<B: ComputeBackend> Samples<B> {
+    pub fn from_dataset(dataset: &Dataset) -> Self {
+        Samples {
+            features: B::from_row_vecs(dataset.features()),
+            targets: B::from_slice(dataset.target()),
+            sample_weights: dataset.weights().map(B::from_slice),
+        }
+    }
+
+    /// Loss and gradients of `loss` on these samples.
+    pub fn loss_and_gradients(
+        &self,
+        loss: &dyn LossFunction<B>,
+        logits: &B::Tensor,
+        weights: &mut B::Tensor,
+        bias: &mut B::Tensor,
+    ) -> LossOutput<B> {
+        loss.weighted_loss_and_gradients(
+            &self.features,
+            logits,
+            &self.targets,
+            self.sample_weights.as_ref(),
+            weights,
+            bias,
+        )
+    }
+}
 
 /// Output of [`LossFunction::loss_and_gradients`].
 #[derive(Debug)]
@@ -29,7 +69,8 @@ pub struct LossOutput<B: ComputeBackend> {
 /// Fusing both operations into one method keeps the training loop fully
 /// generic while allowing each backend to use its natural strategy.
 pub trait LossFunction<B: ComputeBackend>: std::fmt::Debug + Send {
-    /// Compute loss value and parameter gradients.
+    /// Compute loss value and parameter gradients, averaging the loss over
+    /// all samples.
     ///
     /// # Parameters
     ///
@@ -47,5 +88,35 @@ pub trait LossFunction<B: ComputeBackend>: std::fmt::Debug + Send {
         targets: &B::Tensor,
         weights: &mut B::Tensor,
         bias: &mut B::Tensor,
+    ) -> LossOutput<B> {
+        self.weighted_loss_and_gradients(features, logits, targets, None, weights, bias)
+    }
+
+    /// Same as [`loss_and_gradients`](LossFunction::loss_and_gradients)
+    /// with optional per-sample weights `wᵢ` (shape of `targets`): the data
+    /// term becomes the weighted mean `Σ wᵢ ℓᵢ / Σ wᵢ`, and is zero when all
+    /// weights are zero.
+    fn weighted_loss_and_gradients(
+        &self,
+        features: &B::Tensor,
+        logits: &B::Tensor,
+        targets: &B::Tensor,
+        sample_weights: Option<&B::Tensor>,
+        weights: &mut B::Tensor,
+        bias: &mut B::Tensor,
     ) -> LossOutput<B>;
+
+    /// Hessian of the loss w.r.t. the flat parameters
+    /// `[weights..., bias...]` *(p × p)*, for losses with a closed form.
+    ///
+    /// Used by second-order solvers such as
+    /// [`Newton`](crate::optimizers::Newton); `None` by default.
+    fn hessian(
+        &self,
+        _samples: &Samples<B>,
+        _logits: &B::Tensor,
+        _weights: &B::Tensor,
+    ) -> Option<B::Tensor> {
+        None
+    }
 }
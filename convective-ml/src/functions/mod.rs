@@ -1,7 +1,16 @@
-/// Concrete loss functions.
+/// Classification losses: hinge, class-weighted and focal cross-entropy.
+pub mod classification;
+/// Cross-entropy loss and shared regularisation.
 pub mod cost;
+/// Generalized-linear-model likelihoods.
+pub mod glm;
 /// Loss function trait.
 pub mod interface;
+/// Regression losses: squared error, Huber and quantile.
+pub mod regression;
 
+pub use classification::*;
 pub use cost::*;
-pub use interface::{LossFunction, LossOutput};
+pub use glm::{GlmLoss, GlmLossBuilder};
+pub use interface::{LossFunction, LossOutput, Samples};
+pub use regression::*;
@@ -0,0 +1,379 @@
+//! Regression losses: squared error, Huber and quantile (pinball).
+//!
+//! Each loss is the (sample-weighted) mean of a pointwise term of the
+//! residual `r = z − y` between logit and target.  The nalgebra backend
+//! uses the closed-form derivative of that term, the torch backend
+//! autograd.  All of them take an optional [`Regularization`].
+
+use super::{
+    cost::{
+        RegType, Regularization, build_regularization, pointwise_hessian,
+        pointwise_output, regularized,
+    },
+    interface::{LossFunction, LossOutput, Samples},
+};
+use crate::models::backend::NalgebraBackend;
+
+#[cfg(feature = "torch")]
+use super::cost::{autograd_output, reduce};
+#[cfg(feature = "torch")]
+use crate::models::backend::TorchBackend;
+
+// ---------------------------------------------------------------------------
+// Mse
+// ---------------------------------------------------------------------------
+
+/// Mean squared error `(z − y)²`.
+#[derive(Debug)]
+pub struct Mse {
+    pub id: String,
+    pub regularization: Option<Regularization>,
+}
+
+impl Mse {
+    pub fn builder<'a>() -> MseBuilder<'a> {
+        MseBuilder::new()
+    }
+}
+
+regularized!(Mse);
+
+impl LossFunction<NalgebraBackend> for Mse {
+    fn weighted_loss_and_gradients(
+        &self,
+        features: &nalgebra::DMatrix<f64>,
+        logits: &nalgebra::DMatrix<f64>,
+        targets: &nalgebra::DMatrix<f64>,
+        sample_weights: Option<&nalgebra::DMatrix<f64>>,
+        weights: &mut nalgebra::DMatrix<f64>,
+        _bias: &mut nalgebra::DMatrix<f64>,
+    ) -> LossOutput<NalgebraBackend> {
+        pointwise_output(
+            self,
+            features,
+            logits,
+            targets,
+            sample_weights,
+            weights,
+            |z, y| ((z - y).powi(2), 2.0 * (z - y)),
+        )
+    }
+
+    /// `2 [X 1]ᵀ C [X 1]` with `C` the sample coefficients; constant in the
+    /// parameters, so one Newton step solves an unpenalised problem.
+    fn hessian(
+        &self,
+        samples: &Samples<NalgebraBackend>,
+        logits: &nalgebra::DMatrix<f64>,
+        _weights: &nalgebra::DMatrix<f64>,
+    ) -> Option<nalgebra::DMatrix<f64>> {
+        let curvature = logits.iter().map(|_| 2.0);
+        Some(pointwise_hessian(
+            samples,
+            curvature,
+            self.regularization.as_ref(),
+        ))
+    }
+}
+
+#[cfg(feature = "torch")]
+impl LossFunction<TorchBackend> for Mse {
+    fn weighted_loss_and_gradients(
+        &self,
+        _features: &tch::Tensor,
+        logits: &tch::Tensor,
+        targets: &tch::Tensor,
+        sample_weights: Option<&tch::Tensor>,
+        weights: &mut tch::Tensor,
+        bias: &mut tch::Tensor,
+    ) -> LossOutput<TorchBackend> {
+        let per_sample = logits.mse_loss(targets, tch::Reduction::None);
+        autograd_output(self, reduce(&per_sample, sample_weights), weights, bias)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Huber
+// ---------------------------------------------------------------------------
+
+/// Huber loss: quadratic for small residuals, linear for large ones.
+///
+/// ```text
+/// ½ r²             |r| ≤ δ
+/// δ (|r| − ½ δ)    |r| > δ
+/// ```
+#[derive(Debug)]
+pub struct Huber {
+    pub id: String,
+    pub delta: f64,
+    pub regularization: Option<Regularization>,
+}
+
+impl Huber {
+    pub fn builder<'a>() -> HuberBuilder<'a> {
+        HuberBuilder::new()
+    }
+}
+
+regularized!(Huber);
+
+impl LossFunction<NalgebraBackend> for Huber {
+    fn weighted_loss_and_gradients(
+        &self,
+        features: &nalgebra::DMatrix<f64>,
+        logits: &nalgebra::DMatrix<f64>,
+        targets: &nalgebra::DMatrix<f64>,
+        sample_weights: Option<&nalgebra::DMatrix<f64>>,
+        weights: &mut nalgebra::DMatrix<f64>,
+        _bias: &mut nalgebra::DMatrix<f64>,
+    ) -> LossOutput<NalgebraBackend> {
+        let delta = self.delta;
+        pointwise_output(
+            self,
+            features,
+            logits,
+            targets,
+            sample_weights,
+            weights,
+            |z, y| {
+                let r = z - y;
+                if r.abs() <= delta {
+                    (0.5 * r * r, r)
+                } else {
+                    (delta * (r.abs() - 0.5 * delta), delta * r.signum())
+                }
+            },
+        )
+    }
+
+    /// Squared-error curvature on the samples inside the quadratic zone,
+    /// none outside it.
+    fn hessian(
+        &self,
+        samples: &Samples<NalgebraBackend>,
+        logits: &nalgebra::DMatrix<f64>,
+        _weights: &nalgebra::DMatrix<f64>,
+    ) -> Option<nalgebra::DMatrix<f64>> {
+        let curvature = logits.iter().zip(samples.targets.iter()).map(|(z, y)| {
+            if (z - y).abs() <= self.delta {
+                1.0
+            } else {
+                0.0
+            }
+        });
+        Some(pointwise_hessian(
+            samples,
+            curvature,
+            self.regularization.as_ref(),
+        ))
+    }
+}
+
+#[cfg(feature = "torch")]
+impl LossFunction<TorchBackend> for Huber {
+    fn weighted_loss_and_gradients(
+        &self,
+        _features: &tch::Tensor,
+        logits: &tch::Tensor,
+        targets: &tch::Tensor,
+        sample_weights: Option<&tch::Tensor>,
+        weights: &mut tch::Tensor,
+        bias: &mut tch::Tensor,
+    ) -> LossOutput<TorchBackend> {
+        let per_sample = logits.huber_loss(targets, tch::Reduction::None, self.delta);
+        autograd_output(self, reduce(&per_sample, sample_weights), weights, bias)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Quantile
+// ---------------------------------------------------------------------------
+
+/// Pinball loss of the `tau`-quantile, with `u = y − z`:
+///
+/// ```text
+/// τ u          u ≥ 0
+/// (τ − 1) u    u < 0
+/// ```
+///
+/// `tau = 0.5` gives half the mean absolute error.  At `u = 0` the
+/// nalgebra backend uses the subgradient `−τ`.
+#[derive(Debug)]
+pub struct Quantile {
+    pub id: String,
+    pub tau: f64,
+    pub regularization: Option<Regularization>,
+}
+
+impl Quantile {
+    pub fn builder<'a>() -> QuantileBuilder<'a> {
+        QuantileBuilder::new()
+    }
+}
+
+regularized!(Quantile);
+
+impl LossFunction<NalgebraBackend> for Quantile {
+    fn weighted_loss_and_gradients(
+        &self,
+        features: &nalgebra::DMatrix<f64>,
+        logits: &nalgebra::DMatrix<f64>,
+        targets: &nalgebra::DMatrix<f64>,
+        sample_weights: Option<&nalgebra::DMatrix<f64>>,
+        weights: &mut nalgebra::DMatrix<f64>,
+        _bias: &mut nalgebra::DMatrix<f64>,
+    ) -> LossOutput<NalgebraBackend> {
+        let tau = self.tau;
+        pointwise_output(
+            self,
+            features,
+            logits,
+            targets,
+            sample_weights,
+            weights,
+            |z, y| {
+                let u = y - z;
+                let slope = if u < 0.0 { tau - 1.0 } else { tau };
+                (slope * u, -slope)
+            },
+        )
+    }
+}
+
+#[cfg(feature = "torch")]
+impl LossFunction<TorchBackend> for Quantile {
+    fn weighted_loss_and_gradients(
+        &self,
+        _features: &tch::Tensor,
+        logits: &tch::Tensor,
+        targets: &tch::Tensor,
+        sample_weights: Option<&tch::Tensor>,
+        weights: &mut tch::Tensor,
+        bias: &mut tch::Tensor,
+    ) -> LossOutput<TorchBackend> {
+        let u = targets - logits;
+        let per_sample = (&u * self.tau).maximum(&(&u * (self.tau - 1.0)));
+        autograd_output(self, reduce(&per_sample, sample_weights), weights, bias)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Builders
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+pub struct MseBuilder<'a> {
+    id: Option<&'a str>,
+    regularization: Option<(RegType, f64, f64)>,
+}
+
+impl<'a> MseBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Penalise the weights (see [`Regularization`]).
+    pub fn regularization(mut self, kind: RegType, c: f64, lambda: f64) -> Self {
+        self.regularization = Some((kind, c, lambda));
+        self
+    }
+
+    pub fn build(self) -> Result<Mse, &'static str> {
+        let id = self.id.ok_or("Missing id value")?;
+        Ok(Mse {
+            id: id.to_string(),
+            regularization: build_regularization(self.regularization)?,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct HuberBuilder<'a> {
+    id: Option<&'a str>,
+    delta: Option<f64>,
+    regularization: Option<(RegType, f64, f64)>,
+}
+
+impl<'a> HuberBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Residual at which the loss turns linear (default `1.0`).
+    pub fn delta(mut self, delta: f64) -> Self {
+        self.delta = Some(delta);
+        self
+    }
+
+    /// Penalise the weights (see [`Regularization`]).
+    pub fn regularization(mut self, kind: RegType, c: f64, lambda: f64) -> Self {
+        self.regularization = Some((kind, c, lambda));
+        self
+    }
+
+    pub fn build(self) -> Result<Huber, &'static str> {
+        let id = self.id.ok_or("Missing id value")?;
+        let delta = self.delta.unwrap_or(1.0);
+        if !delta.is_finite() || delta <= 0.0 {
+            return Err("Huber delta must be positive");
+        }
+        Ok(Huber {
+            id: id.to_string(),
+            delta,
+            regularization: build_regularization(self.regularization)?,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct QuantileBuilder<'a> {
+    id: Option<&'a str>,
+    tau: Option<f64>,
+    regularization: Option<(RegType, f64, f64)>,
+}
+
+impl<'a> QuantileBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Target quantile in `(0, 1)`.
+    pub fn tau(mut self, tau: f64) -> Self {
+        self.tau = Some(tau);
+        self
+    }
+
+    /// Penalise the weights (see [`Regularization`]).
+    pub fn regularization(mut self, kind: RegType, c: f64, lambda: f64) -> Self {
+        self.regularization = Some((kind, c, lambda));
+        self
+    }
+
+    pub fn build(self) -> Result<Quantile, &'static str> {
+        let id = self.id.ok_or("Missing id value")?;
+        let tau = self.tau.ok_or("Missing quantile tau")?;
+        if !(tau > 0.0 && tau < 1.0) {
+            return Err("Quantile tau must be in (0, 1)");
+        }
+        Ok(Quantile {
+            id: id.to_string(),
+            tau,
+            regularization: build_regularization(self.regularization)?,
+        })
+    }
+}
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LabelError {
+    #[error("Empty price series")]
+    EmptySeries,
+
+    #[error("Non-positive price {price} at row {index}")]
+    NonPositivePrice { index: usize, price: f64 },
+
+    #[error("Length mismatch: expected {expected}, got {got}")]
+    LengthMismatch { expected: usize, got: usize },
+
+    #[error("Invalid configuration: {message}")]
+    InvalidConfig { message: String },
+
+    #[error("Column not found: {name}")]
+    ColumnNotFound { name: String },
+}
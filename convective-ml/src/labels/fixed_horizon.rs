@@ -0,0 +1,77 @@
+//! Fixed-horizon return labels.
+//!
+//! For each row `t` the forward return `r = p[t+h] / p[t] - 1` is mapped to
+//! a direction: up if `r > τ`, down if `r < -τ`, flat otherwise.  The
+//! dead-zone `τ` keeps microstructure noise out of the up/down classes.
+
+use super::{LabelEncoding, LabelError, validate_prices};
+
+#[derive(Debug, Clone)]
+pub struct FixedHorizon {
+    /// Look-ahead in rows.
+    pub horizon: usize,
+    /// Dead-zone half-width `τ` on the forward return.
+    pub threshold: f64,
+    pub encoding: LabelEncoding,
+}
+
+impl FixedHorizon {
+    pub fn new(horizon: usize, threshold: f64, encoding: LabelEncoding) -> Self {
+        Self {
+            horizon,
+            threshold,
+            encoding,
+        }
+    }
+
+    /// Forward returns `p[t+h] / p[t] - 1`, `None` for the last `h` rows.
+    pub fn forward_returns(
+        &self,
+        prices: &[f64],
+    ) -> Result<Vec<Option<f64>>, LabelError> {
+        self.validate()?;
+        validate_prices(prices)?;
+
+        let n = prices.len();
+        Ok((0..n)
+            .map(|t| {
+                let end = t + self.horizon;
+                (end < n).then(|| prices[end] / prices[t] - 1.0)
+            })
+            .collect())
+    }
+
+    /// Encoded labels, one per row.
+    pub fn label(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, LabelError> {
+        let returns = self.forward_returns(prices)?;
+        Ok(returns
+            .into_iter()
+            .map(|r| {
+                r.and_then(|r| {
+                    let direction = if r > self.threshold {
+                        1
+                    } else if r < -self.threshold {
+                        -1
+                    } else {
+                        0
+                    };
+                    self.encoding.encode(direction)
+                })
+            })
+            .collect())
+    }
+
+    fn validate(&self) -> Result<(), LabelError> {
+        if self.horizon == 0 {
+            return Err(LabelError::InvalidConfig {
+                message: "horizon must be at least 1".to_string(),
+            });
+        }
+        if self.threshold < 0.0 {
+            return Err(LabelError::InvalidConfig {
+                message: "threshold must be non-negative".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
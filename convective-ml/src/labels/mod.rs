@@ -0,0 +1,117 @@
+//! Label generation.
+//!
+//! Turns a price series (typically the `midprice` or `microprice` column of
+//! the feature matrix) into supervised-learning targets:
+//!
+//! - [`FixedHorizon`](crate::labels::FixedHorizon): sign of the `h`-step
+//!   forward return, with a dead-zone.
+//! - [`TripleBarrier`](crate::labels::TripleBarrier): first touch of a
+//!   volatility-scaled profit-taking / stop-loss barrier or a vertical
+//!   (time) barrier, with optional meta-labels for a primary model's side.
+//! - [`TrendScanning`](crate::labels::TrendScanning): sign of the most
+//!   significant forward linear trend.
+//!
+//! Every labeler returns one `Option<f64>` per input row, so labels stay
+//! aligned with the feature matrix.  Rows whose label cannot be computed
+//! (e.g. not enough look-ahead at the end of the series) are `None`; use
+//! [`align`](crate::labels::align) to drop them together with their
+//! feature rows.
+
+pub mod errors;
+pub mod fixed_horizon;
+pub mod trend_scanning;
+pub mod triple_barrier;
+
+pub use errors::LabelError;
+pub use fixed_horizon::FixedHorizon;
+pub use trend_scanning::{TrendScan, TrendScanning};
+pub use triple_barrier::{Barrier, BarrierEvent, TripleBarrier};
+
+use crate::features::compute_market::ALL_FEATURE_NAMES;
+
+/// How a directional outcome is encoded as a target value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelEncoding {
+    /// `1.0` for up, `0.0` for down; flat outcomes have no label.
+    ///
+    /// Matches the binary targets expected by
+    /// [`CrossEntropy`](crate::functions::CrossEntropy).
+    Binary,
+    /// `1.0` up, `0.0` flat, `-1.0` down.
+    Signed,
+    /// Class indices `2.0` up, `1.0` flat, `0.0` down.
+    ///
+    /// Matches the targets expected by
+    /// [`SoftmaxCrossEntropy`](crate::functions::SoftmaxCrossEntropy).
+    Classes,
+}
+
+impl LabelEncoding {
+    /// Encode a direction `d ∈ {-1, 0, 1}`.
+    pub fn encode(&self, direction: i8) -> Option<f64> {
+        match (self, direction.signum()) {
+            (LabelEncoding::Binary, 1) => Some(1.0),
+            (LabelEncoding::Binary, -1) => Some(0.0),
+            (LabelEncoding::Binary, _) => None,
+            (LabelEncoding::Signed, d) => Some(d as f64),
+            (LabelEncoding::Classes, d) => Some((d + 1) as f64),
+        }
+    }
+}
+
+/// Extract a named column of a feature matrix produced by
+/// [`compute_all_features`](crate::features::compute_market::compute_all_features).
+pub fn price_column(matrix: &[Vec<f64>], name: &str) -> Result<Vec<f64>, LabelError> {
+    let idx = ALL_FEATURE_NAMES
+        .iter()
+        .position(|&n| n == name)
+        .ok_or_else(|| LabelError::ColumnNotFound {
+            name: name.to_string(),
+        })?;
+
+    matrix
+        .iter()
+        .map(|row| {
+            row.get(idx)
+                .copied()
+                .ok_or_else(|| LabelError::ColumnNotFound {
+                    name: name.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Keep only the rows that carry a label.
+///
+/// Returns the filtered feature matrix and the matching target vector,
+/// ready for `Dataset` construction.
+pub fn align(
+    features: &[Vec<f64>],
+    labels: &[Option<f64>],
+) -> Result<(Vec<Vec<f64>>, Vec<f64>), LabelError> {
+    if features.len() != labels.len() {
+        return Err(LabelError::LengthMismatch {
+            expected: features.len(),
+            got: labels.len(),
+        });
+    }
+
+    Ok(features
+        .iter()
+        .zip(labels)
+        .filter_map(|(row, label)| label.map(|y| (row.clone(), y)))
+        .unzip())
+}
+
+pub(crate) fn validate_prices(prices: &[f64]) -> Result<(), LabelError> {
+    if prices.is_empty() {
+        return Err(LabelError::EmptySeries);
+    }
+    match prices.iter().position(|&p| !(p > 0.0 && p.is_finite())) {
+        Some(index) => Err(LabelError::NonPositivePrice {
+            index,
+            price: prices[index],
+        }),
+        None => Ok(()),
+    }
+}
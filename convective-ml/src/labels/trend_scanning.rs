@@ -0,0 +1,135 @@
+//! Trend-scanning labels.
+//!
+//! For each row `t`, an OLS line `p[t+i] = a + b·i` is fitted over every
+//! forward window length `L ∈ [min_window, max_window]`.  The window with the
+//! largest `|t(b)|` is kept; its sign is the label and its t-value measures
+//! the strength of the trend.
+
+use super::{LabelEncoding, LabelError, validate_prices};
+
+/// Most significant forward trend found at one row.
+#[derive(Debug, Clone)]
+pub struct TrendScan {
+    /// t-statistic of the slope.
+    pub t_value: f64,
+    /// Window length (in rows) that maximised `|t_value|`.
+    pub window: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrendScanning {
+    pub min_window: usize,
+    pub max_window: usize,
+    /// Minimum `|t_value|` for an up/down label; weaker trends are flat.
+    pub min_t_value: f64,
+    pub encoding: LabelEncoding,
+}
+
+impl Default for TrendScanning {
+    fn default() -> Self {
+        Self {
+            min_window: 5,
+            max_window: 50,
+            min_t_value: 0.0,
+            encoding: LabelEncoding::Binary,
+        }
+    }
+}
+
+impl TrendScanning {
+    /// Best trend per row; `None` when fewer than `min_window` rows remain.
+    pub fn scan(&self, prices: &[f64]) -> Result<Vec<Option<TrendScan>>, LabelError> {
+        self.validate()?;
+        validate_prices(prices)?;
+
+        let n = prices.len();
+        Ok((0..n)
+            .map(|t| {
+                (self.min_window..=self.max_window)
+                    .take_while(|&len| t + len <= n)
+                    .filter_map(|len| {
+                        slope_t_value(&prices[t..t + len]).map(|t_value| TrendScan {
+                            t_value,
+                            window: len,
+                        })
+                    })
+                    .max_by(|a, b| a.t_value.abs().total_cmp(&b.t_value.abs()))
+            })
+            .collect())
+    }
+
+    /// Encoded trend direction, one per row.
+    pub fn label(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, LabelError> {
+        Ok(self
+            .scan(prices)?
+            .into_iter()
+            .map(|s| {
+                s.and_then(|s| {
+                    let direction = if s.t_value.abs() < self.min_t_value {
+                        0
+                    } else if s.t_value > 0.0 {
+                        1
+                    } else if s.t_value < 0.0 {
+                        -1
+                    } else {
+                        0
+                    };
+                    self.encoding.encode(direction)
+                })
+            })
+            .collect())
+    }
+
+    fn validate(&self) -> Result<(), LabelError> {
+        if self.min_window < 3 {
+            return Err(LabelError::InvalidConfig {
+                message: "min_window must be at least 3".to_string(),
+            });
+        }
+        if self.max_window < self.min_window {
+            return Err(LabelError::InvalidConfig {
+                message: "max_window must be >= min_window".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// t-statistic of the OLS slope of `y` on `0..y.len()`.
+///
+/// A perfect fit (zero residual variance) yields `±∞` so that noiseless
+/// trends always win the scan.
+fn slope_t_value(y: &[f64]) -> Option<f64> {
+    let n = y.len() as f64;
+    if n < 3.0 {
+        return None;
+    }
+
+    let x_mean = (n - 1.0) / 2.0;
+    let y_mean = y.iter().sum::<f64>() / n;
+
+    let (sxy, sxx) = y
+        .iter()
+        .enumerate()
+        .fold((0.0, 0.0), |(sxy, sxx), (i, &yi)| {
+            let dx = i as f64 - x_mean;
+            (sxy + dx * (yi - y_mean), sxx + dx * dx)
+        });
+    let slope = sxy / sxx;
+    let intercept = y_mean - slope * x_mean;
+
+    let sse: f64 = y
+        .iter()
+        .enumerate()
+        .map(|(i, &yi)| (yi - intercept - slope * i as f64).powi(2))
+        .sum();
+    let se = (sse / (n - 2.0) / sxx).sqrt();
+
+    if se > 0.0 {
+        Some(slope / se)
+    } else if slope != 0.0 {
+        Some(slope.signum() * f64::INFINITY)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,238 @@
+//! Triple-barrier labels.
+//!
+//! For each row `t` three barriers are placed:
+//!
+//! - upper: `p[t] · (1 + pt · σₜ)` (profit taking for a long),
+//! - lower: `p[t] · (1 - sl · σₜ)` (stop loss for a long),
+//! - vertical: row `t + max_holding`.
+//!
+//! `σₜ` is an EWMA estimate of the one-step return volatility using returns
+//! up to and including row `t` (no look-ahead).  The label is the direction
+//! of the first barrier touched; when the vertical barrier is hit first the
+//! sign of the return at that point is used.  Rows where `σₜ` is zero (flat
+//! prices) or not finite have no horizontal barriers and always end at the
+//! vertical one.
+//!
+//! With a primary model's side `sₜ ∈ {-1, 1}` the barriers are oriented by
+//! that side and [`TripleBarrier::meta_label`] emits `1.0` when the bet
+//! would have been profitable and `0.0` otherwise.
+
+use super::{LabelEncoding, LabelError, validate_prices};
+
+/// Barrier that ended an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Barrier {
+    Upper,
+    Lower,
+    Vertical,
+}
+
+/// Outcome of one triple-barrier event started at row `start`.
+#[derive(Debug, Clone)]
+pub struct BarrierEvent {
+    pub start: usize,
+    pub end: usize,
+    /// Return `p[end] / p[start] - 1`.
+    pub ret: f64,
+    pub barrier: Barrier,
+}
+
+impl BarrierEvent {
+    /// Direction of the event: `1` up, `-1` down, `0` flat.
+    pub fn direction(&self) -> i8 {
+        match self.barrier {
+            Barrier::Upper => 1,
+            Barrier::Lower => -1,
+            Barrier::Vertical if self.ret > 0.0 => 1,
+            Barrier::Vertical if self.ret < 0.0 => -1,
+            Barrier::Vertical => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TripleBarrier {
+    /// Profit-taking multiple of `σₜ`; `None` disables the barrier.
+    pub profit_taking: Option<f64>,
+    /// Stop-loss multiple of `σₜ`; `None` disables the barrier.
+    pub stop_loss: Option<f64>,
+    /// Vertical barrier, in rows after the event start.
+    pub max_holding: usize,
+    /// Span of the EWMA volatility estimator.
+    pub vol_span: usize,
+    pub encoding: LabelEncoding,
+}
+
+impl Default for TripleBarrier {
+    fn default() -> Self {
+        Self {
+            profit_taking: Some(1.0),
+            stop_loss: Some(1.0),
+            max_holding: 50,
+            vol_span: 100,
+            encoding: LabelEncoding::Binary,
+        }
+    }
+}
+
+impl TripleBarrier {
+    /// EWMA volatility of one-step returns; `None` on the first row.
+    pub fn volatility(&self, prices: &[f64]) -> Vec<Option<f64>> {
+        let alpha = 2.0 / (self.vol_span as f64 + 1.0);
+        let mut var: Option<f64> = None;
+        let mut out = Vec::with_capacity(prices.len());
+        out.push(None);
+
+        for w in prices.windows(2) {
+            let r = w[1] / w[0] - 1.0;
+            let v = match var {
+                Some(prev) => alpha * r * r + (1.0 - alpha) * prev,
+                None => r * r,
+            };
+            var = Some(v);
+            out.push(Some(v.sqrt()));
+        }
+        out
+    }
+
+    /// Run one event per row with barriers oriented by `sides`
+    /// (`None` ⇒ long everywhere).
+    pub fn events(
+        &self,
+        prices: &[f64],
+        sides: Option<&[f64]>,
+    ) -> Result<Vec<Option<BarrierEvent>>, LabelError> {
+        self.validate()?;
+        validate_prices(prices)?;
+        if let Some(sides) = sides {
+            if sides.len() != prices.len() {
+                return Err(LabelError::LengthMismatch {
+                    expected: prices.len(),
+                    got: sides.len(),
+                });
+            }
+        }
+
+        let n = prices.len();
+        let vol = self.volatility(prices);
+
+        Ok((0..n)
+            .map(|t| {
+                let sigma = vol[t]?;
+                let side = sides.map_or(1.0, |s| s[t].signum());
+                if side == 0.0 {
+                    return None;
+                }
+                self.first_touch(prices, t, sigma, side)
+            })
+            .collect())
+    }
+
+    /// Encoded direction of the first barrier touched, one per row.
+    pub fn label(&self, prices: &[f64]) -> Result<Vec<Option<f64>>, LabelError> {
+        Ok(self
+            .events(prices, None)?
+            .into_iter()
+            .map(|e| e.and_then(|e| self.encoding.encode(e.direction())))
+            .collect())
+    }
+
+    /// Meta-labels for a primary model's `sides`: `1.0` if trading that side
+    /// at row `t` would have closed with a positive return, `0.0` otherwise.
+    pub fn meta_label(
+        &self,
+        prices: &[f64],
+        sides: &[f64],
+    ) -> Result<Vec<Option<f64>>, LabelError> {
+        Ok(self
+            .events(prices, Some(sides))?
+            .into_iter()
+            .enumerate()
+            .map(|(t, e)| {
+                e.map(|e| {
+                    if sides[t].signum() * e.ret > 0.0 {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                })
+            })
+            .collect())
+    }
+
+    fn first_touch(
+        &self,
+        prices: &[f64],
+        start: usize,
+        sigma: f64,
+        side: f64,
+    ) -> Option<BarrierEvent> {
+        let p0 = prices[start];
+        // Profit / loss distances in return space, oriented by side.  A zero
+        // width would fire on the first flat step, so without a usable
+        // volatility only the vertical barrier remains.
+        let usable = sigma.is_finite() && sigma > 0.0;
+        let take = self.profit_taking.filter(|_| usable).map(|m| m * sigma);
+        let stop = self.stop_loss.filter(|_| usable).map(|m| m * sigma);
+        let (upper, lower) = if side > 0.0 {
+            (take, stop)
+        } else {
+            (stop, take)
+        };
+
+        let last = start + self.max_holding;
+        for (end, &p) in prices
+            .iter()
+            .enumerate()
+            .skip(start + 1)
+            .take(self.max_holding)
+        {
+            let ret = p / p0 - 1.0;
+            if upper.is_some_and(|u| ret >= u) {
+                return Some(BarrierEvent {
+                    start,
+                    end,
+                    ret,
+                    barrier: Barrier::Upper,
+                });
+            }
+            if lower.is_some_and(|l| ret <= -l) {
+                return Some(BarrierEvent {
+                    start,
+                    end,
+                    ret,
+                    barrier: Barrier::Lower,
+                });
+            }
+        }
+
+        // No horizontal touch: the vertical barrier must lie inside the series.
+        (last < prices.len()).then(|| BarrierEvent {
+            start,
+            end: last,
+            ret: prices[last] / p0 - 1.0,
+            barrier: Barrier::Vertical,
+        })
+    }
+
+    fn validate(&self) -> Result<(), LabelError> {
+        if self.max_holding == 0 {
+            return Err(LabelError::InvalidConfig {
+                message: "max_holding must be at least 1".to_string(),
+            });
+        }
+        if self.vol_span == 0 {
+            return Err(LabelError::InvalidConfig {
+                message: "vol_span must be at least 1".to_string(),
+            });
+        }
+        if self.profit_taking.is_some_and(|m| m <= 0.0)
+            || self.stop_loss.is_some_and(|m| m <= 0.0)
+        {
+            return Err(LabelError::InvalidConfig {
+                message: "barrier multiples must be positive".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
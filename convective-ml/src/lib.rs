@@ -11,12 +11,24 @@ pub mod models;
 /// Optimizers and Learning Algorithms
 pub mod optimizers;
 
+/// Training processes
+pub mod processes;
+
 /// Various metrics
 pub mod metrics;
 
 /// Features computation
 pub mod features;
 
+/// Feature quality analytics
+pub mod analysis;
+
+/// Target label generation
+pub mod labels;
+
+/// Feature normalisation and transformation pipelines
+pub mod transforms;
+
 // Re-export the main functionality
 pub use features::{
     Feature, FeatureCategory, FeatureError, FeatureSelector, FeaturesOutput,
@@ -27,6 +39,9 @@ pub use features::{
 // Re-export multi-source compute
 pub use features::compute_market::{ALL_FEATURE_NAMES, compute_all_features};
 
+// Re-export information-driven bars
+pub use features::bars::{AdaptiveConfig, BarType, sample_bars};
+
 // Re-export the registries
 pub use features::registry::{
     LIQUIDATION_FEATURES, MARKET_FEATURES, ORDERBOOK_FEATURES, TRADE_FEATURES,
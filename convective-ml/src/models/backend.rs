@@ -30,6 +30,16 @@ pub trait ComputeBackend: Sized + Send + Sync + 'static {
     /// Build a column-vector tensor from a flat slice (targets / labels).
     fn from_slice(data: &[f64]) -> Self::Tensor;
 
+    /// Build a tensor shaped like `like` from `data` laid out in
+    /// [`to_vec`](ComputeBackend::to_vec) order.
+    fn from_vec_like(data: &[f64], like: &Self::Tensor) -> Self::Tensor;
+
+    /// Flatten a tensor into a `Vec<f64>` in the backend's storage order:
+    /// column-major for [`NalgebraBackend`], row-major for
+    /// [`TorchBackend`].  Flat parameters and flat gradients of one backend
+    /// therefore line up entry by entry, but not across backends.
+    fn to_vec(t: &Self::Tensor) -> Vec<f64>;
+
     /// Human-readable shape description (for tracing / debug).
     fn shape_info(t: &Self::Tensor) -> String;
 }
@@ -100,6 +110,14 @@ impl ComputeBackend for NalgebraBackend {
         nalgebra::DMatrix::from_column_slice(data.len(), 1, data)
     }
 
+    fn from_vec_like(data: &[f64], like: &Self::Tensor) -> Self::Tensor {
+        nalgebra::DMatrix::from_column_slice(like.nrows(), like.ncols(), data)
+    }
+
+    fn to_vec(t: &Self::Tensor) -> Vec<f64> {
+        t.iter().copied().collect()
+    }
+
     fn shape_info(t: &Self::Tensor) -> String {
         format!("({}, {})", t.nrows(), t.ncols())
     }
@@ -135,6 +153,26 @@ impl ComputeBackend for TorchBackend {
         tch::Tensor::from_slice(data).to_kind(tch::Kind::Float)
     }
 
+    fn from_vec_like(data: &[f64], like: &Self::Tensor) -> Self::Tensor {
+        tch::Tensor::from_slice(data)
+            .reshape(like.size())
+            .to_kind(like.kind())
+    }
+
+    fn to_vec(t: &Self::Tensor) -> Vec<f64> {
+        let flat = t
+            .detach()
+            .to_device(tch::Device::Cpu)
+            .to_kind(tch::Kind::Double)
+            .flatten(0, -1)
+            .contiguous();
+        Vec::<f64>::try_from(&flat).unwrap_or_else(|_| {
+            (0..flat.numel() as i64)
+                .map(|i| flat.double_value(&[i]))
+                .collect()
+        })
+    }
+
     fn shape_info(t: &Self::Tensor) -> String {
         format!("{:?}", t.size())
     }
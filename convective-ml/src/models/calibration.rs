@@ -0,0 +1,273 @@
+//! Post-hoc probability calibration of binary classifiers.
+//!
+//! A [`Calibrator`] maps the raw score (logit) of a trained model to a
+//! probability.  It is fitted on a held-out validation set, never on the
+//! training data, since a model's scores on its own training samples are
+//! over-confident.
+//!
+//! - [`Calibration::Platt`]: logistic regression on the score,
+//!   `p = σ(a z + b)` (Platt, 1999), with Platt's smoothed targets.  Two
+//!   parameters, so it works with small validation sets.
+//! - [`Calibration::Isotonic`]: non-decreasing piecewise-linear map fitted
+//!   by pool-adjacent-violators (Zadrozny & Elkan, 2002).  More flexible,
+//!   but needs more data to avoid overfitting.
+
+use serde::{Deserialize, Serialize};
+
+/// Calibration method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Calibration {
+    Platt,
+    Isotonic,
+}
+
+/// Fitted score-to-probability map.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Calibrator {
+    /// `p = σ(a z + b)`.
+    Platt { a: f64, b: f64 },
+    /// Linear interpolation through `(scores[i], probabilities[i])`, with
+    /// `scores` increasing and `probabilities` non-decreasing; constant
+    /// beyond the first and last score.
+    Isotonic {
+        scores: Vec<f64>,
+        probabilities: Vec<f64>,
+    },
+}
+
+impl Calibrator {
+    /// Fit `method` on the raw `scores` of validation samples with binary
+    /// `labels` and optional non-negative sample `weights`.
+    pub fn fit(
+        method: Calibration,
+        scores: &[f64],
+        labels: &[f64],
+        weights: Option<&[f64]>,
+    ) -> Result<Self, &'static str> {
+        if scores.is_empty() {
+            return Err("Calibration needs at least one sample");
+        }
+        if scores.len() != labels.len()
+            || weights.is_some_and(|w| w.len() != scores.len())
+        {
+            return Err("Scores, labels and weights have different lengths");
+        }
+        if scores.iter().any(|s| !s.is_finite()) {
+            return Err("Scores must be finite");
+        }
+        if labels.iter().any(|&y| y != 0.0 && y != 1.0) {
+            return Err("Calibration labels must be 0 or 1");
+        }
+        if let Some(weights) = weights {
+            if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+                return Err("Calibration weights must be finite and non-negative");
+            }
+            if weights.iter().sum::<f64>() <= 0.0 {
+                return Err("Calibration weights must have a positive sum");
+            }
+        }
+        let weights = weights.map_or_else(|| vec![1.0; scores.len()], <[f64]>::to_vec);
+
+        match method {
+            Calibration::Platt => fit_platt(scores, labels, &weights),
+            Calibration::Isotonic => fit_isotonic(scores, labels, &weights),
+        }
+    }
+
+    /// Calibrated probability of raw score `score`.
+    pub fn probability(&self, score: f64) -> f64 {
+        match self {
+            Calibrator::Platt { a, b } => sigmoid(a * score + b),
+            Calibrator::Isotonic {
+                scores,
+                probabilities,
+            } => {
+                let i = scores.partition_point(|&s| s <= score);
+                if i == 0 {
+                    return probabilities[0];
+                }
+                if i == scores.len() {
+                    return probabilities[i - 1];
+                }
+                let (x0, x1) = (scores[i - 1], scores[i]);
+                let (p0, p1) = (probabilities[i - 1], probabilities[i]);
+                p0 + (p1 - p0) * (score - x0) / (x1 - x0)
+            }
+        }
+    }
+
+    /// Name and row-major `(rows, data)` of the tensor persisting this
+    /// calibrator next to the model parameters.
+    pub(crate) fn to_tensor(&self) -> (&'static str, usize, Vec<f64>) {
+        match self {
+            Calibrator::Platt { a, b } => ("platt", 1, vec![*a, *b]),
+            Calibrator::Isotonic {
+                scores,
+                probabilities,
+            } => {
+                let mut data = scores.clone();
+                data.extend(probabilities);
+                ("isotonic", 2, data)
+            }
+        }
+    }
+
+    /// Inverse of [`to_tensor`](Calibrator::to_tensor); `None` for tensors
+    /// that do not hold a calibrator.
+    pub(crate) fn from_tensor(name: &str, data: &[f64]) -> Option<Self> {
+        match (name, data) {
+            ("platt", &[a, b]) => Some(Calibrator::Platt { a, b }),
+            ("isotonic", data) if !data.is_empty() && data.len() % 2 == 0 => {
+                let (scores, probabilities) = data.split_at(data.len() / 2);
+                Some(Calibrator::Isotonic {
+                    scores: scores.to_vec(),
+                    probabilities: probabilities.to_vec(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+/// Newton's method on the weighted log-loss of `σ(a z + b)` against
+/// Platt's targets `(N₊ + 1) / (N₊ + 2)` and `1 / (N₋ + 2)`.
+fn fit_platt(
+    scores: &[f64],
+    labels: &[f64],
+    weights: &[f64],
+) -> Result<Calibrator, &'static str> {
+    const MAX_ITERATIONS: usize = 100;
+    const TOLERANCE: f64 = 1e-10;
+
+    let positives: f64 = labels.iter().zip(weights).map(|(y, w)| y * w).sum();
+    let negatives: f64 = weights.iter().sum::<f64>() - positives;
+    let high = (positives + 1.0) / (positives + 2.0);
+    let low = 1.0 / (negatives + 2.0);
+    let targets: Vec<f64> = labels
+        .iter()
+        .map(|&y| if y == 1.0 { high } else { low })
+        .collect();
+
+    let objective = |a: f64, b: f64| -> f64 {
+        scores
+            .iter()
+            .zip(&targets)
+            .zip(weights)
+            .map(|((&z, &t), &w)| {
+                let f = a * z + b;
+                // −t ln σ(f) − (1 − t) ln(1 − σ(f)) = ln(1 + eᶠ) − t f
+                w * (f.max(0.0) + (-f.abs()).exp().ln_1p() - t * f)
+            })
+            .sum()
+    };
+
+    // Start from the identity map of a logit score.
+    let (mut a, mut b) = (1.0, 0.0);
+    let mut current = objective(a, b);
+    for _ in 0..MAX_ITERATIONS {
+        let (mut ga, mut gb, mut haa, mut hab, mut hbb) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for ((&z, &t), &w) in scores.iter().zip(&targets).zip(weights) {
+            let p = sigmoid(a * z + b);
+            let (d, v) = (w * (p - t), w * p * (1.0 - p));
+            ga += d * z;
+            gb += d;
+            haa += v * z * z;
+            hab += v * z;
+            hbb += v;
+        }
+        if ga.abs().max(gb.abs()) < TOLERANCE {
+            break;
+        }
+        // A ridge relative to the curvature keeps the 2 × 2 system solvable
+        // when the scores are (nearly) constant.
+        let ridge = 1e-6 * (haa + hbb);
+        let (haa, hbb) = (haa + ridge, hbb + ridge);
+        let det = haa * hbb - hab * hab;
+        if det.is_nan() || det <= 0.0 {
+            break;
+        }
+        let (da, db) = ((hbb * ga - hab * gb) / det, (haa * gb - hab * ga) / det);
+
+        // Backtrack until the objective decreases; stop if it never does.
+        let mut step = 1.0;
+        let accepted = loop {
+            let (next_a, next_b) = (a - step * da, b - step * db);
+            let candidate = objective(next_a, next_b);
+            if candidate <= current {
+                break Some((next_a, next_b, candidate));
+            }
+            step *= 0.5;
+            if step < 1e-10 {
+                break None;
+            }
+        };
+        match accepted {
+            Some((next_a, next_b, candidate)) => {
+                (a, b, current) = (next_a, next_b, candidate);
+            }
+            None => break,
+        }
+    }
+
+    if !(a.is_finite() && b.is_finite()) {
+        return Err("Platt scaling did not converge");
+    }
+    Ok(Calibrator::Platt { a, b })
+}
+
+/// Weighted pool-adjacent-violators on the samples sorted by score.
+fn fit_isotonic(
+    scores: &[f64],
+    labels: &[f64],
+    weights: &[f64],
+) -> Result<Calibrator, &'static str> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&i, &j| scores[i].total_cmp(&scores[j]));
+
+    // Blocks of (first score, last score, weighted label sum, weight).
+    let mut blocks: Vec<(f64, f64, f64, f64)> = Vec::new();
+    for i in order {
+        let (z, sum, weight) = (scores[i], labels[i] * weights[i], weights[i]);
+        match blocks.last_mut() {
+            // Tied scores must share one value.
+            Some(last) if last.1 == z => {
+                last.2 += sum;
+                last.3 += weight;
+            }
+            _ => blocks.push((z, z, sum, weight)),
+        }
+        while blocks.len() > 1 {
+            let (prev, last) = (blocks[blocks.len() - 2], blocks[blocks.len() - 1]);
+            if prev.2 * last.3 < last.2 * prev.3 {
+                break;
+            }
+            blocks.pop();
+            let merged = blocks.last_mut().expect("at least one block");
+            merged.1 = last.1;
+            merged.2 += last.2;
+            merged.3 += last.3;
+        }
+    }
+    if blocks.iter().any(|b| b.3 <= 0.0) {
+        return Err("Calibration weights must have a positive sum per score");
+    }
+
+    let (mut knots, mut probabilities) = (Vec::new(), Vec::new());
+    for (first, last, sum, weight) in blocks {
+        let p = sum / weight;
+        knots.push(first);
+        probabilities.push(p);
+        if last > first {
+            knots.push(last);
+            probabilities.push(p);
+        }
+    }
+    Ok(Calibrator::Isotonic {
+        scores: knots,
+        probabilities,
+    })
+}
@@ -43,6 +43,63 @@ pub trait Model<B: ComputeBackend>: std::fmt::Debug + Send {
     /// For a linear model this returns raw logits (no activation).
     fn forward(&self, input: &B::Tensor) -> B::Tensor;
 
+    /// Mutable access to the trainable `(weights, bias)` pair.
+    ///
+    /// Used by trainers to hand the parameters to
+    /// [`LossFunction`](crate::functions::LossFunction) and
+    /// [`Optimizer`](crate::optimizers::Optimizer).
+    fn parameters_mut(&mut self) -> (&mut B::Tensor, &mut B::Tensor);
+
+    /// All trainable parameters flattened as `[weights..., bias...]`, each
+    /// in [`ComputeBackend::to_vec`] order so they pair with flattened
+    /// gradients.
+    ///
+    /// Lets distributed trainers mix parameters across nodes without
+    /// knowing the backend tensor type.
+    fn flat_parameters(&self) -> Vec<f64>;
+
+    /// Overwrite the parameters from a vector laid out as
+    /// [`flat_parameters`](Model::flat_parameters).
+    ///
+    /// # Panics
+    ///
+    /// If `params.len()` differs from `flat_parameters().len()`.
+    fn set_flat_parameters(&mut self, params: &[f64]);
+
+    /// Class probabilities of every row of `input`, one `Vec` per row.
+    ///
+    /// The default treats [`forward`](Model::forward) as the logit `z` of a
+    /// binary classifier and returns `[1 − σ(z), σ(z)]`.  Regression models
+    /// return their predicted mean `[μ]` instead.
+    fn predict_proba(&self, input: &B::Tensor) -> Vec<Vec<f64>> {
+        B::to_vec(&self.forward(input))
+            .into_iter()
+            .map(|z| {
+                let p = 1.0 / (1.0 + (-z).exp());
+                vec![1.0 - p, p]
+            })
+            .collect()
+    }
+
+    /// Predicted class index of every row of `input`, as a target value.
+    ///
+    /// With two classes a row is positive when its positive-class
+    /// probability is at least `threshold`; otherwise the most likely class
+    /// wins and `threshold` is ignored.
+    fn predict(&self, input: &B::Tensor, threshold: f64) -> Vec<f64> {
+        self.predict_proba(input)
+            .into_iter()
+            .map(|p| match p.as_slice() {
+                [_, positive] => f64::from(u8::from(*positive >= threshold)),
+                _ => p
+                    .iter()
+                    .enumerate()
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .map_or(f64::NAN, |(class, _)| class as f64),
+            })
+            .collect()
+    }
+
     /// Persist model parameters to `path`.
     fn save_model(&self, path: &str) -> Result<(), B::Error>;
 
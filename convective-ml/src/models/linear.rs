@@ -1,15 +1,85 @@
-//! Linear (logistic-regression) model, generic over [`ComputeBackend`].
+//! Linear (generalized linear) model, generic over [`ComputeBackend`].
 
 use super::backend::{ComputeBackend, NalgebraBackend, NalgebraError};
+use super::calibration::{Calibration, Calibrator};
 use super::interface::{Model, ModelMode};
+use convective_data::datasets::Dataset;
+use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 
+// ---------------------------------------------------------------------------
+// Family
+// ---------------------------------------------------------------------------
+
+/// Distribution of the target, which fixes the (canonical) link between
+/// the linear predictor `η = X w + b` and the mean `μ = E[y | x]`.
+///
+/// Pair a model with [`GlmLoss`](crate::functions::GlmLoss) of the same
+/// family to fit it by maximum likelihood.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Family {
+    /// Binary targets, logit link: `μ = σ(η)` (logistic regression).
+    #[default]
+    Binomial,
+    /// Real-valued targets, identity link: `μ = η` (linear regression).
+    Gaussian,
+    /// Count targets, log link: `μ = exp(η)` (Poisson regression).
+    Poisson,
+}
+
+impl Family {
+    /// Mean `μ` of linear predictor `eta`.
+    pub fn inverse_link(&self, eta: f64) -> f64 {
+        match self {
+            Family::Binomial => 1.0 / (1.0 + (-eta).exp()),
+            Family::Gaussian => eta,
+            Family::Poisson => eta.exp(),
+        }
+    }
+
+    /// Linear predictor `η` of mean `mu`.
+    pub fn link(&self, mu: f64) -> f64 {
+        match self {
+            Family::Binomial => (mu / (1.0 - mu)).ln(),
+            Family::Gaussian => mu,
+            Family::Poisson => mu.ln(),
+        }
+    }
+
+    /// Code of the `family` tensor persisting the family next to the
+    /// model parameters.
+    pub(crate) fn code(&self) -> f64 {
+        match self {
+            Family::Binomial => 0.0,
+            Family::Gaussian => 1.0,
+            Family::Poisson => 2.0,
+        }
+    }
+
+    /// Inverse of [`code`](Family::code).
+    pub(crate) fn from_code(data: &[f64]) -> Option<Self> {
+        match data {
+            [0.0] => Some(Family::Binomial),
+            [1.0] => Some(Family::Gaussian),
+            [2.0] => Some(Family::Poisson),
+            _ => None,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // LinearModel
 // ---------------------------------------------------------------------------
 
 /// A single-layer linear model: `z = X w + b`.
 ///
+/// [`forward`](Model::forward) returns the linear predictor `z`;
+/// [`mean`](LinearModel::mean) maps it through the inverse link of the
+/// model's [`Family`] (logistic by default).  For a binomial model
+/// [`predict_proba`](Model::predict_proba) returns class probabilities,
+/// through its [`calibrator`](LinearModel::calibrator) when one has been
+/// fitted with [`calibrate`](LinearModel::calibrate).
+///
 /// Weights and bias are stored as `B::Tensor`.  The struct is generic over
 /// the compute backend so the same model definition works with `nalgebra`
 /// (default) or `tch` (`--features torch`).
@@ -22,6 +92,9 @@ pub struct LinearModel<B: ComputeBackend> {
     pub id: String,
     pub weights: B::Tensor,
     pub bias: B::Tensor,
+    pub family: Family,
+    /// Post-hoc score-to-probability map, saved with the parameters.
+    pub calibrator: Option<Calibrator>,
     pub mode: ModelMode,
     _backend: PhantomData<B>,
 }
@@ -31,6 +104,54 @@ impl<B: ComputeBackend> LinearModel<B> {
     pub fn builder(input_dim: usize) -> LinearModelBuilder<B> {
         LinearModelBuilder::new(input_dim)
     }
+
+    /// Fit a [`Calibrator`] of kind `method` on the scores of a held-out
+    /// `validation` set and attach it to the model.
+    ///
+    /// Only binomial models can be calibrated.  Sample weights of the
+    /// dataset are honoured.
+    pub fn calibrate(
+        &mut self,
+        validation: &Dataset,
+        method: Calibration,
+    ) -> Result<(), &'static str>
+    where
+        Self: Model<B>,
+    {
+        if self.family != Family::Binomial {
+            return Err("Only binomial models can be calibrated");
+        }
+        if validation.features().is_empty() {
+            return Err("Calibration needs at least one sample");
+        }
+        let scores = B::to_vec(&self.forward(&B::from_row_vecs(validation.features())));
+        self.calibrator = Some(Calibrator::fit(
+            method,
+            &scores,
+            validation.target(),
+            validation.weights(),
+        )?);
+        Ok(())
+    }
+
+    /// Rows of [`predict_proba`](Model::predict_proba) for linear
+    /// predictors `scores`: `[1 − p, p]` for a binomial model, the mean
+    /// `[μ]` for any other family.
+    fn probability_rows(&self, scores: Vec<f64>) -> Vec<Vec<f64>> {
+        scores
+            .into_iter()
+            .map(|z| {
+                if self.family != Family::Binomial {
+                    return vec![self.family.inverse_link(z)];
+                }
+                let p = self.calibrator.as_ref().map_or_else(
+                    || self.family.inverse_link(z),
+                    |calibrator| calibrator.probability(z),
+                );
+                vec![1.0 - p, p]
+            })
+            .collect()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -45,6 +166,7 @@ impl<B: ComputeBackend> LinearModel<B> {
 pub struct LinearModelBuilder<B: ComputeBackend> {
     id: Option<String>,
     input_dim: usize,
+    family: Family,
     _backend: PhantomData<B>,
 }
 
@@ -53,6 +175,7 @@ impl<B: ComputeBackend> LinearModelBuilder<B> {
         Self {
             id: None,
             input_dim,
+            family: Family::default(),
             _backend: PhantomData,
         }
     }
@@ -61,6 +184,12 @@ impl<B: ComputeBackend> LinearModelBuilder<B> {
         self.id = Some(id);
         self
     }
+
+    /// Target distribution and link (default [`Family::Binomial`]).
+    pub fn family(mut self, family: Family) -> Self {
+        self.family = family;
+        self
+    }
 }
 
 // =========================================================================
@@ -84,12 +213,21 @@ impl LinearModelBuilder<NalgebraBackend> {
             id: self.id.unwrap_or_default(),
             weights,
             bias,
+            family: self.family,
+            calibrator: None,
             mode: ModelMode::Training,
             _backend: PhantomData,
         }
     }
 }
 
+impl LinearModel<NalgebraBackend> {
+    /// Predicted mean `μ = g⁻¹(X w + b)` *(n × 1)*.
+    pub fn mean(&self, input: &nalgebra::DMatrix<f64>) -> nalgebra::DMatrix<f64> {
+        self.forward(input).map(|eta| self.family.inverse_link(eta))
+    }
+}
+
 impl Model<NalgebraBackend> for LinearModel<NalgebraBackend> {
     fn id(&self) -> &str {
         &self.id
@@ -110,40 +248,132 @@ impl Model<NalgebraBackend> for LinearModel<NalgebraBackend> {
         z.add_scalar(b)
     }
 
+    fn parameters_mut(
+        &mut self,
+    ) -> (&mut nalgebra::DMatrix<f64>, &mut nalgebra::DMatrix<f64>) {
+        (&mut self.weights, &mut self.bias)
+    }
+
+    fn flat_parameters(&self) -> Vec<f64> {
+        self.weights
+            .iter()
+            .chain(self.bias.iter())
+            .copied()
+            .collect()
+    }
+
+    fn set_flat_parameters(&mut self, params: &[f64]) {
+        let m = self.weights.len();
+        assert_eq!(
+            params.len(),
+            m + self.bias.len(),
+            "parameter length mismatch"
+        );
+        self.weights.copy_from_slice(&params[..m]);
+        self.bias.copy_from_slice(&params[m..]);
+    }
+
+    /// Gaussian and Poisson models return their predicted mean `[μ]`
+    /// per row, which is not a probability.
+    fn predict_proba(&self, input: &nalgebra::DMatrix<f64>) -> Vec<Vec<f64>> {
+        self.probability_rows(NalgebraBackend::to_vec(&self.forward(input)))
+    }
+
+    /// Class labels of a binomial model; the predicted mean of any other
+    /// family, for which `threshold` is ignored.
+    fn predict(&self, input: &nalgebra::DMatrix<f64>, threshold: f64) -> Vec<f64> {
+        if self.family != Family::Binomial {
+            return self.mean(input).iter().copied().collect();
+        }
+        self.predict_proba(input)
+            .into_iter()
+            .map(|p| f64::from(u8::from(p[1] >= threshold)))
+            .collect()
+    }
+
     #[tracing::instrument(skip(self), fields(model_id = %self.id))]
     fn save_model(&self, path: &str) -> Result<(), NalgebraError> {
-        use serde_json::json;
-        use std::io::Write;
-
-        let w_data: Vec<f64> = self.weights.iter().copied().collect();
-        let b_data: Vec<f64> = self.bias.iter().copied().collect();
-
-        let payload = json!([
-            {
-                "name": "weights",
-                "rows": self.weights.nrows(),
-                "cols": self.weights.ncols(),
-                "data": w_data
-            },
-            {
-                "name": "bias",
-                "rows": self.bias.nrows(),
-                "cols": self.bias.ncols(),
-                "data": b_data
-            }
-        ]);
-
-        let mut file = std::fs::File::create(path)?;
-        file.write_all(payload.to_string().as_bytes())?;
-        Ok(())
+        let calibrator = self.calibrator.as_ref().map(|c| {
+            let (name, rows, data) = c.to_tensor();
+            (
+                name,
+                nalgebra::DMatrix::from_row_slice(rows, data.len() / rows, &data),
+            )
+        });
+        let family = nalgebra::DMatrix::from_element(1, 1, self.family.code());
+        let mut matrices = vec![
+            ("weights", &self.weights),
+            ("bias", &self.bias),
+            ("family", &family),
+        ];
+        if let Some((name, mat)) = &calibrator {
+            matrices.push((*name, mat));
+        }
+        save_matrices(path, &matrices)
     }
 
     #[tracing::instrument(skip(self), fields(model_id = %self.id))]
     fn load_model(&mut self, path: &str) -> Result<(), NalgebraError> {
-        let contents = std::fs::read_to_string(path)?;
-        let entries: Vec<serde_json::Value> = serde_json::from_str(&contents)?;
+        self.calibrator = None;
+        for (name, mat) in load_matrices(path)? {
+            match name.as_str() {
+                "weights" => self.weights = mat,
+                "bias" => self.bias = mat,
+                "family" => {
+                    self.family = Family::from_code(mat.as_slice()).ok_or_else(|| {
+                        NalgebraError::Shape("invalid family tensor".to_string())
+                    })?;
+                }
+                other => {
+                    let data: Vec<f64> = mat.transpose().iter().copied().collect();
+                    self.calibrator =
+                        Some(Calibrator::from_tensor(other, &data).ok_or_else(|| {
+                            NalgebraError::Shape(format!(
+                                "unexpected tensor name: {other}"
+                            ))
+                        })?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
 
-        for entry in entries {
+/// Write named matrices to `path` as a JSON array of
+/// `{name, rows, cols, data}` entries (column-major `data`).
+pub(crate) fn save_matrices(
+    path: &str,
+    matrices: &[(&str, &nalgebra::DMatrix<f64>)],
+) -> Result<(), NalgebraError> {
+    use std::io::Write;
+
+    let payload: Vec<serde_json::Value> = matrices
+        .iter()
+        .map(|(name, mat)| {
+            serde_json::json!({
+                "name": name,
+                "rows": mat.nrows(),
+                "cols": mat.ncols(),
+                "data": mat.iter().copied().collect::<Vec<f64>>()
+            })
+        })
+        .collect();
+
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(serde_json::Value::from(payload).to_string().as_bytes())?;
+    Ok(())
+}
+
+/// Read the named matrices written by [`save_matrices`].
+pub(crate) fn load_matrices(
+    path: &str,
+) -> Result<Vec<(String, nalgebra::DMatrix<f64>)>, NalgebraError> {
+    let contents = std::fs::read_to_string(path)?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&contents)?;
+
+    entries
+        .into_iter()
+        .map(|entry| {
             let name = entry["name"]
                 .as_str()
                 .ok_or_else(|| NalgebraError::Shape("missing name".into()))?;
@@ -162,19 +392,12 @@ impl Model<NalgebraBackend> for LinearModel<NalgebraBackend> {
                 .filter_map(|v| v.as_f64())
                 .collect();
 
-            let mat = nalgebra::DMatrix::from_column_slice(rows, cols, &data);
-            match name {
-                "weights" => self.weights = mat,
-                "bias" => self.bias = mat,
-                other => {
-                    return Err(NalgebraError::Shape(format!(
-                        "unexpected tensor name: {other}"
-                    )));
-                }
-            }
-        }
-        Ok(())
-    }
+            Ok((
+                name.to_string(),
+                nalgebra::DMatrix::from_column_slice(rows, cols, &data),
+            ))
+        })
+        .collect()
 }
 
 // =========================================================================
@@ -204,6 +427,8 @@ impl LinearModelBuilder<TorchBackend> {
             id: self.id.unwrap_or_default(),
             weights,
             bias,
+            family: self.family,
+            calibrator: None,
             mode: ModelMode::Training,
             _backend: PhantomData,
         }
@@ -218,6 +443,8 @@ impl LinearModel<TorchBackend> {
             id: self.id.clone(),
             weights: self.weights.shallow_clone(),
             bias: self.bias.shallow_clone(),
+            family: self.family,
+            calibrator: self.calibrator.clone(),
             mode: self.mode,
             _backend: PhantomData,
         }
@@ -235,6 +462,18 @@ impl LinearModel<TorchBackend> {
     ) -> tch::Tensor {
         x.matmul(weights) + bias
     }
+
+    /// Predicted mean `μ = g⁻¹(X w + b)` *(n,)*.
+    pub fn mean(&self, input: &tch::Tensor) -> tch::Tensor {
+        tch::no_grad(|| {
+            let eta = self.forward(input);
+            match self.family {
+                Family::Binomial => eta.sigmoid(),
+                Family::Gaussian => eta,
+                Family::Poisson => eta.exp(),
+            }
+        })
+    }
 }
 
 #[cfg(feature = "torch")]
@@ -266,26 +505,88 @@ impl Model<TorchBackend> for LinearModel<TorchBackend> {
         }
     }
 
+    fn parameters_mut(&mut self) -> (&mut tch::Tensor, &mut tch::Tensor) {
+        (&mut self.weights, &mut self.bias)
+    }
+
+    fn flat_parameters(&self) -> Vec<f64> {
+        let mut params = TorchBackend::to_vec(&self.weights);
+        params.extend(TorchBackend::to_vec(&self.bias));
+        params
+    }
+
+    fn set_flat_parameters(&mut self, params: &[f64]) {
+        let m = self.weights.numel();
+        assert_eq!(
+            params.len(),
+            m + self.bias.numel(),
+            "parameter length mismatch"
+        );
+        // Copy in place so the leaves keep `requires_grad`.
+        tch::no_grad(|| {
+            self.weights.copy_(&TorchBackend::from_slice(&params[..m]));
+            self.bias.copy_(&TorchBackend::from_slice(&params[m..]));
+        });
+    }
+
+    /// Gaussian and Poisson models return their predicted mean `[μ]`
+    /// per row, which is not a probability.
+    fn predict_proba(&self, input: &tch::Tensor) -> Vec<Vec<f64>> {
+        self.probability_rows(TorchBackend::to_vec(&self.forward(input)))
+    }
+
+    /// Class labels of a binomial model; the predicted mean of any other
+    /// family, for which `threshold` is ignored.
+    fn predict(&self, input: &tch::Tensor, threshold: f64) -> Vec<f64> {
+        if self.family != Family::Binomial {
+            return TorchBackend::to_vec(&self.mean(input));
+        }
+        self.predict_proba(input)
+            .into_iter()
+            .map(|p| f64::from(u8::from(p[1] >= threshold)))
+            .collect()
+    }
+
     #[tracing::instrument(skip(self), fields(model_id = %self.id))]
     fn save_model(&self, path: &str) -> Result<(), tch::TchError> {
-        let state_dict = vec![
+        let mut state_dict = vec![
             ("weights".to_string(), self.weights.shallow_clone()),
             ("bias".to_string(), self.bias.shallow_clone()),
+            (
+                "family".to_string(),
+                tch::Tensor::from_slice(&[self.family.code()]),
+            ),
         ];
+        if let Some(calibrator) = &self.calibrator {
+            let (name, rows, data) = calibrator.to_tensor();
+            let tensor = tch::Tensor::from_slice(&data).reshape([rows as i64, -1]);
+            state_dict.push((name.to_string(), tensor));
+        }
         tch::Tensor::save_multi(&state_dict, path)
     }
 
     #[tracing::instrument(skip(self), fields(model_id = %self.id))]
     fn load_model(&mut self, path: &str) -> Result<(), tch::TchError> {
         let state_dict = tch::Tensor::load_multi(path)?;
+        self.calibrator = None;
         for (name, tensor) in state_dict {
             match name.as_str() {
                 "weights" => self.weights = tensor,
                 "bias" => self.bias = tensor,
+                "family" => {
+                    let data = TorchBackend::to_vec(&tensor);
+                    self.family = Family::from_code(&data).ok_or_else(|| {
+                        tch::TchError::FileFormat("invalid family tensor".to_string())
+                    })?;
+                }
                 _ => {
-                    return Err(tch::TchError::FileFormat(format!(
-                        "unexpected tensor: {name}"
-                    )));
+                    let data = TorchBackend::to_vec(&tensor);
+                    self.calibrator =
+                        Some(Calibrator::from_tensor(&name, &data).ok_or_else(|| {
+                            tch::TchError::FileFormat(format!(
+                                "unexpected tensor: {name}"
+                            ))
+                        })?);
                 }
             }
         }
@@ -5,16 +5,24 @@
 /// - [`TorchBackend`]: Optional backend using `tch::Tensor`, enabled via `--features torch`.
 pub mod backend;
 
+/// Post-hoc probability calibration.
+pub mod calibration;
+
 /// Model trait and supporting types.
 pub mod interface;
 
-/// Linear (logistic-regression) model.
+/// Linear (generalized linear) model.
 pub mod linear;
 
+/// Multiclass (softmax) linear model.
+pub mod softmax;
+
 // Re-exports for convenience
 pub use backend::{ComputeBackend, NalgebraBackend};
+pub use calibration::{Calibration, Calibrator};
 pub use interface::{Model, ModelMode};
-pub use linear::{LinearModel, LinearModelBuilder};
+pub use linear::{Family, LinearModel, LinearModelBuilder};
+pub use softmax::{SoftmaxModel, SoftmaxModelBuilder};
 
 #[cfg(any(feature = "torch", doc))]
 #[cfg_attr(docsrs, doc(cfg(feature = "torch")))]
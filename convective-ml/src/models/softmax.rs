@@ -0,0 +1,322 @@
+//! Multinomial logistic-regression model, generic over [`ComputeBackend`].
+
+use super::backend::{ComputeBackend, NalgebraBackend, NalgebraError};
+use super::interface::{Model, ModelMode};
+use super::linear::{load_matrices, save_matrices};
+use std::marker::PhantomData;
+
+// ---------------------------------------------------------------------------
+// SoftmaxModel
+// ---------------------------------------------------------------------------
+
+/// A linear model with one logit per class: `Z = X W + 1 b`.
+///
+/// `W` is *(m × k)* and `b` is *(1 × k)*, so [`forward`](Model::forward)
+/// returns *(n × k)* logits and [`predict`](Model::predict) the most likely
+/// class of every row.  Train it with
+/// [`SoftmaxCrossEntropy`](crate::functions::SoftmaxCrossEntropy) on class
+/// indices `0..k` (e.g. [`LabelEncoding::Classes`](crate::labels::LabelEncoding)).
+///
+/// Flat parameters are `[W..., b...]` with `W` flattened in the backend's
+/// [`to_vec`](ComputeBackend::to_vec) order (class by class on nalgebra,
+/// feature by feature on torch), so they pair entry by entry with the
+/// flattened gradients.
+#[derive(Debug)]
+pub struct SoftmaxModel<B: ComputeBackend> {
+    pub id: String,
+    pub weights: B::Tensor,
+    pub bias: B::Tensor,
+    pub mode: ModelMode,
+    _backend: PhantomData<B>,
+}
+
+impl<B: ComputeBackend> SoftmaxModel<B> {
+    /// Create a [`SoftmaxModelBuilder`] for `input_dim` features and
+    /// `classes` classes.
+    pub fn builder(input_dim: usize, classes: usize) -> SoftmaxModelBuilder<B> {
+        SoftmaxModelBuilder::new(input_dim, classes)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Builder (backend-agnostic skeleton)
+// ---------------------------------------------------------------------------
+
+/// Builder for [`SoftmaxModel`].
+///
+/// Call [`glorot_uniform_init`](SoftmaxModelBuilder::glorot_uniform_init)
+/// to materialise the model.  That method is implemented once per backend.
+#[derive(Debug)]
+pub struct SoftmaxModelBuilder<B: ComputeBackend> {
+    id: Option<String>,
+    input_dim: usize,
+    classes: usize,
+    _backend: PhantomData<B>,
+}
+
+impl<B: ComputeBackend> SoftmaxModelBuilder<B> {
+    /// # Panics
+    ///
+    /// If `classes < 2`.
+    pub fn new(input_dim: usize, classes: usize) -> Self {
+        assert!(classes >= 2, "a softmax model needs at least two classes");
+        Self {
+            id: None,
+            input_dim,
+            classes,
+            _backend: PhantomData,
+        }
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Glorot-uniform bound `√(6 / (m + k))`.
+    fn limit(&self) -> f64 {
+        (6.0 / (self.input_dim + self.classes) as f64).sqrt()
+    }
+}
+
+/// Row-wise softmax of *(n × k)* logits, shifted by the row maximum for
+/// numerical stability.
+pub(crate) fn softmax_rows(logits: &nalgebra::DMatrix<f64>) -> nalgebra::DMatrix<f64> {
+    let mut probabilities = logits.clone();
+    for mut row in probabilities.row_iter_mut() {
+        let max = row.max();
+        row.apply(|z| *z = (*z - max).exp());
+        let total = row.sum();
+        row /= total;
+    }
+    probabilities
+}
+
+// =========================================================================
+// Nalgebra implementation
+// =========================================================================
+
+impl SoftmaxModelBuilder<NalgebraBackend> {
+    /// Initialise weights with Glorot-uniform and bias to zero.
+    pub fn glorot_uniform_init(self) -> SoftmaxModel<NalgebraBackend> {
+        use nalgebra::DMatrix;
+        use rand::Rng;
+
+        let limit = self.limit();
+        let mut rng = rand::rng();
+
+        let weights = DMatrix::from_fn(self.input_dim, self.classes, |_, _| {
+            rng.random_range(-limit..limit)
+        });
+        let bias = DMatrix::zeros(1, self.classes);
+
+        SoftmaxModel {
+            id: self.id.unwrap_or_default(),
+            weights,
+            bias,
+            mode: ModelMode::Training,
+            _backend: PhantomData,
+        }
+    }
+}
+
+impl Model<NalgebraBackend> for SoftmaxModel<NalgebraBackend> {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn mode(&self) -> ModelMode {
+        self.mode
+    }
+
+    fn set_mode(&mut self, mode: ModelMode) {
+        self.mode = mode;
+    }
+
+    #[tracing::instrument(skip(self, input), fields(model_id = %self.id, mode = ?self.mode))]
+    fn forward(&self, input: &nalgebra::DMatrix<f64>) -> nalgebra::DMatrix<f64> {
+        let mut z = input * &self.weights; // (n, m) × (m, k) → (n, k)
+        for mut row in z.row_iter_mut() {
+            row += &self.bias;
+        }
+        z
+    }
+
+    fn parameters_mut(
+        &mut self,
+    ) -> (&mut nalgebra::DMatrix<f64>, &mut nalgebra::DMatrix<f64>) {
+        (&mut self.weights, &mut self.bias)
+    }
+
+    fn flat_parameters(&self) -> Vec<f64> {
+        // nalgebra stores column-major, i.e. class by class.
+        self.weights
+            .iter()
+            .chain(self.bias.iter())
+            .copied()
+            .collect()
+    }
+
+    fn set_flat_parameters(&mut self, params: &[f64]) {
+        let mk = self.weights.len();
+        assert_eq!(
+            params.len(),
+            mk + self.bias.len(),
+            "parameter length mismatch"
+        );
+        self.weights.copy_from_slice(&params[..mk]);
+        self.bias.copy_from_slice(&params[mk..]);
+    }
+
+    fn predict_proba(&self, input: &nalgebra::DMatrix<f64>) -> Vec<Vec<f64>> {
+        softmax_rows(&self.forward(input))
+            .row_iter()
+            .map(|row| row.iter().copied().collect())
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self), fields(model_id = %self.id))]
+    fn save_model(&self, path: &str) -> Result<(), NalgebraError> {
+        save_matrices(path, &[("weights", &self.weights), ("bias", &self.bias)])
+    }
+
+    #[tracing::instrument(skip(self), fields(model_id = %self.id))]
+    fn load_model(&mut self, path: &str) -> Result<(), NalgebraError> {
+        for (name, mat) in load_matrices(path)? {
+            match name.as_str() {
+                "weights" => self.weights = mat,
+                "bias" => self.bias = mat,
+                other => {
+                    return Err(NalgebraError::Shape(format!(
+                        "unexpected tensor name: {other}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// =========================================================================
+// Torch implementation
+// =========================================================================
+
+#[cfg(feature = "torch")]
+use super::backend::TorchBackend;
+
+#[cfg(feature = "torch")]
+impl SoftmaxModelBuilder<TorchBackend> {
+    /// Initialise weights with Glorot-uniform and bias to zero.
+    ///
+    /// Weights and bias are created with `requires_grad = true`.
+    pub fn glorot_uniform_init(self) -> SoftmaxModel<TorchBackend> {
+        let (m, k) = (self.input_dim as i64, self.classes as i64);
+        let limit = self.limit();
+
+        let rand_weights =
+            tch::Tensor::rand([m, k], (tch::Kind::Float, tch::Device::Cpu))
+                * (2.0 * limit)
+                - limit;
+        let weights = rand_weights.set_requires_grad(true);
+
+        let bias = tch::Tensor::zeros([1, k], (tch::Kind::Float, tch::Device::Cpu))
+            .set_requires_grad(true);
+
+        SoftmaxModel {
+            id: self.id.unwrap_or_default(),
+            weights,
+            bias,
+            mode: ModelMode::Training,
+            _backend: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "torch")]
+impl Model<TorchBackend> for SoftmaxModel<TorchBackend> {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn mode(&self) -> ModelMode {
+        self.mode
+    }
+
+    fn set_mode(&mut self, mode: ModelMode) {
+        self.mode = mode;
+    }
+
+    #[tracing::instrument(skip(self, input), fields(model_id = %self.id, mode = ?self.mode))]
+    fn forward(&self, input: &tch::Tensor) -> tch::Tensor {
+        // (n, m) × (m, k) + (1, k) broadcasts to (n, k).
+        if self.mode == ModelMode::Inference {
+            tch::no_grad(|| input.matmul(&self.weights) + &self.bias)
+        } else {
+            input.matmul(&self.weights) + &self.bias
+        }
+    }
+
+    fn parameters_mut(&mut self) -> (&mut tch::Tensor, &mut tch::Tensor) {
+        (&mut self.weights, &mut self.bias)
+    }
+
+    fn flat_parameters(&self) -> Vec<f64> {
+        let mut params = TorchBackend::to_vec(&self.weights);
+        params.extend(TorchBackend::to_vec(&self.bias));
+        params
+    }
+
+    fn set_flat_parameters(&mut self, params: &[f64]) {
+        let mk = self.weights.numel();
+        assert_eq!(
+            params.len(),
+            mk + self.bias.numel(),
+            "parameter length mismatch"
+        );
+        // Copy in place so the leaves keep `requires_grad`.
+        tch::no_grad(|| {
+            self.weights.copy_(
+                &TorchBackend::from_slice(&params[..mk]).reshape(self.weights.size()),
+            );
+            self.bias.copy_(
+                &TorchBackend::from_slice(&params[mk..]).reshape(self.bias.size()),
+            );
+        });
+    }
+
+    fn predict_proba(&self, input: &tch::Tensor) -> Vec<Vec<f64>> {
+        let classes = self.bias.numel();
+        let probabilities =
+            tch::no_grad(|| self.forward(input).softmax(-1, tch::Kind::Double));
+        TorchBackend::to_vec(&probabilities)
+            .chunks(classes)
+            .map(<[f64]>::to_vec)
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self), fields(model_id = %self.id))]
+    fn save_model(&self, path: &str) -> Result<(), tch::TchError> {
+        let state_dict = vec![
+            ("weights".to_string(), self.weights.shallow_clone()),
+            ("bias".to_string(), self.bias.shallow_clone()),
+        ];
+        tch::Tensor::save_multi(&state_dict, path)
+    }
+
+    #[tracing::instrument(skip(self), fields(model_id = %self.id))]
+    fn load_model(&mut self, path: &str) -> Result<(), tch::TchError> {
+        let state_dict = tch::Tensor::load_multi(path)?;
+        for (name, tensor) in state_dict {
+            match name.as_str() {
+                "weights" => self.weights = tensor,
+                "bias" => self.bias = tensor,
+                _ => {
+                    return Err(tch::TchError::FileFormat(format!(
+                        "unexpected tensor: {name}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,426 @@
+//! Optimisers with per-coordinate adaptive step sizes.
+//!
+//! All three keep running statistics of the squared gradient `g²` and
+//! divide the step by its square root, so coordinates with consistently
+//! large gradients take smaller steps.
+
+use super::gradient::{OptimizerState, buffer, flat_optimizer};
+
+// ---------------------------------------------------------------------------
+// AdaGrad
+// ---------------------------------------------------------------------------
+
+/// AdaGrad (Duchi et al., 2011): `G ← G + g²`, `w ← w − η g / (√G + ε)`.
+#[derive(Debug)]
+pub struct AdaGrad {
+    pub id: String,
+    pub learning_rate: f64,
+    pub epsilon: f64,
+    sum_squares: Option<Vec<f64>>,
+    steps: u64,
+}
+
+impl AdaGrad {
+    pub fn builder() -> AdaGradBuilder {
+        AdaGradBuilder::new()
+    }
+
+    fn update(&mut self, params: &mut [f64], grads: &[f64], _weight_count: usize) {
+        let sums = buffer(&mut self.sum_squares, grads.len());
+        for ((p, g), s) in params.iter_mut().zip(grads).zip(sums.iter_mut()) {
+            *s += g * g;
+            *p -= self.learning_rate * g / (s.sqrt() + self.epsilon);
+        }
+        self.steps += 1;
+    }
+
+    fn clear(&mut self) {
+        self.sum_squares = None;
+        self.steps = 0;
+    }
+
+    fn export_state(&self) -> OptimizerState {
+        let mut state = OptimizerState {
+            steps: self.steps,
+            ..Default::default()
+        };
+        if let Some(s) = &self.sum_squares {
+            state.buffers.insert("sum_squares".into(), s.clone());
+        }
+        state
+    }
+
+    fn import_state(&mut self, state: OptimizerState) -> Result<(), &'static str> {
+        self.sum_squares = state.buffer("sum_squares");
+        self.steps = state.steps;
+        Ok(())
+    }
+}
+
+flat_optimizer!(AdaGrad);
+
+// ---------------------------------------------------------------------------
+// RMSProp
+// ---------------------------------------------------------------------------
+
+/// RMSProp: `s ← ρ s + (1 − ρ) g²`, `w ← w − η g / (√s + ε)`.
+#[derive(Debug)]
+pub struct RmsProp {
+    pub id: String,
+    pub learning_rate: f64,
+    pub decay: f64,
+    pub epsilon: f64,
+    mean_squares: Option<Vec<f64>>,
+    steps: u64,
+}
+
+impl RmsProp {
+    pub fn builder() -> RmsPropBuilder {
+        RmsPropBuilder::new()
+    }
+
+    fn update(&mut self, params: &mut [f64], grads: &[f64], _weight_count: usize) {
+        let means = buffer(&mut self.mean_squares, grads.len());
+        for ((p, g), s) in params.iter_mut().zip(grads).zip(means.iter_mut()) {
+            *s = self.decay * *s + (1.0 - self.decay) * g * g;
+            *p -= self.learning_rate * g / (s.sqrt() + self.epsilon);
+        }
+        self.steps += 1;
+    }
+
+    fn clear(&mut self) {
+        self.mean_squares = None;
+        self.steps = 0;
+    }
+
+    fn export_state(&self) -> OptimizerState {
+        let mut state = OptimizerState {
+            steps: self.steps,
+            ..Default::default()
+        };
+        if let Some(s) = &self.mean_squares {
+            state.buffers.insert("mean_squares".into(), s.clone());
+        }
+        state
+    }
+
+    fn import_state(&mut self, state: OptimizerState) -> Result<(), &'static str> {
+        self.mean_squares = state.buffer("mean_squares");
+        self.steps = state.steps;
+        Ok(())
+    }
+}
+
+flat_optimizer!(RmsProp);
+
+// ---------------------------------------------------------------------------
+// Adam / AdamW
+// ---------------------------------------------------------------------------
+
+/// Adam (Kingma & Ba, 2015) with bias-corrected moment estimates:
+///
+/// ```text
+/// m ← β₁ m + (1 − β₁) g,   v ← β₂ v + (1 − β₂) g²
+/// w ← w − η m̂ / (√v̂ + ε),  m̂ = m / (1 − β₁ᵗ), v̂ = v / (1 − β₂ᵗ)
+/// ```
+///
+/// `weight_decay = λ` adds `λ w` to the weight gradient (L2 penalty,
+/// classic Adam).  With `decoupled` it is applied as `w ← w − η λ w`
+/// outside the adaptive step instead (AdamW, Loshchilov & Hutter, 2019).
+/// The bias is never decayed.
+#[derive(Debug)]
+pub struct Adam {
+    pub id: String,
+    pub learning_rate: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+    pub epsilon: f64,
+    pub weight_decay: f64,
+    pub decoupled: bool,
+    first_moment: Option<Vec<f64>>,
+    second_moment: Option<Vec<f64>>,
+    steps: u64,
+}
+
+impl Adam {
+    pub fn builder() -> AdamBuilder {
+        AdamBuilder::new()
+    }
+
+    fn update(&mut self, params: &mut [f64], grads: &[f64], weight_count: usize) {
+        self.steps += 1;
+        let t = self.steps as i32;
+        let correction1 = 1.0 - self.beta1.powi(t);
+        let correction2 = 1.0 - self.beta2.powi(t);
+        let (lr, decay) = (self.learning_rate, self.weight_decay);
+
+        let m = buffer(&mut self.first_moment, grads.len());
+        let v = buffer(&mut self.second_moment, grads.len());
+        for (k, (p, &g)) in params.iter_mut().zip(grads).enumerate() {
+            let decays = k < weight_count && decay > 0.0;
+            let g = if decays && !self.decoupled {
+                g + decay * *p
+            } else {
+                g
+            };
+            m[k] = self.beta1 * m[k] + (1.0 - self.beta1) * g;
+            v[k] = self.beta2 * v[k] + (1.0 - self.beta2) * g * g;
+            let m_hat = m[k] / correction1;
+            let v_hat = v[k] / correction2;
+            if decays && self.decoupled {
+                *p -= lr * decay * *p;
+            }
+            *p -= lr * m_hat / (v_hat.sqrt() + self.epsilon);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.first_moment = None;
+        self.second_moment = None;
+        self.steps = 0;
+    }
+
+    fn export_state(&self) -> OptimizerState {
+        let mut state = OptimizerState {
+            steps: self.steps,
+            ..Default::default()
+        };
+        if let (Some(m), Some(v)) = (&self.first_moment, &self.second_moment) {
+            state.buffers.insert("first_moment".into(), m.clone());
+            state.buffers.insert("second_moment".into(), v.clone());
+        }
+        state
+    }
+
+    fn import_state(&mut self, state: OptimizerState) -> Result<(), &'static str> {
+        let m = state.buffer("first_moment");
+        let v = state.buffer("second_moment");
+        if m.as_ref().map(Vec::len) != v.as_ref().map(Vec::len) {
+            return Err("Optimizer state buffers differ in length");
+        }
+        self.first_moment = m;
+        self.second_moment = v;
+        self.steps = state.steps;
+        Ok(())
+    }
+}
+
+flat_optimizer!(Adam);
+
+// ---------------------------------------------------------------------------
+// Builders
+// ---------------------------------------------------------------------------
+
+fn validate(
+    id: Option<String>,
+    learning_rate: Option<f64>,
+    epsilon: f64,
+) -> Result<(String, f64), &'static str> {
+    let id = id.ok_or("Missing id")?;
+    let learning_rate = learning_rate.ok_or("Missing learning_rate")?;
+    if epsilon.is_nan() || epsilon <= 0.0 {
+        return Err("epsilon must be positive");
+    }
+    Ok((id, learning_rate))
+}
+
+#[derive(Debug)]
+pub struct AdaGradBuilder {
+    id: Option<String>,
+    learning_rate: Option<f64>,
+    epsilon: f64,
+}
+
+impl Default for AdaGradBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdaGradBuilder {
+    pub fn new() -> Self {
+        AdaGradBuilder {
+            id: None,
+            learning_rate: None,
+            epsilon: 1e-10,
+        }
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn learning_rate(mut self, lr: f64) -> Self {
+        self.learning_rate = Some(lr);
+        self
+    }
+
+    /// Denominator offset (default 1e-10).
+    pub fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    pub fn build(self) -> Result<AdaGrad, &'static str> {
+        let (id, learning_rate) = validate(self.id, self.learning_rate, self.epsilon)?;
+        Ok(AdaGrad {
+            id,
+            learning_rate,
+            epsilon: self.epsilon,
+            sum_squares: None,
+            steps: 0,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct RmsPropBuilder {
+    id: Option<String>,
+    learning_rate: Option<f64>,
+    decay: f64,
+    epsilon: f64,
+}
+
+impl Default for RmsPropBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RmsPropBuilder {
+    pub fn new() -> Self {
+        RmsPropBuilder {
+            id: None,
+            learning_rate: None,
+            decay: 0.99,
+            epsilon: 1e-8,
+        }
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn learning_rate(mut self, lr: f64) -> Self {
+        self.learning_rate = Some(lr);
+        self
+    }
+
+    /// Decay `ρ ∈ [0, 1)` of the squared-gradient average (default 0.99).
+    pub fn decay(mut self, decay: f64) -> Self {
+        self.decay = decay;
+        self
+    }
+
+    /// Denominator offset (default 1e-8).
+    pub fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    pub fn build(self) -> Result<RmsProp, &'static str> {
+        let (id, learning_rate) = validate(self.id, self.learning_rate, self.epsilon)?;
+        if !(0.0..1.0).contains(&self.decay) {
+            return Err("decay must be in [0, 1)");
+        }
+        Ok(RmsProp {
+            id,
+            learning_rate,
+            decay: self.decay,
+            epsilon: self.epsilon,
+            mean_squares: None,
+            steps: 0,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct AdamBuilder {
+    id: Option<String>,
+    learning_rate: Option<f64>,
+    beta1: f64,
+    beta2: f64,
+    epsilon: f64,
+    weight_decay: f64,
+    decoupled: bool,
+}
+
+impl Default for AdamBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdamBuilder {
+    pub fn new() -> Self {
+        AdamBuilder {
+            id: None,
+            learning_rate: None,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+            weight_decay: 0.0,
+            decoupled: false,
+        }
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn learning_rate(mut self, lr: f64) -> Self {
+        self.learning_rate = Some(lr);
+        self
+    }
+
+    /// Moment decay rates (default 0.9 and 0.999).
+    pub fn betas(mut self, beta1: f64, beta2: f64) -> Self {
+        self.beta1 = beta1;
+        self.beta2 = beta2;
+        self
+    }
+
+    pub fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// L2 weight decay added to the gradient (Adam).
+    pub fn weight_decay(mut self, weight_decay: f64) -> Self {
+        self.weight_decay = weight_decay;
+        self.decoupled = false;
+        self
+    }
+
+    /// Decoupled weight decay (AdamW).
+    pub fn decoupled_weight_decay(mut self, weight_decay: f64) -> Self {
+        self.weight_decay = weight_decay;
+        self.decoupled = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Adam, &'static str> {
+        let (id, learning_rate) = validate(self.id, self.learning_rate, self.epsilon)?;
+        if !(0.0..1.0).contains(&self.beta1) || !(0.0..1.0).contains(&self.beta2) {
+            return Err("betas must be in [0, 1)");
+        }
+        if self.weight_decay.is_nan() || self.weight_decay < 0.0 {
+            return Err("weight_decay must be non-negative");
+        }
+        Ok(Adam {
+            id,
+            learning_rate,
+            beta1: self.beta1,
+            beta2: self.beta2,
+            epsilon: self.epsilon,
+            weight_decay: self.weight_decay,
+            decoupled: self.decoupled,
+            first_moment: None,
+            second_moment: None,
+            steps: 0,
+        })
+    }
+}
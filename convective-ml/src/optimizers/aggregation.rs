@@ -0,0 +1,195 @@
+//! Byzantine-robust aggregation rules and adversarial node behaviour.
+//!
+//! An [`Aggregator`] combines parameter vectors received from several
+//! nodes.  [`WeightedMean`](Aggregator::WeightedMean) is the plain average
+//! used by FedAvg and DGD; the other rules tolerate a bounded number of
+//! arbitrarily corrupted inputs.  An [`Adversary`] produces such inputs for
+//! robustness experiments.
+
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+use serde::{Deserialize, Serialize};
+
+/// Rule combining `m` parameter vectors into one.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Aggregator {
+    /// `Σ_i w_i x_i / Σ_i w_i`.
+    #[default]
+    WeightedMean,
+    /// Coordinate-wise median; weights are ignored.
+    CoordinateMedian,
+    /// Coordinate-wise mean after discarding the `trim` smallest and
+    /// largest values; weights are ignored.  Falls back to the median when
+    /// `2 · trim ≥ m`.
+    TrimmedMean { trim: usize },
+    /// (Multi-)Krum (Blanchard et al., 2017): scores every vector by the
+    /// summed squared distance to its `m − byzantine − 2` nearest
+    /// neighbours and averages the `select` lowest-scoring ones.  `select =
+    /// 1` is plain Krum.  Weights are ignored.
+    Krum { byzantine: usize, select: usize },
+    /// Weighted geometric median `argmin_z Σ_i w_i ‖z − x_i‖₂`, computed
+    /// with Weiszfeld iterations until the step falls below `tolerance`.
+    GeometricMedian { iterations: usize, tolerance: f64 },
+}
+
+impl Aggregator {
+    /// Combine `vectors` with non-negative `weights`.
+    ///
+    /// All vectors must have the same length; an empty input yields an
+    /// empty vector.
+    pub fn aggregate(&self, vectors: &[&[f64]], weights: &[f64]) -> Vec<f64> {
+        let p = vectors.first().map_or(0, |v| v.len());
+        if vectors.len() <= 1 {
+            return vectors.first().map(|v| v.to_vec()).unwrap_or_default();
+        }
+        match *self {
+            Aggregator::WeightedMean => weighted_mean(vectors, weights),
+            Aggregator::CoordinateMedian => {
+                (0..p).map(|k| median(&mut column(vectors, k))).collect()
+            }
+            Aggregator::TrimmedMean { trim } => (0..p)
+                .map(|k| trimmed_mean(&mut column(vectors, k), trim))
+                .collect(),
+            Aggregator::Krum { byzantine, select } => krum(vectors, byzantine, select),
+            Aggregator::GeometricMedian {
+                iterations,
+                tolerance,
+            } => geometric_median(vectors, weights, iterations, tolerance),
+        }
+    }
+}
+
+fn column(vectors: &[&[f64]], k: usize) -> Vec<f64> {
+    vectors.iter().map(|v| v[k]).collect()
+}
+
+fn weighted_mean(vectors: &[&[f64]], weights: &[f64]) -> Vec<f64> {
+    let p = vectors[0].len();
+    let total: f64 = weights.iter().sum();
+    let mut out = vec![0.0; p];
+    for (v, w) in vectors.iter().zip(weights) {
+        for (o, x) in out.iter_mut().zip(v.iter()) {
+            *o += w / total * x;
+        }
+    }
+    out
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let m = values.len();
+    if m % 2 == 1 {
+        values[m / 2]
+    } else {
+        0.5 * (values[m / 2 - 1] + values[m / 2])
+    }
+}
+
+fn trimmed_mean(values: &mut [f64], trim: usize) -> f64 {
+    if 2 * trim >= values.len() {
+        return median(values);
+    }
+    values.sort_by(f64::total_cmp);
+    let kept = &values[trim..values.len() - trim];
+    kept.iter().sum::<f64>() / kept.len() as f64
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+fn krum(vectors: &[&[f64]], byzantine: usize, select: usize) -> Vec<f64> {
+    let m = vectors.len();
+    // Krum assumes m > 2f + 2; otherwise score against every other vector.
+    let neighbours = m.saturating_sub(byzantine + 2).clamp(1, m - 1);
+    let mut scores: Vec<(usize, f64)> = (0..m)
+        .map(|i| {
+            let mut distances: Vec<f64> = (0..m)
+                .filter(|&j| j != i)
+                .map(|j| squared_distance(vectors[i], vectors[j]))
+                .collect();
+            distances.sort_by(f64::total_cmp);
+            (i, distances[..neighbours].iter().sum())
+        })
+        .collect();
+    scores.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let chosen: Vec<&[f64]> = scores
+        .iter()
+        .take(select.clamp(1, m))
+        .map(|&(i, _)| vectors[i])
+        .collect();
+    weighted_mean(&chosen, &vec![1.0; chosen.len()])
+}
+
+fn geometric_median(
+    vectors: &[&[f64]],
+    weights: &[f64],
+    iterations: usize,
+    tolerance: f64,
+) -> Vec<f64> {
+    // Smoothing keeps the Weiszfeld weights finite when z hits an input.
+    const SMOOTHING: f64 = 1e-12;
+
+    let mut z = weighted_mean(vectors, weights);
+    for _ in 0..iterations {
+        let coefficients: Vec<f64> = vectors
+            .iter()
+            .zip(weights)
+            .map(|(v, w)| w / squared_distance(v, &z).sqrt().max(SMOOTHING))
+            .collect();
+        let next = weighted_mean(vectors, &coefficients);
+        let step = squared_distance(&next, &z).sqrt();
+        z = next;
+        if step < tolerance {
+            break;
+        }
+    }
+    z
+}
+
+// ---------------------------------------------------------------------------
+// Adversaries
+// ---------------------------------------------------------------------------
+
+/// Corruption applied by a Byzantine node to the parameters it shares.
+///
+/// `reference` is the point the honest update started from (the global
+/// model in federated training, the node's previous parameters in
+/// decentralized training), so `honest − reference` is the honest update.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Adversary {
+    /// Send `reference − (honest − reference)`: the update with its sign
+    /// flipped.
+    SignFlip,
+    /// Add i.i.d. Gaussian noise with standard deviation `std`.
+    RandomNoise { std: f64 },
+    /// Send `reference + factor · (honest − reference)`.
+    Scaled { factor: f64 },
+}
+
+impl Adversary {
+    pub fn corrupt<R: Rng + ?Sized>(
+        &self,
+        honest: &[f64],
+        reference: &[f64],
+        rng: &mut R,
+    ) -> Vec<f64> {
+        match *self {
+            Adversary::SignFlip => honest
+                .iter()
+                .zip(reference)
+                .map(|(h, r)| 2.0 * r - h)
+                .collect(),
+            Adversary::RandomNoise { std } => match Normal::new(0.0, std) {
+                Ok(noise) => honest.iter().map(|h| h + noise.sample(rng)).collect(),
+                Err(_) => honest.to_vec(),
+            },
+            Adversary::Scaled { factor } => honest
+                .iter()
+                .zip(reference)
+                .map(|(h, r)| r + factor * (h - r))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! Compression of parameter vectors exchanged between nodes.
+//!
+//! A [`Compressor`] turns a dense vector into a [`Compressed`] message whose
+//! [`bytes`](Compressed::bytes) is its encoded size on the wire.  Lossy
+//! compressors are applied to *differences* by an [`Encoder`]: the sender
+//! and all its receivers keep the same public estimate `x̂` of the sender's
+//! vector, the sender transmits `C(x − x̂)` and everyone adds the decoded
+//! message to `x̂` (Koloskova et al., 2019).  The compression error is thus
+//! re-sent in later rounds instead of being lost, so the encoder needs no
+//! [`ErrorFeedback`]; that is meant for non-cumulative messages such as
+//! federated client updates.
+
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::index};
+use serde::{Deserialize, Serialize};
+
+/// Size of an uncompressed `f64` vector of length `dimension`.
+pub fn dense_bytes(dimension: usize) -> usize {
+    dimension * size_of::<f64>()
+}
+
+/// Compression operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Compressor {
+    /// No compression.
+    #[default]
+    Identity,
+    /// Keep the `k` entries of largest magnitude.
+    TopK { k: usize },
+    /// Keep `k` uniformly chosen entries.  Values are not rescaled, so the
+    /// operator is biased but contractive, like top-k.
+    RandomK { k: usize },
+    /// QSGD (Alistarh et al., 2017): stochastic rounding of `|x_k| / ‖x‖₂`
+    /// to one of `levels` levels, plus a sign bit.  Unbiased.
+    Qsgd { levels: u16 },
+    /// One sign bit per entry, scaled by the mean magnitude `‖x‖₁ / p`.
+    /// Pair it with error feedback or an [`Encoder`].
+    Sign,
+}
+
+/// Encoded vector, as sent over the wire.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Compressed {
+    Dense(Vec<f64>),
+    /// Values at ascending `indices`; every other entry is zero.
+    Sparse {
+        dimension: usize,
+        indices: Vec<u32>,
+        values: Vec<f64>,
+    },
+    /// Entry `k` is `norm · quantized[k] / levels`.
+    Quantized {
+        norm: f64,
+        levels: u16,
+        quantized: Vec<i32>,
+    },
+    /// Entry `k` is `+scale` if bit `k` of `bits` is set, `−scale` otherwise.
+    Sign {
+        dimension: usize,
+        scale: f64,
+        bits: Vec<u8>,
+    },
+}
+
+impl Compressed {
+    pub fn dimension(&self) -> usize {
+        match self {
+            Compressed::Dense(values) => values.len(),
+            Compressed::Sparse { dimension, .. } | Compressed::Sign { dimension, .. } => {
+                *dimension
+            }
+            Compressed::Quantized { quantized, .. } => quantized.len(),
+        }
+    }
+
+    /// Encoded size: `f64` values and scales take 8 bytes, indices 4 bytes,
+    /// QSGD entries `1 + ⌈log₂(levels + 1)⌉` bits and signs 1 bit.
+    pub fn bytes(&self) -> usize {
+        match self {
+            Compressed::Dense(values) => dense_bytes(values.len()),
+            Compressed::Sparse { values, .. } => {
+                values.len() * (size_of::<u32>() + size_of::<f64>())
+            }
+            Compressed::Quantized {
+                levels, quantized, ..
+            } => {
+                let bits_per_entry = 1
+                    + (u32::from(*levels) + 1)
+                        .next_power_of_two()
+                        .trailing_zeros() as usize;
+                size_of::<f64>() + (quantized.len() * bits_per_entry).div_ceil(8)
+            }
+            Compressed::Sign { bits, .. } => size_of::<f64>() + bits.len(),
+        }
+    }
+
+    pub fn decode(&self) -> Vec<f64> {
+        let mut out = vec![0.0; self.dimension()];
+        self.add_to(&mut out);
+        out
+    }
+
+    /// `target += decode(self)`.
+    pub fn add_to(&self, target: &mut [f64]) {
+        match self {
+            Compressed::Dense(values) => {
+                for (t, v) in target.iter_mut().zip(values) {
+                    *t += v;
+                }
+            }
+            Compressed::Sparse {
+                indices, values, ..
+            } => {
+                for (&k, v) in indices.iter().zip(values) {
+                    target[k as usize] += v;
+                }
+            }
+            Compressed::Quantized {
+                norm,
+                levels,
+                quantized,
+            } => {
+                let unit = norm / f64::from(*levels);
+                for (t, &q) in target.iter_mut().zip(quantized) {
+                    *t += unit * f64::from(q);
+                }
+            }
+            Compressed::Sign { scale, bits, .. } => {
+                for (k, t) in target.iter_mut().enumerate() {
+                    let positive = bits[k / 8] & (1 << (k % 8)) != 0;
+                    *t += if positive { *scale } else { -scale };
+                }
+            }
+        }
+    }
+}
+
+impl Compressor {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match *self {
+            Compressor::TopK { k: 0 } | Compressor::RandomK { k: 0 } => {
+                Err("Compressor must keep at least one entry")
+            }
+            Compressor::Qsgd { levels: 0 } => Err("QSGD needs at least one level"),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn compress<R: Rng + ?Sized>(&self, x: &[f64], rng: &mut R) -> Compressed {
+        let p = x.len();
+        match *self {
+            Compressor::Identity => Compressed::Dense(x.to_vec()),
+            Compressor::TopK { k } => {
+                let mut order: Vec<usize> = (0..p).collect();
+                if k < p {
+                    order.select_nth_unstable_by(k, |&a, &b| {
+                        x[b].abs().total_cmp(&x[a].abs())
+                    });
+                    order.truncate(k);
+                }
+                sparse(x, order)
+            }
+            Compressor::RandomK { k } => {
+                sparse(x, index::sample(rng, p, k.min(p)).into_vec())
+            }
+            Compressor::Qsgd { levels } => {
+                let norm = x.iter().map(|v| v * v).sum::<f64>().sqrt();
+                let s = f64::from(levels);
+                let quantized = x
+                    .iter()
+                    .map(|&v| {
+                        if norm == 0.0 {
+                            return 0;
+                        }
+                        let r = v.abs() / norm * s;
+                        let mut level = r.floor();
+                        if rng.random_bool((r - level).clamp(0.0, 1.0)) {
+                            level += 1.0;
+                        }
+                        (v.signum() * level) as i32
+                    })
+                    .collect();
+                Compressed::Quantized {
+                    norm,
+                    levels,
+                    quantized,
+                }
+            }
+            Compressor::Sign => {
+                let scale = if p == 0 {
+                    0.0
+                } else {
+                    x.iter().map(|v| v.abs()).sum::<f64>() / p as f64
+                };
+                let mut bits = vec![0u8; p.div_ceil(8)];
+                for (k, &v) in x.iter().enumerate() {
+                    if v >= 0.0 {
+                        bits[k / 8] |= 1 << (k % 8);
+                    }
+                }
+                Compressed::Sign {
+                    dimension: p,
+                    scale,
+                    bits,
+                }
+            }
+        }
+    }
+}
+
+fn sparse(x: &[f64], mut indices: Vec<usize>) -> Compressed {
+    indices.sort_unstable();
+    Compressed::Sparse {
+        dimension: x.len(),
+        values: indices.iter().map(|&k| x[k]).collect(),
+        indices: indices.into_iter().map(|k| k as u32).collect(),
+    }
+}
+
+/// Error feedback (Karimireddy et al., 2019): compress `x + e` and keep the
+/// residual `e ← x + e − C(x + e)` for the next call.
+#[derive(Debug, Clone, Default)]
+pub struct ErrorFeedback {
+    memory: Vec<f64>,
+}
+
+impl ErrorFeedback {
+    pub fn compress<R: Rng + ?Sized>(
+        &mut self,
+        compressor: &Compressor,
+        x: &[f64],
+        rng: &mut R,
+    ) -> Compressed {
+        self.memory.resize(x.len(), 0.0);
+        for (m, v) in self.memory.iter_mut().zip(x) {
+            *m += v;
+        }
+        let message = compressor.compress(&self.memory, rng);
+        let decoded = message.decode();
+        for (m, d) in self.memory.iter_mut().zip(decoded) {
+            *m -= d;
+        }
+        message
+    }
+
+    pub fn memory(&self) -> &[f64] {
+        &self.memory
+    }
+}
+
+/// Sender side of a compressed link.
+///
+/// Receivers mirror [`estimate`](Encoder::estimate) by starting from zeros
+/// and applying every message with [`Compressed::add_to`].
+#[derive(Debug, Clone)]
+pub struct Encoder {
+    compressor: Compressor,
+    estimate: Vec<f64>,
+    rng: StdRng,
+}
+
+impl Encoder {
+    /// Encoder with its own random stream; `seed` makes randomized
+    /// compressors reproducible.
+    pub fn new(compressor: Compressor, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+        Encoder {
+            compressor,
+            estimate: Vec::new(),
+            rng,
+        }
+    }
+
+    /// Message moving the public estimate towards `x`.
+    pub fn encode(&mut self, x: &[f64]) -> Compressed {
+        self.estimate.resize(x.len(), 0.0);
+        let difference: Vec<f64> =
+            x.iter().zip(&self.estimate).map(|(v, e)| v - e).collect();
+        let message = self.compressor.compress(&difference, &mut self.rng);
+        message.add_to(&mut self.estimate);
+        message
+    }
+
+    /// Value of the sender's vector as known to its receivers.
+    pub fn estimate(&self) -> &[f64] {
+        &self.estimate
+    }
+}
@@ -0,0 +1,230 @@
+//! Decentralized consensus ADMM.
+
+use super::{ConsensusOptimizer, GradientOracle};
+use convective_data::datasets::types::consensus::ConsensusConfig;
+
+/// Decentralized consensus ADMM (Shi et al., 2014).
+///
+/// Node `i` with neighbours `N_i` (non-zero off-diagonal entries of the
+/// symmetrised mixing matrix; weights themselves are not used) solves
+///
+/// ```text
+/// x_i^{k+1} = argmin_x  f_i(x) + ⟨φ_i^k, x⟩ + ρ Σ_{j∈N_i} ‖x − (x_i^k + x_j^k)/2‖²
+/// φ_i^{k+1} = φ_i^k + ρ Σ_{j∈N_i} (x_i^{k+1} − x_j^{k+1})
+/// ```
+///
+/// The local subproblem is solved inexactly with `inner_steps` gradient
+/// steps of size `learning_rate`, which must stay below
+/// `1 / (L_i + 2ρ|N_i|)` for a local smoothness constant `L_i`.
+#[derive(Debug)]
+pub struct Admm {
+    pub id: String,
+    /// Penalty parameter `ρ`.
+    pub rho: f64,
+    /// Step size of the inner subproblem solver.
+    pub learning_rate: f64,
+    pub inner_steps: usize,
+    pub epsilon: Option<f64>,
+    duals: Option<Vec<Vec<f64>>>,
+}
+
+impl Admm {
+    pub fn builder() -> AdmmBuilder {
+        AdmmBuilder::new()
+    }
+
+    /// Build from a config entry with `rho`, `learning_rate` and optional
+    /// `inner_steps` / `epsilon`.
+    pub fn from_config(config: &ConsensusConfig) -> Result<Self, &'static str> {
+        let mut builder = AdmmBuilder::new().id(config.id.clone());
+        if let Some(rho) = config.param("rho") {
+            builder = builder.rho(rho);
+        }
+        if let Some(lr) = config.param("learning_rate") {
+            builder = builder.learning_rate(lr);
+        }
+        if let Some(steps) = config.param("inner_steps") {
+            if steps < 1.0 || steps.fract() != 0.0 {
+                return Err("inner_steps must be a positive integer");
+            }
+            builder = builder.inner_steps(steps as usize);
+        }
+        if let Some(eps) = config.param("epsilon") {
+            builder = builder.epsilon(eps);
+        }
+        builder.build()
+    }
+}
+
+impl ConsensusOptimizer for Admm {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn round(
+        &mut self,
+        params: &mut [Vec<f64>],
+        mixing: &[Vec<f64>],
+        active: &[bool],
+        oracle: &mut GradientOracle<'_>,
+    ) -> Vec<f64> {
+        let n = params.len();
+        let p = params.first().map_or(0, Vec::len);
+        let mut duals = match self.duals.take() {
+            Some(duals) if duals.len() == n => duals,
+            _ => vec![vec![0.0; p]; n],
+        };
+
+        let neighbours: Vec<Vec<usize>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .filter(|&j| j != i && (mixing[i][j] > 0.0 || mixing[j][i] > 0.0))
+                    .collect()
+            })
+            .collect();
+
+        let mut losses = Vec::with_capacity(n);
+        let mut next = Vec::with_capacity(n);
+        for i in 0..n {
+            if !active[i] {
+                // Isolated this round: no primal step, and no neighbours to
+                // move the dual.
+                losses.push(oracle(i, &params[i]).loss);
+                next.push(params[i].clone());
+                continue;
+            }
+            let degree = neighbours[i].len() as f64;
+            // Σ_j (x_i + x_j): the subproblem's quadratic anchor.
+            let mut anchor: Vec<f64> = params[i].iter().map(|x| degree * x).collect();
+            for &j in &neighbours[i] {
+                for (a, xj) in anchor.iter_mut().zip(&params[j]) {
+                    *a += xj;
+                }
+            }
+
+            let mut z = params[i].clone();
+            for step in 0..self.inner_steps {
+                let local = oracle(i, &z);
+                if step == 0 {
+                    losses.push(local.loss);
+                }
+                for k in 0..p {
+                    let grad =
+                        local.gradient[k] + duals[i][k] + 2.0 * self.rho * degree * z[k]
+                            - self.rho * anchor[k];
+                    z[k] -= self.learning_rate * grad;
+                }
+            }
+            next.push(z);
+        }
+
+        for i in 0..n {
+            for &j in &neighbours[i] {
+                for k in 0..p {
+                    duals[i][k] += self.rho * (next[i][k] - next[j][k]);
+                }
+            }
+        }
+
+        for (x, new) in params.iter_mut().zip(next) {
+            *x = new;
+        }
+        self.duals = Some(duals);
+        losses
+    }
+
+    fn tolerance(&self) -> Option<f64> {
+        self.epsilon
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+    fn reset(&mut self) {
+        self.duals = None;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct AdmmBuilder {
+    id: Option<String>,
+    rho: Option<f64>,
+    learning_rate: Option<f64>,
+    inner_steps: usize,
+    epsilon: Option<f64>,
+}
+
+impl Default for AdmmBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdmmBuilder {
+    pub fn new() -> Self {
+        AdmmBuilder {
+            id: None,
+            rho: None,
+            learning_rate: None,
+            inner_steps: 10,
+            epsilon: None,
+        }
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn rho(mut self, rho: f64) -> Self {
+        self.rho = Some(rho);
+        self
+    }
+
+    pub fn learning_rate(mut self, lr: f64) -> Self {
+        self.learning_rate = Some(lr);
+        self
+    }
+
+    pub fn inner_steps(mut self, steps: usize) -> Self {
+        self.inner_steps = steps;
+        self
+    }
+
+    pub fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = Some(epsilon);
+        self
+    }
+
+    pub fn build(self) -> Result<Admm, &'static str> {
+        let id = self.id.ok_or("Missing id")?;
+        let rho = self.rho.ok_or("Missing rho")?;
+        let learning_rate = self.learning_rate.ok_or("Missing learning_rate")?;
+        if rho.is_nan() || rho <= 0.0 {
+            return Err("rho must be positive");
+        }
+        if learning_rate.is_nan() || learning_rate <= 0.0 {
+            return Err("learning_rate must be positive");
+        }
+        if self.inner_steps == 0 {
+            return Err("inner_steps must be positive");
+        }
+        Ok(Admm {
+            id,
+            rho,
+            learning_rate,
+            inner_steps: self.inner_steps,
+            epsilon: self.epsilon,
+            duals: None,
+        })
+    }
+}
@@ -0,0 +1,273 @@
+//! Decentralized gradient descent (DGD).
+
+use super::{ConsensusOptimizer, GradientOracle, combine_with};
+use crate::optimizers::{
+    aggregation::Aggregator,
+    compression::{Compressor, Encoder, dense_bytes},
+};
+use convective_data::datasets::types::consensus::ConsensusConfig;
+
+/// Order of the mixing (combine) and local gradient (adapt) steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpdateStrategy {
+    /// `x_i ← Σ_j W_ij x_j − α ∇f_i(x_i)` (classic DGD).
+    #[default]
+    CombineThenAdapt,
+    /// `x_i ← Σ_j W_ij (x_j − α ∇f_j(x_j))` (diffusion).
+    AdaptThenCombine,
+}
+
+/// Decentralized gradient descent.
+///
+/// With a constant `learning_rate` the nodes converge to a neighbourhood of
+/// the global optimum whose radius shrinks with the step size.  A decaying
+/// [`LearningRateSchedule::InverseTime`](crate::optimizers::LearningRateSchedule::InverseTime)
+/// on the trainer removes that bias.
+///
+/// A robust [`Aggregator`] replaces the weighted neighbour average, which
+/// lets honest nodes tolerate Byzantine neighbours at the cost of exact
+/// averaging.
+///
+/// With a lossy [`Compressor`] every node shares its vector through an
+/// [`Encoder`]: neighbours mix the node's public estimate instead of its
+/// exact value, while the node itself uses its exact value.
+#[derive(Debug)]
+pub struct Dgd {
+    pub id: String,
+    pub learning_rate: f64,
+    /// Stop tolerance on the mean-loss change and the disagreement.
+    pub epsilon: Option<f64>,
+    pub strategy: UpdateStrategy,
+    /// Combination rule over the neighbourhood (default: weighted mean).
+    pub aggregator: Aggregator,
+    /// Compression of the shared vectors (default: none).
+    pub compressor: Compressor,
+    /// Seed of the randomized compressors; node `i` uses `seed + i`.
+    pub seed: Option<u64>,
+    encoders: Vec<Encoder>,
+    message_bytes: Vec<usize>,
+}
+
+impl Dgd {
+    pub fn builder() -> DgdBuilder {
+        DgdBuilder::new()
+    }
+
+    /// Build from a `[[models.consensus]]` entry with a `learning_rate` and
+    /// an optional `epsilon` parameter.
+    pub fn from_config(config: &ConsensusConfig) -> Result<Self, &'static str> {
+        let mut builder = DgdBuilder::new().id(config.id.clone());
+        if let Some(lr) = config.param("learning_rate") {
+            builder = builder.learning_rate(lr);
+        }
+        if let Some(eps) = config.param("epsilon") {
+            builder = builder.epsilon(eps);
+        }
+        builder.build()
+    }
+
+    /// Encoder of node `node`, used when the compressor is not the identity.
+    pub fn encoder(&self, node: usize) -> Encoder {
+        let seed = self.seed.map(|s| s.wrapping_add(node as u64));
+        Encoder::new(self.compressor, seed)
+    }
+
+    /// Vectors seen by the neighbours of each node: `shared` itself, or
+    /// the public estimates after this round's compressed messages.
+    /// Inactive nodes send nothing.
+    fn exchange(
+        &mut self,
+        shared: &[Vec<f64>],
+        active: &[bool],
+    ) -> Option<Vec<Vec<f64>>> {
+        let p = shared.first().map_or(0, Vec::len);
+        if self.compressor == Compressor::Identity {
+            self.message_bytes = active
+                .iter()
+                .map(|&a| if a { dense_bytes(p) } else { 0 })
+                .collect();
+            return None;
+        }
+        if self.encoders.len() != shared.len() {
+            self.encoders = (0..shared.len()).map(|i| self.encoder(i)).collect();
+        }
+        self.message_bytes = self
+            .encoders
+            .iter_mut()
+            .zip(shared)
+            .zip(active)
+            .map(|((encoder, x), &a)| if a { encoder.encode(x).bytes() } else { 0 })
+            .collect();
+        Some(
+            self.encoders
+                .iter()
+                .map(|e| e.estimate().to_vec())
+                .collect(),
+        )
+    }
+}
+
+impl ConsensusOptimizer for Dgd {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn round(
+        &mut self,
+        params: &mut [Vec<f64>],
+        mixing: &[Vec<f64>],
+        active: &[bool],
+        oracle: &mut GradientOracle<'_>,
+    ) -> Vec<f64> {
+        let local: Vec<_> = params
+            .iter()
+            .enumerate()
+            .map(|(i, x)| oracle(i, x))
+            .collect();
+        let lr = self.learning_rate;
+
+        let updated = match self.strategy {
+            UpdateStrategy::CombineThenAdapt => {
+                let public = self.exchange(params, active);
+                let mut mixed = combine_with(
+                    &self.aggregator,
+                    mixing,
+                    params,
+                    public.as_deref().unwrap_or(params),
+                );
+                for (x, g) in mixed.iter_mut().zip(&local) {
+                    for (xk, gk) in x.iter_mut().zip(&g.gradient) {
+                        *xk -= lr * gk;
+                    }
+                }
+                mixed
+            }
+            UpdateStrategy::AdaptThenCombine => {
+                let adapted: Vec<Vec<f64>> = params
+                    .iter()
+                    .zip(&local)
+                    .map(|(x, g)| {
+                        x.iter()
+                            .zip(&g.gradient)
+                            .map(|(xk, gk)| xk - lr * gk)
+                            .collect()
+                    })
+                    .collect();
+                let public = self.exchange(&adapted, active);
+                combine_with(
+                    &self.aggregator,
+                    mixing,
+                    &adapted,
+                    public.as_deref().unwrap_or(&adapted),
+                )
+            }
+        };
+
+        for ((x, new), &a) in params.iter_mut().zip(updated).zip(active) {
+            if a {
+                *x = new;
+            }
+        }
+        local.into_iter().map(|g| g.loss).collect()
+    }
+
+    fn tolerance(&self) -> Option<f64> {
+        self.epsilon
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+    fn message_bytes(&self, node: usize, dimension: usize) -> usize {
+        self.message_bytes
+            .get(node)
+            .copied()
+            .unwrap_or_else(|| dense_bytes(dimension))
+    }
+
+    fn reset(&mut self) {
+        self.encoders.clear();
+        self.message_bytes.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+pub struct DgdBuilder {
+    id: Option<String>,
+    learning_rate: Option<f64>,
+    epsilon: Option<f64>,
+    strategy: UpdateStrategy,
+    aggregator: Aggregator,
+    compressor: Compressor,
+    seed: Option<u64>,
+}
+
+impl DgdBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn learning_rate(mut self, lr: f64) -> Self {
+        self.learning_rate = Some(lr);
+        self
+    }
+
+    pub fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = Some(epsilon);
+        self
+    }
+
+    pub fn strategy(mut self, strategy: UpdateStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn aggregator(mut self, aggregator: Aggregator) -> Self {
+        self.aggregator = aggregator;
+        self
+    }
+
+    pub fn compressor(mut self, compressor: Compressor) -> Self {
+        self.compressor = compressor;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Result<Dgd, &'static str> {
+        let id = self.id.ok_or("Missing id")?;
+        let learning_rate = self.learning_rate.ok_or("Missing learning_rate")?;
+        if learning_rate.is_nan() || learning_rate <= 0.0 {
+            return Err("learning_rate must be positive");
+        }
+        self.compressor.validate()?;
+        Ok(Dgd {
+            id,
+            learning_rate,
+            epsilon: self.epsilon,
+            strategy: self.strategy,
+            aggregator: self.aggregator,
+            compressor: self.compressor,
+            seed: self.seed,
+            encoders: Vec::new(),
+            message_bytes: Vec::new(),
+        })
+    }
+}
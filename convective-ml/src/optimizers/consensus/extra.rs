@@ -0,0 +1,195 @@
+//! EXTRA: exact first-order decentralized algorithm.
+
+use super::{ConsensusOptimizer, GradientOracle, evaluate, evaluate_active, mix};
+use convective_data::datasets::types::consensus::ConsensusConfig;
+
+/// EXTRA consensus optimizer (Shi, Ling, Wu & Yin, 2015).
+///
+/// Corrects the DGD bias with the difference of two consecutive iterates,
+/// using `W̃ = (I + W) / 2`:
+///
+/// ```text
+/// x^1     = W x^0 − α ∇f(x^0)
+/// x^{k+2} = (I + W) x^{k+1} − W̃ x^k − α (∇f(x^{k+1}) − ∇f(x^k))
+/// ```
+///
+/// Converges to the exact optimum with a constant step size for symmetric,
+/// doubly-stochastic mixing matrices.
+#[derive(Debug)]
+pub struct Extra {
+    pub id: String,
+    pub learning_rate: f64,
+    pub epsilon: Option<f64>,
+    state: Option<ExtraState>,
+}
+
+#[derive(Debug)]
+struct ExtraState {
+    prev_params: Vec<Vec<f64>>,
+    prev_gradients: Vec<Vec<f64>>,
+    gradients: Vec<Vec<f64>>,
+    losses: Vec<f64>,
+    /// Whether each node has taken its first (DGD) step.
+    started: Vec<bool>,
+}
+
+impl Extra {
+    pub fn builder() -> ExtraBuilder {
+        ExtraBuilder::new()
+    }
+
+    /// Build from a config entry with `learning_rate` and optional `epsilon`.
+    pub fn from_config(config: &ConsensusConfig) -> Result<Self, &'static str> {
+        let mut builder = ExtraBuilder::new().id(config.id.clone());
+        if let Some(lr) = config.param("learning_rate") {
+            builder = builder.learning_rate(lr);
+        }
+        if let Some(eps) = config.param("epsilon") {
+            builder = builder.epsilon(eps);
+        }
+        builder.build()
+    }
+}
+
+impl ConsensusOptimizer for Extra {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn round(
+        &mut self,
+        params: &mut [Vec<f64>],
+        mixing: &[Vec<f64>],
+        active: &[bool],
+        oracle: &mut GradientOracle<'_>,
+    ) -> Vec<f64> {
+        let lr = self.learning_rate;
+        let state = match self.state.take() {
+            Some(state) if state.prev_params.len() == params.len() => state,
+            _ => {
+                let (losses, gradients) = evaluate(params, oracle);
+                ExtraState {
+                    prev_params: params.to_vec(),
+                    prev_gradients: gradients.clone(),
+                    gradients,
+                    losses,
+                    started: vec![false; params.len()],
+                }
+            }
+        };
+
+        let mixed = mix(mixing, params);
+        let mixed_prev = mix(mixing, &state.prev_params);
+        let next: Vec<Vec<f64>> = params
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                let g = &state.gradients[i];
+                if !active[i] {
+                    x.clone()
+                } else if state.started[i] {
+                    // x^{k+2} = x + Wx − (x_prev + W x_prev)/2 − α (g − g_prev)
+                    let (prev, g_prev) =
+                        (&state.prev_params[i], &state.prev_gradients[i]);
+                    (0..x.len())
+                        .map(|k| {
+                            x[k] + mixed[i][k]
+                                - 0.5 * (prev[k] + mixed_prev[i][k])
+                                - lr * (g[k] - g_prev[k])
+                        })
+                        .collect()
+                } else {
+                    // A node's first step is plain DGD.
+                    (0..x.len()).map(|k| mixed[i][k] - lr * g[k]).collect()
+                }
+            })
+            .collect();
+
+        // Inactive nodes keep their whole history.
+        let (mut losses, mut gradients) = (state.losses.clone(), state.gradients.clone());
+        evaluate_active(&next, active, oracle, &mut losses, &mut gradients);
+        let mut prev_params = state.prev_params;
+        let mut prev_gradients = state.prev_gradients;
+        let mut started = state.started;
+        for i in 0..params.len() {
+            if active[i] {
+                prev_params[i].clone_from(&params[i]);
+                prev_gradients[i].clone_from(&state.gradients[i]);
+                started[i] = true;
+            }
+        }
+        for (x, new) in params.iter_mut().zip(next) {
+            *x = new;
+        }
+        self.state = Some(ExtraState {
+            prev_params,
+            prev_gradients,
+            gradients,
+            losses,
+            started,
+        });
+        state.losses
+    }
+
+    fn tolerance(&self) -> Option<f64> {
+        self.epsilon
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+    fn reset(&mut self) {
+        self.state = None;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+pub struct ExtraBuilder {
+    id: Option<String>,
+    learning_rate: Option<f64>,
+    epsilon: Option<f64>,
+}
+
+impl ExtraBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn learning_rate(mut self, lr: f64) -> Self {
+        self.learning_rate = Some(lr);
+        self
+    }
+
+    pub fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = Some(epsilon);
+        self
+    }
+
+    pub fn build(self) -> Result<Extra, &'static str> {
+        let id = self.id.ok_or("Missing id")?;
+        let learning_rate = self.learning_rate.ok_or("Missing learning_rate")?;
+        if learning_rate.is_nan() || learning_rate <= 0.0 {
+            return Err("learning_rate must be positive");
+        }
+        Ok(Extra {
+            id,
+            learning_rate,
+            epsilon: self.epsilon,
+            state: None,
+        })
+    }
+}
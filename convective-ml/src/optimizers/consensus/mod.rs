@@ -0,0 +1,296 @@
+//! Consensus optimizers for decentralized training.
+//!
+//! A consensus optimizer updates the parameters of `N` nodes in lock-step.
+//! Node `i` only sees its own loss `f_i` and the parameters of its
+//! neighbours, weighted by a row-stochastic mixing matrix `W` (see
+//! [`TopologyConfig`](convective_data::datasets::types::topology::TopologyConfig)).
+//!
+//! Algorithms work on flat `Vec<f64>` parameter vectors (see
+//! [`Model::flat_parameters`](crate::models::Model::flat_parameters)), so
+//! they are backend-agnostic.  Local gradients are requested through an
+//! *oracle* callback supplied by the trainer, which lets an algorithm
+//! evaluate gradients at any point (several times per round if needed).
+
+pub mod admm;
+pub mod dgd;
+pub mod extra;
+pub mod tracking;
+
+pub use admm::{Admm, AdmmBuilder};
+pub use dgd::{Dgd, DgdBuilder, UpdateStrategy};
+pub use extra::{Extra, ExtraBuilder};
+pub use tracking::{GradientTracking, GradientTrackingBuilder};
+
+use super::{aggregation::Aggregator, compression::dense_bytes};
+use convective_data::datasets::types::consensus::{ConsensusConfig, ConsensusMethod};
+
+/// Local loss and gradient of one node at a given parameter vector.
+#[derive(Debug, Clone)]
+pub struct LocalGradient {
+    pub loss: f64,
+    pub gradient: Vec<f64>,
+}
+
+/// Gradient oracle: `oracle(i, x)` evaluates node `i`'s local objective at `x`.
+pub type GradientOracle<'a> = dyn FnMut(usize, &[f64]) -> LocalGradient + 'a;
+
+/// Synchronous decentralized optimizer over flat parameter vectors.
+pub trait ConsensusOptimizer: std::fmt::Debug + Send {
+    fn id(&self) -> &str;
+
+    /// Run one communication round.
+    ///
+    /// * `params` — one parameter vector per node, updated in place.
+    /// * `mixing` — row-stochastic `N × N` mixing matrix.
+    /// * `active` — `active[i]` is false when node `i` sits the round out;
+    ///   its parameters and optimizer state stay unchanged, and `mixing`
+    ///   must give it the identity row and column.
+    /// * `oracle` — local loss / gradient evaluation.
+    ///
+    /// Returns the local loss of every node at the start of the round.
+    fn round(
+        &mut self,
+        params: &mut [Vec<f64>],
+        mixing: &[Vec<f64>],
+        active: &[bool],
+        oracle: &mut GradientOracle<'_>,
+    ) -> Vec<f64>;
+
+    /// Convergence tolerance on loss change and disagreement, if any.
+    fn tolerance(&self) -> Option<f64> {
+        None
+    }
+
+    /// Step size currently in use.
+    fn learning_rate(&self) -> f64;
+
+    /// Change the step size between rounds, e.g. from a
+    /// [`LearningRateSchedule`](crate::optimizers::LearningRateSchedule).
+    fn set_learning_rate(&mut self, learning_rate: f64);
+
+    /// Size of the message node `node` sent to each out-neighbour in the
+    /// last round, for `dimension` parameters.
+    ///
+    /// Defaults to one uncompressed parameter vector.
+    fn message_bytes(&self, _node: usize, dimension: usize) -> usize {
+        dense_bytes(dimension)
+    }
+
+    /// Clear internal state carried between rounds.
+    fn reset(&mut self) {}
+}
+
+impl ConsensusOptimizer for Box<dyn ConsensusOptimizer> {
+    fn id(&self) -> &str {
+        self.as_ref().id()
+    }
+
+    fn round(
+        &mut self,
+        params: &mut [Vec<f64>],
+        mixing: &[Vec<f64>],
+        active: &[bool],
+        oracle: &mut GradientOracle<'_>,
+    ) -> Vec<f64> {
+        self.as_mut().round(params, mixing, active, oracle)
+    }
+
+    fn tolerance(&self) -> Option<f64> {
+        self.as_ref().tolerance()
+    }
+
+    fn learning_rate(&self) -> f64 {
+        self.as_ref().learning_rate()
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.as_mut().set_learning_rate(learning_rate)
+    }
+
+    fn message_bytes(&self, node: usize, dimension: usize) -> usize {
+        self.as_ref().message_bytes(node, dimension)
+    }
+
+    fn reset(&mut self) {
+        self.as_mut().reset()
+    }
+}
+
+/// Instantiate the optimizer selected by a `[[models.consensus]]` entry.
+///
+/// | `label`            | optimizer            | parameters                                       |
+/// |--------------------|----------------------|--------------------------------------------------|
+/// | `DGD` (default)    | [`Dgd`]              | `learning_rate`, `epsilon`                       |
+/// | `GradientTracking` | [`GradientTracking`] | `learning_rate`, `epsilon`                       |
+/// | `EXTRA`            | [`Extra`]            | `learning_rate`, `epsilon`                       |
+/// | `ADMM`             | [`Admm`]             | `rho`, `learning_rate`, `inner_steps`, `epsilon` |
+pub fn from_config(
+    config: &ConsensusConfig,
+) -> Result<Box<dyn ConsensusOptimizer>, &'static str> {
+    Ok(match config.label {
+        ConsensusMethod::DGD => Box::new(Dgd::from_config(config)?),
+        ConsensusMethod::GradientTracking => {
+            Box::new(GradientTracking::from_config(config)?)
+        }
+        ConsensusMethod::EXTRA => Box::new(Extra::from_config(config)?),
+        ConsensusMethod::ADMM => Box::new(Admm::from_config(config)?),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Evaluate every node at its parameters, returning `(losses, gradients)`.
+pub(crate) fn evaluate(
+    params: &[Vec<f64>],
+    oracle: &mut GradientOracle<'_>,
+) -> (Vec<f64>, Vec<Vec<f64>>) {
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let local = oracle(i, x);
+            (local.loss, local.gradient)
+        })
+        .unzip()
+}
+
+/// Re-evaluate the active nodes at `params`, updating their `losses` and
+/// `gradients`; inactive nodes keep theirs.
+pub(crate) fn evaluate_active(
+    params: &[Vec<f64>],
+    active: &[bool],
+    oracle: &mut GradientOracle<'_>,
+    losses: &mut [f64],
+    gradients: &mut [Vec<f64>],
+) {
+    for (i, x) in params.iter().enumerate() {
+        if active[i] {
+            let local = oracle(i, x);
+            losses[i] = local.loss;
+            gradients[i] = local.gradient;
+        }
+    }
+}
+
+/// Neighbour averaging `x_i ← Σ_j W_ij x_j`.
+pub fn mix(mixing: &[Vec<f64>], params: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let p = params.first().map_or(0, Vec::len);
+    mixing
+        .iter()
+        .map(|row| {
+            let mut out = vec![0.0; p];
+            for (w, x) in row.iter().zip(params) {
+                if *w != 0.0 {
+                    for (o, xk) in out.iter_mut().zip(x) {
+                        *o += w * xk;
+                    }
+                }
+            }
+            out
+        })
+        .collect()
+}
+
+/// Neighbour aggregation with `aggregator` over `{j : W_ij > 0} ∪ {i}`.
+///
+/// Equals [`mix`] for [`Aggregator::WeightedMean`].
+pub fn mix_with(
+    aggregator: &Aggregator,
+    mixing: &[Vec<f64>],
+    params: &[Vec<f64>],
+) -> Vec<Vec<f64>> {
+    combine_with(aggregator, mixing, params, params)
+}
+
+/// [`mix_with`] where node `i` uses its own `own[i]` and the values
+/// `public[j]` of its neighbours.
+pub fn combine_with(
+    aggregator: &Aggregator,
+    mixing: &[Vec<f64>],
+    own: &[Vec<f64>],
+    public: &[Vec<f64>],
+) -> Vec<Vec<f64>> {
+    if *aggregator == Aggregator::WeightedMean {
+        let mut mixed = mix(mixing, public);
+        if !std::ptr::eq(own, public) {
+            for (i, x) in mixed.iter_mut().enumerate() {
+                let w = mixing[i][i];
+                for (m, (o, p)) in x.iter_mut().zip(own[i].iter().zip(&public[i])) {
+                    *m += w * (o - p);
+                }
+            }
+        }
+        return mixed;
+    }
+    mixing
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let (vectors, weights): (Vec<&[f64]>, Vec<f64>) = row
+                .iter()
+                .zip(public)
+                .enumerate()
+                .filter(|&(j, (w, _))| j == i || *w > 0.0)
+                .map(|(j, (w, x))| {
+                    if j == i {
+                        (own[i].as_slice(), *w)
+                    } else {
+                        (x.as_slice(), *w)
+                    }
+                })
+                .unzip();
+            aggregator.aggregate(&vectors, &weights)
+        })
+        .collect()
+}
+
+/// Network average `x̄ = (1/N) Σ_i x_i`.
+pub fn average(params: &[Vec<f64>]) -> Vec<f64> {
+    let n = params.len() as f64;
+    let p = params.first().map_or(0, Vec::len);
+    let mut mean = vec![0.0; p];
+    for x in params {
+        for (m, xk) in mean.iter_mut().zip(x) {
+            *m += xk / n;
+        }
+    }
+    mean
+}
+
+/// Consensus disagreement `(1/N) Σ_i ‖x_i − x̄‖₂`.
+pub fn disagreement(params: &[Vec<f64>]) -> f64 {
+    if params.is_empty() {
+        return 0.0;
+    }
+    let mean = average(params);
+    params
+        .iter()
+        .map(|x| {
+            x.iter()
+                .zip(&mean)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        })
+        .sum::<f64>()
+        / params.len() as f64
+}
+
+/// Check that `mixing` is a non-negative, row-stochastic `n × n` matrix.
+pub fn validate_mixing(mixing: &[Vec<f64>], n: usize) -> Result<(), &'static str> {
+    if mixing.len() != n || mixing.iter().any(|row| row.len() != n) {
+        return Err("Mixing matrix must be square with one row per node");
+    }
+    if mixing.iter().flatten().any(|w| !w.is_finite() || *w < 0.0) {
+        return Err("Mixing matrix has negative or non-finite weights");
+    }
+    if mixing
+        .iter()
+        .any(|row| (row.iter().sum::<f64>() - 1.0).abs() > 1e-9)
+    {
+        return Err("Mixing matrix rows must sum to 1");
+    }
+    Ok(())
+}
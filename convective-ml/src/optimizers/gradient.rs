@@ -1,6 +1,8 @@
 //! Gradient-descent family of optimisers, generic over [`ComputeBackend`].
 
 use crate::models::backend::{ComputeBackend, NalgebraBackend};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // ---------------------------------------------------------------------------
 // Trait
@@ -10,17 +12,195 @@ use crate::models::backend::{ComputeBackend, NalgebraBackend};
 ///
 /// Generic over `B` so that the parameter update logic can differ between
 /// nalgebra (direct subtraction) and torch (`no_grad` context).
+///
+/// Optimisers may keep state between steps (momentum buffers, moment
+/// estimates, step counts).  That state is tied to one set of parameters;
+/// [`state`](Optimizer::state) and [`load_state`](Optimizer::load_state)
+/// export and restore it for checkpointing.
 pub trait Optimizer<B: ComputeBackend>: std::fmt::Debug + Send {
     /// Apply one gradient-descent step.
     fn step(
-        &self,
+        &mut self,
         weights: &mut B::Tensor,
         bias: &mut B::Tensor,
         weight_grad: &B::Tensor,
         bias_grad: &B::Tensor,
     );
+
+    /// Step size currently in use.
+    fn learning_rate(&self) -> f64;
+
+    /// Change the step size, e.g. from a
+    /// [`LearningRateSchedule`](super::LearningRateSchedule).  Accumulated
+    /// state is kept.
+    fn set_learning_rate(&mut self, learning_rate: f64);
+
+    /// Forget the state accumulated by previous steps.
+    fn reset(&mut self) {}
+
+    /// Snapshot of the internal state.
+    fn state(&self) -> OptimizerState {
+        OptimizerState::default()
+    }
+
+    /// Restore a snapshot taken by [`state`](Optimizer::state).
+    fn load_state(&mut self, _state: OptimizerState) -> Result<(), &'static str> {
+        Ok(())
+    }
+}
+
+/// Serialisable optimiser state.
+///
+/// Buffers are flattened like the parameters, as `[weights..., bias...]`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OptimizerState {
+    /// Number of steps taken.
+    pub steps: u64,
+    #[serde(default)]
+    pub scalars: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub buffers: BTreeMap<String, Vec<f64>>,
+}
+
+impl OptimizerState {
+    pub(crate) fn buffer(&self, name: &str) -> Option<Vec<f64>> {
+        self.buffers.get(name).cloned()
+    }
 }
 
+// ---------------------------------------------------------------------------
+// Flat-parameter optimisers
+// ---------------------------------------------------------------------------
+
+/// Flat `[weights..., bias...]` view of the parameters.
+pub(crate) fn flatten<B: ComputeBackend>(
+    weights: &B::Tensor,
+    bias: &B::Tensor,
+) -> Vec<f64> {
+    let mut flat = B::to_vec(weights);
+    flat.extend(B::to_vec(bias));
+    flat
+}
+
+/// State buffer in `slot`, reset to `len` zeros if it is missing or was
+/// built for parameters of another size.
+pub(crate) fn buffer(slot: &mut Option<Vec<f64>>, len: usize) -> &mut Vec<f64> {
+    if slot.as_ref().is_none_or(|v| v.len() != len) {
+        *slot = Some(vec![0.0; len]);
+    }
+    slot.get_or_insert_default()
+}
+
+/// Implement [`Optimizer`] for both backends on top of inherent methods
+/// working on flat `[weights..., bias...]` vectors:
+///
+/// * `fn update(&mut self, params: &mut [f64], grads: &[f64], weight_count: usize)`
+/// * `fn clear(&mut self)`
+/// * `fn export_state(&self) -> OptimizerState`
+/// * `fn import_state(&mut self, state: OptimizerState) -> Result<(), &'static str>`
+///
+/// `weight_count` separates the weights from the bias, which optimisers
+/// never penalise or decay.  The type must have a `learning_rate: f64` field.
+macro_rules! flat_optimizer {
+    ($ty:ty) => {
+        impl $crate::optimizers::Optimizer<$crate::models::backend::NalgebraBackend>
+            for $ty
+        {
+            fn step(
+                &mut self,
+                weights: &mut nalgebra::DMatrix<f64>,
+                bias: &mut nalgebra::DMatrix<f64>,
+                weight_grad: &nalgebra::DMatrix<f64>,
+                bias_grad: &nalgebra::DMatrix<f64>,
+            ) {
+                use $crate::{
+                    models::backend::NalgebraBackend, optimizers::gradient::flatten,
+                };
+                let mut params = flatten::<NalgebraBackend>(weights, bias);
+                let grads = flatten::<NalgebraBackend>(weight_grad, bias_grad);
+                self.update(&mut params, &grads, weights.len());
+                let (w, b) = params.split_at(weights.len());
+                weights.copy_from_slice(w);
+                bias.copy_from_slice(b);
+            }
+
+            fn learning_rate(&self) -> f64 {
+                self.learning_rate
+            }
+
+            fn set_learning_rate(&mut self, learning_rate: f64) {
+                self.learning_rate = learning_rate;
+            }
+
+            fn reset(&mut self) {
+                self.clear();
+            }
+
+            fn state(&self) -> $crate::optimizers::OptimizerState {
+                self.export_state()
+            }
+
+            fn load_state(
+                &mut self,
+                state: $crate::optimizers::OptimizerState,
+            ) -> Result<(), &'static str> {
+                self.import_state(state)
+            }
+        }
+
+        #[cfg(feature = "torch")]
+        impl $crate::optimizers::Optimizer<$crate::models::backend::TorchBackend>
+            for $ty
+        {
+            fn step(
+                &mut self,
+                weights: &mut tch::Tensor,
+                bias: &mut tch::Tensor,
+                weight_grad: &tch::Tensor,
+                bias_grad: &tch::Tensor,
+            ) {
+                use $crate::{
+                    models::backend::{ComputeBackend, TorchBackend},
+                    optimizers::gradient::flatten,
+                };
+                let mut params = flatten::<TorchBackend>(weights, bias);
+                let grads = flatten::<TorchBackend>(weight_grad, bias_grad);
+                let m = weights.numel();
+                self.update(&mut params, &grads, m);
+                let (w, b) = params.split_at(m);
+                tch::no_grad(|| {
+                    weights.copy_(&TorchBackend::from_slice(w).reshape(weights.size()));
+                    bias.copy_(&TorchBackend::from_slice(b).reshape(bias.size()));
+                });
+            }
+
+            fn learning_rate(&self) -> f64 {
+                self.learning_rate
+            }
+
+            fn set_learning_rate(&mut self, learning_rate: f64) {
+                self.learning_rate = learning_rate;
+            }
+
+            fn reset(&mut self) {
+                self.clear();
+            }
+
+            fn state(&self) -> $crate::optimizers::OptimizerState {
+                self.export_state()
+            }
+
+            fn load_state(
+                &mut self,
+                state: $crate::optimizers::OptimizerState,
+            ) -> Result<(), &'static str> {
+                self.import_state(state)
+            }
+        }
+    };
+}
+pub(crate) use flat_optimizer;
+
 // ---------------------------------------------------------------------------
 // Gradient Descent
 // ---------------------------------------------------------------------------
@@ -41,7 +221,7 @@ impl GradientDescent {
 
 impl Optimizer<NalgebraBackend> for GradientDescent {
     fn step(
-        &self,
+        &mut self,
         weights: &mut nalgebra::DMatrix<f64>,
         bias: &mut nalgebra::DMatrix<f64>,
         weight_grad: &nalgebra::DMatrix<f64>,
@@ -50,6 +230,14 @@ impl Optimizer<NalgebraBackend> for GradientDescent {
         *weights -= weight_grad * self.learning_rate;
         *bias -= bias_grad * self.learning_rate;
     }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
 }
 
 // --- Torch impl ---
@@ -60,7 +248,7 @@ use crate::models::backend::TorchBackend;
 #[cfg(feature = "torch")]
 impl Optimizer<TorchBackend> for GradientDescent {
     fn step(
-        &self,
+        &mut self,
         weights: &mut tch::Tensor,
         bias: &mut tch::Tensor,
         weight_grad: &tch::Tensor,
@@ -71,6 +259,14 @@ impl Optimizer<TorchBackend> for GradientDescent {
             let _ = bias.f_sub_(&(bias_grad * self.learning_rate));
         });
     }
+
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
 }
 
 // ---------------------------------------------------------------------------
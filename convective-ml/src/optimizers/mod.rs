@@ -1,2 +1,23 @@
+pub mod adaptive;
+pub mod aggregation;
+pub mod compression;
+pub mod consensus;
 pub mod gradient;
-pub use gradient::{GradientDescent, GradientDescentBuilder, Optimizer};
+pub mod momentum;
+pub mod proximal;
+pub mod schedule;
+pub mod solver;
+pub use adaptive::{AdaGrad, AdaGradBuilder, Adam, AdamBuilder, RmsProp, RmsPropBuilder};
+pub use aggregation::{Adversary, Aggregator};
+pub use compression::{Compressed, Compressor};
+pub use consensus::{
+    Admm, ConsensusOptimizer, Dgd, DgdBuilder, Extra, GradientTracking, UpdateStrategy,
+};
+pub use gradient::{GradientDescent, GradientDescentBuilder, Optimizer, OptimizerState};
+pub use momentum::{Momentum, MomentumBuilder};
+pub use proximal::{ProximalGradient, ProximalGradientBuilder};
+pub use schedule::LearningRateSchedule;
+pub use solver::{
+    CoordinateDescent, CoordinateDescentBuilder, Lbfgs, LbfgsBuilder, Newton,
+    NewtonBuilder, Problem, Ridge, RidgeBuilder, Solver,
+};
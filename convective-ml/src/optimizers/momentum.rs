@@ -0,0 +1,134 @@
+//! Heavy-ball and Nesterov momentum.
+
+use super::gradient::{OptimizerState, buffer, flat_optimizer};
+
+/// Gradient descent with momentum:
+///
+/// ```text
+/// v ← μ v + g
+/// w ← w − η v              (heavy ball)
+/// w ← w − η (g + μ v)      (Nesterov)
+/// ```
+///
+/// The Nesterov form evaluates the look-ahead through the current
+/// gradient, as in Sutskever et al. (2013), so it needs no extra gradient
+/// evaluation.
+#[derive(Debug)]
+pub struct Momentum {
+    pub id: String,
+    pub learning_rate: f64,
+    pub momentum: f64,
+    pub nesterov: bool,
+    velocity: Option<Vec<f64>>,
+    steps: u64,
+}
+
+impl Momentum {
+    pub fn builder() -> MomentumBuilder {
+        MomentumBuilder::new()
+    }
+
+    fn update(&mut self, params: &mut [f64], grads: &[f64], _weight_count: usize) {
+        let velocity = buffer(&mut self.velocity, grads.len());
+        for ((p, g), v) in params.iter_mut().zip(grads).zip(velocity.iter_mut()) {
+            *v = self.momentum * *v + g;
+            let direction = if self.nesterov {
+                g + self.momentum * *v
+            } else {
+                *v
+            };
+            *p -= self.learning_rate * direction;
+        }
+        self.steps += 1;
+    }
+
+    fn clear(&mut self) {
+        self.velocity = None;
+        self.steps = 0;
+    }
+
+    fn export_state(&self) -> OptimizerState {
+        let mut state = OptimizerState {
+            steps: self.steps,
+            ..Default::default()
+        };
+        if let Some(v) = &self.velocity {
+            state.buffers.insert("velocity".into(), v.clone());
+        }
+        state
+    }
+
+    fn import_state(&mut self, state: OptimizerState) -> Result<(), &'static str> {
+        self.velocity = state.buffer("velocity");
+        self.steps = state.steps;
+        Ok(())
+    }
+}
+
+flat_optimizer!(Momentum);
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct MomentumBuilder {
+    id: Option<String>,
+    learning_rate: Option<f64>,
+    momentum: f64,
+    nesterov: bool,
+}
+
+impl Default for MomentumBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MomentumBuilder {
+    pub fn new() -> Self {
+        MomentumBuilder {
+            id: None,
+            learning_rate: None,
+            momentum: 0.9,
+            nesterov: false,
+        }
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn learning_rate(mut self, lr: f64) -> Self {
+        self.learning_rate = Some(lr);
+        self
+    }
+
+    /// Momentum coefficient `μ ∈ [0, 1)` (default 0.9).
+    pub fn momentum(mut self, momentum: f64) -> Self {
+        self.momentum = momentum;
+        self
+    }
+
+    pub fn nesterov(mut self, nesterov: bool) -> Self {
+        self.nesterov = nesterov;
+        self
+    }
+
+    pub fn build(self) -> Result<Momentum, &'static str> {
+        let id = self.id.ok_or("Missing id")?;
+        let learning_rate = self.learning_rate.ok_or("Missing learning_rate")?;
+        if !(0.0..1.0).contains(&self.momentum) {
+            return Err("momentum must be in [0, 1)");
+        }
+        Ok(Momentum {
+            id,
+            learning_rate,
+            momentum: self.momentum,
+            nesterov: self.nesterov,
+            velocity: None,
+            steps: 0,
+        })
+    }
+}
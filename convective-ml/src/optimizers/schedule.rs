@@ -0,0 +1,108 @@
+//! Learning-rate schedules.
+//!
+//! A [`LearningRateSchedule`] maps the trainer's iteration counter `t`
+//! (epoch for [`Singular`](crate::processes::Singular), round for the
+//! federated and decentralized trainers) to a multiple of the optimizer's
+//! base learning rate `η₀`.  Trainers apply it through
+//! [`Optimizer::set_learning_rate`](super::Optimizer::set_learning_rate)
+//! and [`ConsensusOptimizer::set_learning_rate`](super::ConsensusOptimizer::set_learning_rate)
+//! before every iteration, so it works with every optimizer.
+
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum LearningRateSchedule {
+    /// `η₀`.
+    #[default]
+    Constant,
+    /// `η₀ γ^⌊t / step_size⌋`.
+    Step { step_size: usize, gamma: f64 },
+    /// `η₀ γᵗ`.
+    Exponential { gamma: f64 },
+    /// Cosine annealing from `η₀` to `η₀ · min_factor` over `period`
+    /// iterations, then constant:
+    /// `η₀ (m + (1 − m)(1 + cos(π t / period)) / 2)`.
+    Cosine { period: usize, min_factor: f64 },
+    /// `η₀ / (1 + decay · t)^power`.  `power = 1` gives the `O(1/t)` steps
+    /// under which DGD converges to the exact optimum; any
+    /// `power ∈ (1/2, 1]` satisfies `Σ η_t = ∞`, `Σ η_t² < ∞`.
+    InverseTime { decay: f64, power: f64 },
+    /// Linear warmup from `η₀ / steps` to `η₀` over the first `steps`
+    /// iterations, then `then` with its counter starting at zero.
+    Warmup {
+        steps: usize,
+        then: Box<LearningRateSchedule>,
+    },
+}
+
+impl LearningRateSchedule {
+    /// Learning rate at iteration `t` for base rate `base`.
+    pub fn rate(&self, base: f64, t: usize) -> f64 {
+        base * self.factor(t)
+    }
+
+    /// Multiplier of the base rate at iteration `t`.
+    pub fn factor(&self, t: usize) -> f64 {
+        match self {
+            LearningRateSchedule::Constant => 1.0,
+            LearningRateSchedule::Step { step_size, gamma } => {
+                gamma.powi((t / step_size.max(&1)) as i32)
+            }
+            LearningRateSchedule::Exponential { gamma } => gamma.powi(t as i32),
+            LearningRateSchedule::Cosine { period, min_factor } => {
+                let progress = (t as f64 / (*period).max(1) as f64).min(1.0);
+                min_factor + (1.0 - min_factor) * (1.0 + (PI * progress).cos()) / 2.0
+            }
+            LearningRateSchedule::InverseTime { decay, power } => {
+                (1.0 + decay * t as f64).powf(-power)
+            }
+            LearningRateSchedule::Warmup { steps, then } => {
+                if t < *steps {
+                    (t + 1) as f64 / *steps as f64
+                } else {
+                    then.factor(t - steps)
+                }
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match self {
+            LearningRateSchedule::Constant => Ok(()),
+            LearningRateSchedule::Step { step_size, gamma } => {
+                if *step_size == 0 {
+                    return Err("step_size must be positive");
+                }
+                positive(*gamma, "gamma must be positive")
+            }
+            LearningRateSchedule::Exponential { gamma } => {
+                positive(*gamma, "gamma must be positive")
+            }
+            LearningRateSchedule::Cosine { period, min_factor } => {
+                if *period == 0 {
+                    return Err("period must be positive");
+                }
+                if !(0.0..=1.0).contains(min_factor) {
+                    return Err("min_factor must be in [0, 1]");
+                }
+                Ok(())
+            }
+            LearningRateSchedule::InverseTime { decay, power } => {
+                if decay.is_nan() || *decay < 0.0 {
+                    return Err("decay must be non-negative");
+                }
+                positive(*power, "power must be positive")
+            }
+            LearningRateSchedule::Warmup { then, .. } => then.validate(),
+        }
+    }
+}
+
+fn positive(value: f64, message: &'static str) -> Result<(), &'static str> {
+    if value.is_nan() || value <= 0.0 {
+        Err(message)
+    } else {
+        Ok(())
+    }
+}
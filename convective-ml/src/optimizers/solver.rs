@@ -0,0 +1,684 @@
+//! Full-batch solvers that need more than one gradient per step.
+//!
+//! An [`Optimizer`](super::Optimizer) only sees the gradient at the current
+//! parameters.  A [`Solver`] instead gets a [`Problem`] it can evaluate at
+//! any point (line searches) and, where the loss provides one, query for
+//! the Hessian (Newton steps).  Both work on flat
+//! `[weights..., bias...]` vectors.
+
+use super::{consensus::LocalGradient, proximal::soft_threshold};
+use crate::{
+    functions::{LossFunction, RegType, Regularization, Samples, cost::sample_scale},
+    models::{ComputeBackend, Model, NalgebraBackend},
+    processes::distributed::local_gradient,
+};
+use std::collections::VecDeque;
+
+// ---------------------------------------------------------------------------
+// Trait
+// ---------------------------------------------------------------------------
+
+/// Iterative solver driven by a trainer, one iteration per epoch.
+pub trait Solver<B: ComputeBackend>: std::fmt::Debug + Send {
+    fn id(&self) -> &str;
+
+    /// Run one iteration from the model's current parameters, leaving the
+    /// model at the new iterate.
+    ///
+    /// Returns the loss at the start of the iteration.
+    fn iterate(&mut self, problem: &mut Problem<'_, B>) -> Result<f64, &'static str>;
+
+    /// Forget curvature information gathered by previous iterations.
+    fn reset(&mut self) {}
+}
+
+/// Training objective of one model on a fixed batch.
+#[derive(Debug)]
+pub struct Problem<'a, B: ComputeBackend> {
+    model: &'a mut dyn Model<B>,
+    loss: &'a dyn LossFunction<B>,
+    samples: &'a Samples<B>,
+    evaluations: usize,
+}
+
+impl<'a, B: ComputeBackend> Problem<'a, B> {
+    /// Objective of `model` under `loss` on `samples`.
+    pub fn new(
+        model: &'a mut dyn Model<B>,
+        loss: &'a dyn LossFunction<B>,
+        samples: &'a Samples<B>,
+    ) -> Self {
+        Problem {
+            model,
+            loss,
+            samples,
+            evaluations: 0,
+        }
+    }
+
+    pub fn parameters(&self) -> Vec<f64> {
+        self.model.flat_parameters()
+    }
+
+    pub fn set_parameters(&mut self, x: &[f64]) {
+        self.model.set_flat_parameters(x);
+    }
+
+    /// Loss and gradient at `x`; leaves the model at `x`.
+    pub fn evaluate(&mut self, x: &[f64]) -> LocalGradient {
+        self.evaluations += 1;
+        local_gradient(&mut *self.model, self.loss, self.samples, x)
+    }
+
+    /// Hessian at `x` from [`LossFunction::hessian`]; leaves the model at
+    /// `x`.
+    pub fn hessian(&mut self, x: &[f64]) -> Option<B::Tensor> {
+        self.model.set_flat_parameters(x);
+        let logits = self.model.forward(&self.samples.features);
+        let (weights, _) = self.model.parameters_mut();
+        self.loss.hessian(self.samples, &logits, weights)
+    }
+
+    /// Samples the objective is evaluated on.
+    pub fn samples(&self) -> &'a Samples<B> {
+        self.samples
+    }
+
+    /// Number of [`evaluate`](Problem::evaluate) calls so far.
+    pub fn evaluations(&self) -> usize {
+        self.evaluations
+    }
+}
+
+/// Largest `t ∈ {1, ½, ¼, …}` (at most `max_halvings` halvings) with
+/// `f(x + t d) ≤ f(x) + c₁ t ∇f(x)ᵀd`; the model is left at the accepted
+/// point.  The last trial point is accepted if none satisfies the
+/// condition.
+fn backtracking<B: ComputeBackend>(
+    problem: &mut Problem<'_, B>,
+    x: &[f64],
+    current: &LocalGradient,
+    direction: &[f64],
+    max_halvings: usize,
+) -> f64 {
+    const C1: f64 = 1e-4;
+    let slope = dot(&current.gradient, direction);
+    let mut t = 1.0;
+    for _ in 0..max_halvings {
+        let trial = axpy(x, t, direction);
+        let loss = problem.evaluate(&trial).loss;
+        if loss.is_finite() && loss <= current.loss + C1 * t * slope {
+            return t;
+        }
+        t *= 0.5;
+    }
+    problem.set_parameters(&axpy(x, t, direction));
+    t
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// `x + t d`.
+fn axpy(x: &[f64], t: f64, d: &[f64]) -> Vec<f64> {
+    x.iter().zip(d).map(|(x, d)| x + t * d).collect()
+}
+
+fn converged(gradient: &[f64], tolerance: f64) -> bool {
+    gradient.iter().all(|g| g.abs() <= tolerance)
+}
+
+// ---------------------------------------------------------------------------
+// Newton / IRLS
+// ---------------------------------------------------------------------------
+
+/// Damped Newton method: `d = −(H + δI)⁻¹ ∇f`, followed by a backtracking
+/// line search.
+///
+/// Needs a loss with a closed-form [`hessian`](LossFunction::hessian), so
+/// it is only implemented for [`NalgebraBackend`].  For logistic
+/// regression each step is one iteratively reweighted least squares (IRLS)
+/// solve and convergence is quadratic near the optimum, typically within
+/// ten iterations.  The damping `δ` keeps the system solvable when the
+/// data are separable or features are collinear.
+#[derive(Debug)]
+pub struct Newton {
+    pub id: String,
+    pub damping: f64,
+    /// Stop once every gradient entry is at most this large.
+    pub tolerance: f64,
+    pub max_halvings: usize,
+}
+
+impl Newton {
+    pub fn builder() -> NewtonBuilder {
+        NewtonBuilder::new()
+    }
+}
+
+impl Solver<NalgebraBackend> for Newton {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn iterate(
+        &mut self,
+        problem: &mut Problem<'_, NalgebraBackend>,
+    ) -> Result<f64, &'static str> {
+        let x = problem.parameters();
+        let current = problem.evaluate(&x);
+        if converged(&current.gradient, self.tolerance) {
+            return Ok(current.loss);
+        }
+
+        let mut hessian = problem
+            .hessian(&x)
+            .ok_or("Loss function has no closed-form Hessian")?;
+        if hessian.nrows() != x.len() || hessian.ncols() != x.len() {
+            return Err("Hessian does not match the number of parameters");
+        }
+        for k in 0..x.len() {
+            hessian[(k, k)] += self.damping;
+        }
+        let gradient = nalgebra::DVector::from_column_slice(&current.gradient);
+        let step = hessian
+            .cholesky()
+            .ok_or("Hessian is not positive definite")?
+            .solve(&gradient);
+        let direction: Vec<f64> = step.iter().map(|s| -s).collect();
+
+        backtracking(problem, &x, &current, &direction, self.max_halvings);
+        Ok(current.loss)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Ridge
+// ---------------------------------------------------------------------------
+
+/// Closed-form ridge regression: one iteration jumps to the minimiser of
+///
+/// ```text
+/// Σ cᵢ (xᵢᵀw + b − yᵢ)² + α ‖w‖₂²
+/// ```
+///
+/// by a Cholesky solve of the normal equations
+/// `([X 1]ᵀ C [X 1] + α I_w) β = [X 1]ᵀ C y`, with `cᵢ = 1 / n` or the
+/// normalised sample weights.  The intercept is not penalised.
+///
+/// This is the objective of [`Mse`](crate::functions::Mse) with an L2
+/// [`Regularization`](crate::functions::Regularization) of strength `α`;
+/// use that loss so the reported losses match what is minimised.  Needs a
+/// single-output model such as
+/// [`LinearModel`](crate::models::LinearModel) with
+/// [`Family::Gaussian`](crate::models::Family::Gaussian).
+#[derive(Debug)]
+pub struct Ridge {
+    pub id: String,
+    pub alpha: f64,
+}
+
+impl Ridge {
+    pub fn builder() -> RidgeBuilder {
+        RidgeBuilder::new()
+    }
+}
+
+impl Solver<NalgebraBackend> for Ridge {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn iterate(
+        &mut self,
+        problem: &mut Problem<'_, NalgebraBackend>,
+    ) -> Result<f64, &'static str> {
+        let x = problem.parameters();
+        let samples = problem.samples();
+        let (n, m) = samples.features.shape();
+        if x.len() != m + 1 {
+            return Err("Ridge needs a model with one weight per feature and a bias");
+        }
+        let loss = problem.evaluate(&x).loss;
+
+        let scale = sample_scale(n, samples.sample_weights.as_ref());
+        let design = samples.features.clone().insert_column(m, 1.0);
+        let mut weighted = design.clone();
+        for (i, c) in scale.iter().enumerate() {
+            weighted.row_mut(i).scale_mut(*c);
+        }
+
+        let mut gram = design.transpose() * &weighted;
+        for k in 0..m {
+            gram[(k, k)] += self.alpha;
+        }
+        let rhs = weighted.transpose() * &samples.targets;
+        let beta = gram
+            .cholesky()
+            .ok_or("Normal equations are singular; use a positive alpha")?
+            .solve(&rhs);
+
+        problem.set_parameters(beta.as_slice());
+        Ok(loss)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Coordinate descent
+// ---------------------------------------------------------------------------
+
+/// Cyclic coordinate descent for the lasso and the elastic net: one
+/// iteration sweeps the intercept and then every weight, each time
+/// minimising
+///
+/// ```text
+/// Σ cᵢ (xᵢᵀw + b − yᵢ)² + c₁ ‖w‖₁ + c₂ ‖w‖₂²
+/// ```
+///
+/// exactly along that coordinate, with `cᵢ` as in [`Ridge`] and the
+/// intercept unpenalised.  The residual `r = y − Xw − b` is kept up to
+/// date, so a weight update
+///
+/// ```text
+/// w_j ← S(Σ cᵢ xᵢⱼ (rᵢ + xᵢⱼ w_j), c₁ / 2) / (Σ cᵢ xᵢⱼ² + c₂)
+/// ```
+///
+/// costs `O(n)` and a sweep `O(nm)`, the price of one gradient.  `S` is
+/// the [`soft_threshold`], so weights reach exact zeros.
+///
+/// This is the objective of [`Mse`](crate::functions::Mse) with the same
+/// [`Regularization`]; use that loss so the reported losses match what is
+/// minimised.  Needs a single-output model such as
+/// [`LinearModel`](crate::models::LinearModel) with
+/// [`Family::Gaussian`](crate::models::Family::Gaussian).
+#[derive(Debug)]
+pub struct CoordinateDescent {
+    pub id: String,
+    pub penalty: Regularization,
+}
+
+impl CoordinateDescent {
+    pub fn builder() -> CoordinateDescentBuilder {
+        CoordinateDescentBuilder::new()
+    }
+}
+
+impl Solver<NalgebraBackend> for CoordinateDescent {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn iterate(
+        &mut self,
+        problem: &mut Problem<'_, NalgebraBackend>,
+    ) -> Result<f64, &'static str> {
+        let mut x = problem.parameters();
+        let samples = problem.samples();
+        let (n, m) = samples.features.shape();
+        if x.len() != m + 1 {
+            return Err(
+                "Coordinate descent needs a model with one weight per feature and a bias",
+            );
+        }
+        let loss = problem.evaluate(&x).loss;
+
+        let (l1, l2) = self.penalty.coefficients();
+        let scale = sample_scale(n, samples.sample_weights.as_ref());
+        let features = &samples.features;
+        let fitted = features * nalgebra::DVector::from_column_slice(&x[..m]);
+        let mut residual: Vec<f64> = samples
+            .targets
+            .iter()
+            .zip(fitted.iter())
+            .map(|(y, z)| y - z - x[m])
+            .collect();
+
+        let total: f64 = scale.iter().sum();
+        if total > 0.0 {
+            let shift = dot(&scale, &residual) / total;
+            residual.iter_mut().for_each(|r| *r -= shift);
+            x[m] += shift;
+        }
+
+        for (weight, column) in x[..m].iter_mut().zip(features.column_iter()) {
+            let (mut rho, mut curvature) = (0.0, 0.0);
+            for ((r, c), v) in residual.iter().zip(&scale).zip(column.iter()) {
+                rho += c * v * r;
+                curvature += c * v * v;
+            }
+            rho += curvature * *weight;
+            let updated = if curvature + l2 > 0.0 {
+                soft_threshold(rho, l1 / 2.0) / (curvature + l2)
+            } else {
+                0.0
+            };
+
+            let delta = updated - *weight;
+            if delta != 0.0 {
+                for (r, v) in residual.iter_mut().zip(column.iter()) {
+                    *r -= delta * v;
+                }
+                *weight = updated;
+            }
+        }
+
+        problem.set_parameters(&x);
+        Ok(loss)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// L-BFGS
+// ---------------------------------------------------------------------------
+
+/// Limited-memory BFGS (Nocedal, 1980) with a backtracking Armijo line
+/// search.
+///
+/// The inverse Hessian is approximated from the last `memory` pairs
+/// `s = x_{k+1} − x_k`, `y = ∇f_{k+1} − ∇f_k` by the two-loop recursion,
+/// scaled by `sᵀy / yᵀy`.  Pairs with `sᵀy ≤ 0` are skipped so the
+/// approximation stays positive definite, and the history is dropped if it
+/// ever fails to give a descent direction.  Only gradients are needed, so
+/// it works with every backend.
+#[derive(Debug)]
+pub struct Lbfgs {
+    pub id: String,
+    pub memory: usize,
+    /// Stop once every gradient entry is at most this large.
+    pub tolerance: f64,
+    pub max_halvings: usize,
+    history: VecDeque<(Vec<f64>, Vec<f64>)>,
+    previous: Option<(Vec<f64>, Vec<f64>)>,
+}
+
+impl Lbfgs {
+    pub fn builder() -> LbfgsBuilder {
+        LbfgsBuilder::new()
+    }
+
+    /// `−H ∇f` by the two-loop recursion.
+    fn direction(&self, gradient: &[f64]) -> Vec<f64> {
+        let mut q = gradient.to_vec();
+        let mut alphas = Vec::with_capacity(self.history.len());
+        for (s, y) in self.history.iter().rev() {
+            let alpha = dot(s, &q) / dot(y, s);
+            for (q, y) in q.iter_mut().zip(y) {
+                *q -= alpha * y;
+            }
+            alphas.push(alpha);
+        }
+        if let Some((s, y)) = self.history.back() {
+            let gamma = dot(s, y) / dot(y, y);
+            q.iter_mut().for_each(|q| *q *= gamma);
+        }
+        for ((s, y), alpha) in self.history.iter().zip(alphas.into_iter().rev()) {
+            let beta = dot(y, &q) / dot(y, s);
+            for (q, s) in q.iter_mut().zip(s) {
+                *q += (alpha - beta) * s;
+            }
+        }
+        q.iter_mut().for_each(|q| *q = -*q);
+        q
+    }
+}
+
+impl<B: ComputeBackend> Solver<B> for Lbfgs {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn iterate(&mut self, problem: &mut Problem<'_, B>) -> Result<f64, &'static str> {
+        let x = problem.parameters();
+        let current = problem.evaluate(&x);
+        if converged(&current.gradient, self.tolerance) {
+            return Ok(current.loss);
+        }
+
+        if let Some((x_prev, g_prev)) = self.previous.take() {
+            if x_prev.len() == x.len() {
+                let s: Vec<f64> = x.iter().zip(&x_prev).map(|(a, b)| a - b).collect();
+                let y: Vec<f64> = current
+                    .gradient
+                    .iter()
+                    .zip(&g_prev)
+                    .map(|(a, b)| a - b)
+                    .collect();
+                if dot(&s, &y) > f64::EPSILON * dot(&y, &y) {
+                    if self.history.len() == self.memory {
+                        self.history.pop_front();
+                    }
+                    self.history.push_back((s, y));
+                }
+            } else {
+                self.history.clear();
+            }
+        }
+
+        let mut direction = self.direction(&current.gradient);
+        if dot(&direction, &current.gradient) >= 0.0 {
+            self.history.clear();
+            direction = current.gradient.iter().map(|g| -g).collect();
+        }
+        if self.history.is_empty() {
+            // No curvature yet: cap the first step at unit length.
+            let norm = dot(&direction, &direction).sqrt();
+            if norm > 1.0 {
+                direction.iter_mut().for_each(|d| *d /= norm);
+            }
+        }
+
+        backtracking(problem, &x, &current, &direction, self.max_halvings);
+        self.previous = Some((x, current.gradient));
+        Ok(current.loss)
+    }
+
+    fn reset(&mut self) {
+        self.history.clear();
+        self.previous = None;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Builders
+// ---------------------------------------------------------------------------
+
+fn validate(id: Option<String>, tolerance: f64) -> Result<String, &'static str> {
+    let id = id.ok_or("Missing id")?;
+    if tolerance.is_nan() || tolerance < 0.0 {
+        return Err("tolerance must be non-negative");
+    }
+    Ok(id)
+}
+
+#[derive(Debug)]
+pub struct NewtonBuilder {
+    id: Option<String>,
+    damping: f64,
+    tolerance: f64,
+    max_halvings: usize,
+}
+
+impl Default for NewtonBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NewtonBuilder {
+    pub fn new() -> Self {
+        NewtonBuilder {
+            id: None,
+            damping: 1e-8,
+            tolerance: 1e-10,
+            max_halvings: 30,
+        }
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Diagonal added to the Hessian (default 1e-8).
+    pub fn damping(mut self, damping: f64) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Gradient tolerance below which steps are skipped (default 1e-10).
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Maximum step halvings of the line search (default 30).
+    pub fn max_halvings(mut self, max_halvings: usize) -> Self {
+        self.max_halvings = max_halvings;
+        self
+    }
+
+    pub fn build(self) -> Result<Newton, &'static str> {
+        let id = validate(self.id, self.tolerance)?;
+        if self.damping.is_nan() || self.damping < 0.0 {
+            return Err("damping must be non-negative");
+        }
+        Ok(Newton {
+            id,
+            damping: self.damping,
+            tolerance: self.tolerance,
+            max_halvings: self.max_halvings,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct LbfgsBuilder {
+    id: Option<String>,
+    memory: usize,
+    tolerance: f64,
+    max_halvings: usize,
+}
+
+impl Default for LbfgsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LbfgsBuilder {
+    pub fn new() -> Self {
+        LbfgsBuilder {
+            id: None,
+            memory: 10,
+            tolerance: 1e-10,
+            max_halvings: 30,
+        }
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Number of stored curvature pairs (default 10).
+    pub fn memory(mut self, memory: usize) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Gradient tolerance below which steps are skipped (default 1e-10).
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Maximum step halvings of the line search (default 30).
+    pub fn max_halvings(mut self, max_halvings: usize) -> Self {
+        self.max_halvings = max_halvings;
+        self
+    }
+
+    pub fn build(self) -> Result<Lbfgs, &'static str> {
+        let id = validate(self.id, self.tolerance)?;
+        if self.memory == 0 {
+            return Err("memory must be positive");
+        }
+        Ok(Lbfgs {
+            id,
+            memory: self.memory,
+            tolerance: self.tolerance,
+            max_halvings: self.max_halvings,
+            history: VecDeque::new(),
+            previous: None,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RidgeBuilder {
+    id: Option<String>,
+    alpha: f64,
+}
+
+impl RidgeBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Penalty strength `α` on the weights (default 0, ordinary least
+    /// squares).
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn build(self) -> Result<Ridge, &'static str> {
+        let id = self.id.ok_or("Missing id")?;
+        if !self.alpha.is_finite() || self.alpha < 0.0 {
+            return Err("alpha must be non-negative");
+        }
+        Ok(Ridge {
+            id,
+            alpha: self.alpha,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CoordinateDescentBuilder {
+    id: Option<String>,
+    penalty: Option<(RegType, f64, f64)>,
+}
+
+impl CoordinateDescentBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Penalty with strength `c` and elastic-net mix `lambda` (see
+    /// [`Regularization`]).
+    pub fn penalty(mut self, kind: RegType, c: f64, lambda: f64) -> Self {
+        self.penalty = Some((kind, c, lambda));
+        self
+    }
+
+    pub fn build(self) -> Result<CoordinateDescent, &'static str> {
+        let id = self.id.ok_or("Missing id")?;
+        let (kind, c, lambda) = self.penalty.ok_or("Missing penalty")?;
+        Ok(CoordinateDescent {
+            id,
+            penalty: Regularization::new(kind, c, lambda)?,
+        })
+    }
+}
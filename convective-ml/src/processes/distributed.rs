@@ -0,0 +1,488 @@
+//! Decentralized multi-node trainer, generic over [`ComputeBackend`].
+
+use super::errors::ProcessError;
+use crate::{
+    functions::{LossFunction, Samples},
+    models::{ComputeBackend, Model, ModelMode},
+    optimizers::{
+        LearningRateSchedule,
+        aggregation::Adversary,
+        consensus::{ConsensusOptimizer, LocalGradient, disagreement, validate_mixing},
+    },
+};
+use convective_data::datasets::{BatchSampler, Dataset, types::topology::TopologyConfig};
+use convective_graph::dynamic::DynamicTopology;
+use rand::{SeedableRng, rngs::StdRng};
+use serde::Serialize;
+
+/// Per-round training report.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoundRecord {
+    pub round: usize,
+    /// Local loss of every node at the start of the round.
+    pub losses: Vec<f64>,
+    /// Mean of `losses` over honest nodes.
+    pub mean_loss: f64,
+    /// Nodes that took part in the round.
+    pub active: usize,
+    /// Consensus disagreement `(1/N) Σ_i ‖x_i − x̄‖₂` after the round,
+    /// over honest nodes only.
+    pub disagreement: f64,
+    /// Bytes each node sent to its out-neighbours during the round.
+    pub bytes_sent: Vec<usize>,
+}
+
+// ---------------------------------------------------------------------------
+// Distributed
+// ---------------------------------------------------------------------------
+
+/// Trainer for `N` nodes, each holding one model and one local dataset.
+///
+/// Every round, the [`ConsensusOptimizer`] combines neighbour parameters
+/// through the mixing matrix and applies local gradient steps.  Gradients
+/// are computed with each node's [`LossFunction`] on its own data only;
+/// nodes never see each other's samples.
+///
+/// With a [`DynamicTopology`], the mixing matrix is requested anew every
+/// round and nodes marked inactive (dropouts, stragglers) keep their
+/// parameters and their optimizer state (trackers, stored gradients,
+/// duals) from the previous round.
+///
+/// A [`LearningRateSchedule`] sets the optimizer's learning rate before
+/// every round from its initial rate and the number of rounds run so far;
+/// [`InverseTime`](LearningRateSchedule::InverseTime) decay lets DGD reach
+/// the exact optimum instead of a neighbourhood.
+///
+/// With a [`BatchSampler`] every gradient evaluation of node `i` uses the
+/// next mini-batch of its own sampler
+/// [`fork(i)`](BatchSampler::fork) (decentralized SGD), and the reported
+/// losses are mini-batch losses.
+///
+/// Nodes registered with an [`Adversary`] replace their parameters by a
+/// corrupted version after every round, relative to their parameters at
+/// the start of the round, so neighbours receive corrupted values.  Pair
+/// them with a robust [`Aggregator`](crate::optimizers::Aggregator) in
+/// [`Dgd`](crate::optimizers::Dgd).
+#[derive(Debug)]
+pub struct Distributed<B: ComputeBackend> {
+    datasets: Vec<Dataset>,
+    models: Vec<Box<dyn Model<B>>>,
+    losses: Vec<Box<dyn LossFunction<B>>>,
+    optimizer: Box<dyn ConsensusOptimizer>,
+    schedule: Option<LearningRateSchedule>,
+    base_learning_rate: f64,
+    /// Mini-batch sampler of every node, when batching.
+    samplers: Option<Vec<BatchSampler>>,
+    mixing: Vec<Vec<f64>>,
+    dynamic: Option<Box<dyn DynamicTopology>>,
+    adversaries: Vec<(usize, Adversary)>,
+    rng: StdRng,
+    history: Vec<RoundRecord>,
+    converged_at: Option<usize>,
+}
+
+impl<B: ComputeBackend> Distributed<B> {
+    pub fn builder() -> DistributedBuilder<B> {
+        DistributedBuilder::new()
+    }
+
+    /// Run at most `rounds` communication rounds.
+    ///
+    /// Stops early once both the change of the mean loss and the
+    /// disagreement fall below the optimizer's
+    /// [`tolerance`](ConsensusOptimizer::tolerance).
+    pub fn train(&mut self, rounds: usize) -> Result<(), ProcessError> {
+        if self.datasets.iter().any(Dataset::is_empty) {
+            return Err(ProcessError::EmptyDataset);
+        }
+
+        let span = tracing::info_span!(
+            "distributed_train",
+            optimizer = %self.optimizer.id(),
+            nodes = self.models.len(),
+            rounds,
+        );
+        let _guard = span.enter();
+
+        let tensors: Vec<Samples<B>> =
+            self.datasets.iter().map(Samples::from_dataset).collect();
+
+        let mut params: Vec<Vec<f64>> =
+            self.models.iter().map(|m| m.flat_parameters()).collect();
+        for model in &mut self.models {
+            model.set_mode(ModelMode::Training);
+        }
+        self.converged_at = None;
+        let tolerance = self.optimizer.tolerance();
+
+        for round in 0..rounds {
+            let round_span = tracing::debug_span!("round", round);
+            let _round_guard = round_span.enter();
+
+            if let Some(schedule) = &self.schedule {
+                let rate = schedule.rate(self.base_learning_rate, self.history.len());
+                self.optimizer.set_learning_rate(rate);
+            }
+
+            let schedule = self.dynamic.as_mut().map(|d| d.at(round));
+            if let Some(topology) = &schedule {
+                validate_mixing(&topology.mixing, self.models.len()).map_err(|e| {
+                    ProcessError::InvalidConfig {
+                        message: format!("round {round} topology: {e}"),
+                    }
+                })?;
+            }
+            let mixing = schedule.as_ref().map_or(&self.mixing, |t| &t.mixing);
+            let active = schedule
+                .as_ref()
+                .map_or_else(|| vec![true; params.len()], |t| t.active.clone());
+            let previous = (!self.adversaries.is_empty()).then(|| params.clone());
+
+            let losses = {
+                let models = &mut self.models;
+                let loss_fns = &self.losses;
+                let datasets = &self.datasets;
+                let samplers = &mut self.samplers;
+                let mut oracle = |i: usize, x: &[f64]| match samplers.as_mut() {
+                    Some(samplers) => local_gradient(
+                        models[i].as_mut(),
+                        loss_fns[i].as_ref(),
+                        &batch_tensors::<B>(&datasets[i], &mut samplers[i]),
+                        x,
+                    ),
+                    None => local_gradient(
+                        models[i].as_mut(),
+                        loss_fns[i].as_ref(),
+                        &tensors[i],
+                        x,
+                    ),
+                };
+                self.optimizer
+                    .round(&mut params, mixing, &active, &mut oracle)
+            };
+            if let Some(previous) = &previous {
+                for &(i, adversary) in &self.adversaries {
+                    params[i] =
+                        adversary.corrupt(&params[i], &previous[i], &mut self.rng);
+                }
+            }
+
+            let honest: Vec<f64> = losses
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| self.is_honest(i))
+                .map(|(_, &l)| l)
+                .collect();
+            if let Some(&loss) = honest.iter().find(|l| !l.is_finite()) {
+                return Err(ProcessError::NonFiniteLoss { epoch: round, loss });
+            }
+            for (model, x) in self.models.iter_mut().zip(&params) {
+                model.set_flat_parameters(x);
+            }
+
+            let dimension = params.first().map_or(0, Vec::len);
+            let bytes_sent = (0..params.len())
+                .map(|i| {
+                    let peers = (0..mixing.len())
+                        .filter(|&j| j != i && mixing[j][i] > 0.0)
+                        .count();
+                    peers * self.optimizer.message_bytes(i, dimension)
+                })
+                .collect();
+            let record = RoundRecord {
+                round,
+                mean_loss: honest.iter().sum::<f64>() / honest.len() as f64,
+                losses,
+                active: active.iter().filter(|&&a| a).count(),
+                disagreement: self.honest_disagreement(&params),
+                bytes_sent,
+            };
+            tracing::debug!(
+                mean_loss = record.mean_loss,
+                active = record.active,
+                disagreement = record.disagreement,
+            );
+
+            let previous = self.history.last().map(|r| r.mean_loss);
+            let converged = tolerance.is_some_and(|tol| {
+                record.disagreement < tol
+                    && previous.is_some_and(|prev| (prev - record.mean_loss).abs() < tol)
+            });
+            self.history.push(record);
+
+            if converged {
+                tracing::info!(round, "converged");
+                self.converged_at = Some(round);
+                break;
+            }
+        }
+
+        if let Some(last) = self.history.last() {
+            tracing::info!(
+                mean_loss = last.mean_loss,
+                disagreement = last.disagreement,
+                "training finished"
+            );
+        }
+        Ok(())
+    }
+
+    fn is_honest(&self, node: usize) -> bool {
+        self.adversaries.iter().all(|&(a, _)| a != node)
+    }
+
+    fn honest_disagreement(&self, params: &[Vec<f64>]) -> f64 {
+        if self.adversaries.is_empty() {
+            return disagreement(params);
+        }
+        let honest: Vec<Vec<f64>> = params
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| self.is_honest(i))
+            .map(|(_, x)| x.clone())
+            .collect();
+        disagreement(&honest)
+    }
+
+    /// Persist the parameters of node `node`.
+    pub fn save_model(&self, node: usize, path: &str) -> Result<(), B::Error> {
+        self.models[node].save_model(path)
+    }
+
+    pub fn models(&self) -> &[Box<dyn Model<B>>] {
+        &self.models
+    }
+
+    /// Static mixing matrix; empty when only a
+    /// [`dynamic_topology`](DistributedBuilder::dynamic_topology) was given.
+    pub fn mixing_matrix(&self) -> &[Vec<f64>] {
+        &self.mixing
+    }
+
+    /// Report of every round run so far.
+    pub fn history(&self) -> &[RoundRecord] {
+        &self.history
+    }
+
+    /// Round at which the last [`train`](Distributed::train) call stopped early.
+    pub fn converged_at(&self) -> Option<usize> {
+        self.converged_at
+    }
+}
+
+/// Evaluate one node's loss and flat gradient at parameters `x`.
+pub(crate) fn local_gradient<B: ComputeBackend>(
+    model: &mut dyn Model<B>,
+    loss: &dyn LossFunction<B>,
+    samples: &Samples<B>,
+    x: &[f64],
+) -> LocalGradient {
+    model.set_flat_parameters(x);
+    let logits = model.forward(&samples.features);
+    let (weights, bias) = model.parameters_mut();
+    let output = samples.loss_and_gradients(loss, &logits, weights, bias);
+
+    let mut gradient = B::to_vec(&output.weight_grad);
+    gradient.extend(B::to_vec(&output.bias_grad));
+    LocalGradient {
+        loss: output.loss_value,
+        gradient,
+    }
+}
+
+/// Tensors of the next mini-batch `sampler` draws from `dataset`.
+pub(crate) fn batch_tensors<B: ComputeBackend>(
+    dataset: &Dataset,
+    sampler: &mut BatchSampler,
+) -> Samples<B> {
+    Samples::from_dataset(&dataset.subset(&sampler.next_batch(dataset.len())))
+}
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct DistributedBuilder<B: ComputeBackend> {
+    datasets: Option<Vec<Dataset>>,
+    models: Option<Vec<Box<dyn Model<B>>>>,
+    losses: Option<Vec<Box<dyn LossFunction<B>>>>,
+    optimizer: Option<Box<dyn ConsensusOptimizer>>,
+    schedule: Option<LearningRateSchedule>,
+    sampler: Option<BatchSampler>,
+    topology: Option<TopologyConfig>,
+    mixing: Option<Vec<Vec<f64>>>,
+    dynamic: Option<Box<dyn DynamicTopology>>,
+    adversaries: Vec<(usize, Adversary)>,
+    seed: Option<u64>,
+}
+
+impl<B: ComputeBackend> Default for DistributedBuilder<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: ComputeBackend> DistributedBuilder<B> {
+    pub fn new() -> Self {
+        DistributedBuilder {
+            datasets: None,
+            models: None,
+            losses: None,
+            optimizer: None,
+            schedule: None,
+            sampler: None,
+            topology: None,
+            mixing: None,
+            dynamic: None,
+            adversaries: Vec::new(),
+            seed: None,
+        }
+    }
+
+    /// One local dataset per node.
+    pub fn datasets(mut self, datasets: Vec<Dataset>) -> Self {
+        self.datasets = Some(datasets);
+        self
+    }
+
+    /// One model per node.
+    pub fn models<M: Model<B> + 'static>(mut self, models: Vec<M>) -> Self {
+        self.models = Some(
+            models
+                .into_iter()
+                .map(|m| -> Box<dyn Model<B>> { Box::new(m) })
+                .collect(),
+        );
+        self
+    }
+
+    /// One loss function per node.
+    pub fn losses<L: LossFunction<B> + 'static>(mut self, losses: Vec<L>) -> Self {
+        self.losses = Some(
+            losses
+                .into_iter()
+                .map(|l| -> Box<dyn LossFunction<B>> { Box::new(l) })
+                .collect(),
+        );
+        self
+    }
+
+    pub fn optimizer(mut self, optimizer: impl ConsensusOptimizer + 'static) -> Self {
+        self.optimizer = Some(Box::new(optimizer));
+        self
+    }
+
+    /// Learning-rate schedule over rounds, relative to the optimizer's
+    /// initial rate.
+    pub fn schedule(mut self, schedule: LearningRateSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Stochastic gradients on mini-batches; node `i` draws from
+    /// [`sampler.fork(i)`](BatchSampler::fork).
+    pub fn batches(mut self, sampler: BatchSampler) -> Self {
+        self.sampler = Some(sampler);
+        self
+    }
+
+    /// Communication graph; its weights form the mixing matrix.
+    pub fn topology(mut self, topology: TopologyConfig) -> Self {
+        self.topology = Some(topology);
+        self
+    }
+
+    /// Dense mixing matrix (takes precedence over [`topology`](Self::topology)).
+    pub fn mixing_matrix(mut self, mixing: Vec<Vec<f64>>) -> Self {
+        self.mixing = Some(mixing);
+        self
+    }
+
+    /// Per-round mixing matrices, e.g. a
+    /// [`FaultyTopology`](convective_graph::dynamic::FaultyTopology).
+    ///
+    /// Replaces the static topology during training; a static one is then
+    /// optional.
+    pub fn dynamic_topology(mut self, topology: impl DynamicTopology + 'static) -> Self {
+        self.dynamic = Some(Box::new(topology));
+        self
+    }
+
+    /// Make node `node` Byzantine.
+    pub fn adversary(mut self, node: usize, adversary: Adversary) -> Self {
+        self.adversaries.retain(|(a, _)| *a != node);
+        self.adversaries.push((node, adversary));
+        self
+    }
+
+    /// Seed of random adversaries.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Result<Distributed<B>, &'static str> {
+        let datasets = self.datasets.ok_or("Missing datasets")?;
+        let models = self.models.ok_or("Missing models")?;
+        let losses = self.losses.ok_or("Missing losses")?;
+        let optimizer = self.optimizer.ok_or("Missing optimizer")?;
+        let mixing = match (self.mixing, self.topology) {
+            (Some(mixing), _) => mixing,
+            (None, Some(topology)) => topology
+                .mixing_matrix()
+                .map_err(|_| "Topology vertex out of range")?,
+            (None, None) if self.dynamic.is_some() => Vec::new(),
+            (None, None) => return Err("Missing topology"),
+        };
+
+        let n = models.len();
+        if n == 0 {
+            return Err("At least one node is required");
+        }
+        if datasets.len() != n || losses.len() != n {
+            return Err("Datasets, models and losses must have one entry per node");
+        }
+        let p = models[0].flat_parameters().len();
+        if models.iter().any(|m| m.flat_parameters().len() != p) {
+            return Err("All models must have the same number of parameters");
+        }
+        if !mixing.is_empty() {
+            validate_mixing(&mixing, n)?;
+        }
+        if self.dynamic.as_ref().is_some_and(|d| d.node_count() != n) {
+            return Err("Dynamic topology must have one node per model");
+        }
+        if self.adversaries.iter().any(|(a, _)| *a >= n) {
+            return Err("Adversary index out of range");
+        }
+        if self.adversaries.len() >= n {
+            return Err("At least one node must be honest");
+        }
+        if let Some(schedule) = &self.schedule {
+            schedule.validate()?;
+        }
+        let rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+
+        Ok(Distributed {
+            datasets,
+            models,
+            losses,
+            base_learning_rate: optimizer.learning_rate(),
+            optimizer,
+            schedule: self.schedule,
+            samplers: self
+                .sampler
+                .map(|s| (0..n).map(|i| s.fork(i as u64)).collect()),
+            mixing,
+            dynamic: self.dynamic,
+            adversaries: self.adversaries,
+            rng,
+            history: Vec::new(),
+            converged_at: None,
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ProcessError {
+    #[error("Empty dataset")]
+    EmptyDataset,
+
+    #[error("Non-finite loss {loss} at epoch {epoch}")]
+    NonFiniteLoss { epoch: usize, loss: f64 },
+
+    #[error("Solver failed at epoch {epoch}: {message}")]
+    SolverFailed { epoch: usize, message: &'static str },
+
+    #[error("Invalid configuration: {message}")]
+    InvalidConfig { message: String },
+
+    #[error("Node {node}: {source}")]
+    Transport {
+        node: usize,
+        #[source]
+        source: TransportError,
+    },
+
+    #[error("Node {node} stopped because peer {peer} failed")]
+    Aborted { node: usize, peer: usize },
+
+    #[error("Node {node} panicked")]
+    NodePanicked { node: usize },
+}
+
+#[derive(Error, Debug)]
+pub enum TransportError {
+    #[error("Unknown peer {0}")]
+    UnknownPeer(usize),
+
+    #[error("Peer {0} disconnected")]
+    Disconnected(usize),
+
+    #[error("Inbox closed")]
+    Closed,
+
+    #[error("Timed out waiting for peers")]
+    Timeout,
+}
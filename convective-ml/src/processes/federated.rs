@@ -0,0 +1,574 @@
+//! Federated averaging (FedAvg / FedProx) over a star topology.
+
+use super::{distributed::local_gradient, errors::ProcessError, singular::check_batches};
+use crate::{
+    functions::{LossFunction, Samples},
+    models::{ComputeBackend, Model, ModelMode},
+    optimizers::{
+        LearningRateSchedule, Optimizer,
+        aggregation::{Adversary, Aggregator},
+        compression::{Compressor, ErrorFeedback, dense_bytes},
+    },
+};
+use convective_data::datasets::{BatchSampler, Dataset};
+use rand::{SeedableRng, rngs::StdRng};
+use serde::Serialize;
+
+/// Per-round federated training report.
+#[derive(Debug, Clone, Serialize)]
+pub struct FederatedRound {
+    pub round: usize,
+    /// Clients sampled this round.
+    pub participants: Vec<usize>,
+    /// Loss of the global model on each participant's data, measured
+    /// before local training.
+    pub losses: Vec<f64>,
+    /// Mean of `losses` weighted by the participants' sample counts.
+    pub mean_loss: f64,
+    /// Bytes each participant uploaded to the server.
+    pub bytes_sent: Vec<usize>,
+}
+
+// ---------------------------------------------------------------------------
+// Federated
+// ---------------------------------------------------------------------------
+
+/// Star-topology coordinator: a server holds the global parameters and
+/// clients train on their private datasets.
+///
+/// Each round the server
+///
+/// 1. samples a fraction of the clients,
+/// 2. sends them the global parameters,
+/// 3. lets each run `local_epochs` epochs of the local [`Optimizer`] on
+///    its own data (one full-batch step per epoch, or one step per
+///    mini-batch with a [`BatchSampler`]), starting from a reset optimizer
+///    state,
+/// 4. replaces the global parameters by the sample-count weighted average
+///    `Σ_k n_k x_k / Σ_k n_k` of the returned client parameters.
+///
+/// Step 4 can be swapped for a Byzantine-robust [`Aggregator`]; the
+/// sample counts are passed as weights.  Clients registered with an
+/// [`Adversary`] corrupt the parameters they return, relative to the global
+/// parameters they received.
+///
+/// With a lossy [`Compressor`] clients upload the compressed difference
+/// `C(x_k − x_global)`, optionally with per-client error feedback, and the
+/// server aggregates `x_global + C(x_k − x_global)`.  Downloads of the
+/// global parameters are not compressed.
+///
+/// A [`LearningRateSchedule`] sets the clients' learning rate at the start
+/// of every round from the optimizer's initial rate and the round count, so
+/// all local steps of a round share one rate.
+///
+/// With `proximal = μ > 0` the local objective becomes
+/// `f_k(x) + μ/2 ‖x − x_global‖²` (FedProx), which limits client drift on
+/// heterogeneous data.
+///
+/// Clients are simulated sequentially with a single model instance whose
+/// parameters are swapped in and out through
+/// [`Model::flat_parameters`], so the coordinator works with every
+/// backend.  After training the model holds the global parameters.
+#[derive(Debug)]
+pub struct Federated<B: ComputeBackend> {
+    datasets: Vec<Dataset>,
+    model: Box<dyn Model<B>>,
+    loss: Box<dyn LossFunction<B>>,
+    optimizer: Box<dyn Optimizer<B>>,
+    schedule: Option<LearningRateSchedule>,
+    base_learning_rate: f64,
+    local_epochs: usize,
+    /// Mini-batch sampler of every client, when batching.
+    samplers: Option<Vec<BatchSampler>>,
+    participation: f64,
+    proximal: f64,
+    tolerance: Option<f64>,
+    aggregator: Aggregator,
+    adversaries: Vec<(usize, Adversary)>,
+    compressor: Compressor,
+    /// Error-feedback memory of every client, when enabled.
+    feedback: Option<Vec<ErrorFeedback>>,
+    rng: StdRng,
+    history: Vec<FederatedRound>,
+    converged_at: Option<usize>,
+}
+
+impl<B: ComputeBackend> Federated<B> {
+    pub fn builder() -> FederatedBuilder<B> {
+        FederatedBuilder::new()
+    }
+
+    /// Run at most `rounds` federated rounds.
+    pub fn train(&mut self, rounds: usize) -> Result<(), ProcessError> {
+        if self.datasets.iter().any(Dataset::is_empty) {
+            return Err(ProcessError::EmptyDataset);
+        }
+        if let Some(samplers) = &self.samplers {
+            for (sampler, dataset) in samplers.iter().zip(&self.datasets) {
+                check_batches(sampler, dataset.len())?;
+            }
+        }
+
+        let span = tracing::info_span!(
+            "federated_train",
+            model_id = %self.model.id(),
+            clients = self.datasets.len(),
+            rounds,
+            proximal = self.proximal,
+        );
+        let _guard = span.enter();
+
+        let tensors: Vec<Samples<B>> =
+            self.datasets.iter().map(Samples::from_dataset).collect();
+
+        self.model.set_mode(ModelMode::Training);
+        self.converged_at = None;
+
+        for round in 0..rounds {
+            let round_span = tracing::debug_span!("round", round);
+            let _round_guard = round_span.enter();
+
+            if let Some(schedule) = &self.schedule {
+                let rate = schedule.rate(self.base_learning_rate, self.history.len());
+                self.optimizer.set_learning_rate(rate);
+            }
+            let global = self.model.flat_parameters();
+            let participants = self.sample_clients();
+
+            let mut losses = Vec::with_capacity(participants.len());
+            let mut updates = Vec::with_capacity(participants.len());
+            let mut bytes_sent = Vec::with_capacity(participants.len());
+            let sizes: Vec<f64> = participants
+                .iter()
+                .map(|&k| self.datasets[k].len() as f64)
+                .collect();
+            let total: f64 = sizes.iter().sum();
+
+            for &k in &participants {
+                let loss = self.local_update(k, &tensors[k], &global)?;
+                losses.push(loss);
+
+                let mut update = self.model.flat_parameters();
+                if let Some((_, adversary)) =
+                    self.adversaries.iter().find(|(c, _)| *c == k)
+                {
+                    update = adversary.corrupt(&update, &global, &mut self.rng);
+                }
+                let (update, bytes) = self.upload(k, update, &global);
+                updates.push(update);
+                bytes_sent.push(bytes);
+                tracing::trace!(client = k, loss, "local update");
+            }
+            let views: Vec<&[f64]> = updates.iter().map(Vec::as_slice).collect();
+            let aggregate = self.aggregator.aggregate(&views, &sizes);
+            self.model.set_flat_parameters(&aggregate);
+
+            let mean_loss = sizes
+                .iter()
+                .zip(&losses)
+                .map(|(n, l)| n / total * l)
+                .sum::<f64>();
+            tracing::debug!(mean_loss, participants = participants.len());
+
+            let previous = self.history.last().map(|r| r.mean_loss);
+            self.history.push(FederatedRound {
+                round,
+                participants,
+                losses,
+                mean_loss,
+                bytes_sent,
+            });
+
+            let converged = self.tolerance.is_some_and(|tol| {
+                previous.is_some_and(|prev| (prev - mean_loss).abs() < tol)
+            });
+            if converged {
+                tracing::info!(round, "converged");
+                self.converged_at = Some(round);
+                break;
+            }
+        }
+
+        if let Some(last) = self.history.last() {
+            tracing::info!(mean_loss = last.mean_loss, "training finished");
+        }
+        Ok(())
+    }
+
+    /// Train the shared model on client `k` starting from `global`.
+    ///
+    /// Returns the loss of the global parameters on the client's whole
+    /// dataset.
+    fn local_update(
+        &mut self,
+        k: usize,
+        samples: &Samples<B>,
+        global: &[f64],
+    ) -> Result<f64, ProcessError> {
+        self.model.set_flat_parameters(global);
+        // Optimizer state belongs to one client's trajectory.
+        self.optimizer.reset();
+        let anchor = {
+            let (weights, bias) = self.model.parameters_mut();
+            (B::to_vec(weights), B::to_vec(bias))
+        };
+        // The first full-batch step sees the global parameters on the whole
+        // dataset; no mini-batch step does, so evaluate them up front.
+        let mut initial_loss = match self.samplers {
+            Some(_) => {
+                let loss = local_gradient(
+                    self.model.as_mut(),
+                    self.loss.as_ref(),
+                    samples,
+                    global,
+                )
+                .loss;
+                if !loss.is_finite() {
+                    return Err(ProcessError::NonFiniteLoss { epoch: 0, loss });
+                }
+                loss
+            }
+            None => f64::NAN,
+        };
+
+        for epoch in 0..self.local_epochs {
+            match &mut self.samplers {
+                None => {
+                    let loss = self.local_step(samples, &anchor, epoch)?;
+                    if epoch == 0 {
+                        initial_loss = loss;
+                    }
+                }
+                Some(samplers) => {
+                    let batches: Vec<Dataset> =
+                        samplers[k].batches(&self.datasets[k]).collect();
+                    for batch in &batches {
+                        let samples = Samples::from_dataset(batch);
+                        self.local_step(&samples, &anchor, epoch)?;
+                    }
+                }
+            }
+        }
+        Ok(initial_loss)
+    }
+
+    /// One local optimizer step; returns the loss before the step.
+    ///
+    /// `anchor` holds the global weights and bias the proximal term pulls
+    /// towards, each flattened like the model's own tensors.
+    fn local_step(
+        &mut self,
+        samples: &Samples<B>,
+        anchor: &(Vec<f64>, Vec<f64>),
+        epoch: usize,
+    ) -> Result<f64, ProcessError> {
+        let logits = self.model.forward(&samples.features);
+        let (weights, bias) = self.model.parameters_mut();
+        let output =
+            samples.loss_and_gradients(self.loss.as_ref(), &logits, weights, bias);
+
+        if !output.loss_value.is_finite() {
+            return Err(ProcessError::NonFiniteLoss {
+                epoch,
+                loss: output.loss_value,
+            });
+        }
+
+        if self.proximal > 0.0 {
+            // ∇ μ/2 ‖x − x_global‖² = μ (x − x_global)
+            let (weight_grad, bias_grad) = (
+                proximal_gradient::<B>(
+                    &output.weight_grad,
+                    weights,
+                    &anchor.0,
+                    self.proximal,
+                ),
+                proximal_gradient::<B>(&output.bias_grad, bias, &anchor.1, self.proximal),
+            );
+            self.optimizer.step(weights, bias, &weight_grad, &bias_grad);
+        } else {
+            self.optimizer
+                .step(weights, bias, &output.weight_grad, &output.bias_grad);
+        }
+        Ok(output.loss_value)
+    }
+
+    /// Parameters of client `k` as received by the server, and the upload
+    /// size.
+    fn upload(
+        &mut self,
+        k: usize,
+        update: Vec<f64>,
+        global: &[f64],
+    ) -> (Vec<f64>, usize) {
+        if self.compressor == Compressor::Identity {
+            let bytes = dense_bytes(update.len());
+            return (update, bytes);
+        }
+        let difference: Vec<f64> =
+            update.iter().zip(global).map(|(x, g)| x - g).collect();
+        let message = match &mut self.feedback {
+            Some(feedback) => {
+                feedback[k].compress(&self.compressor, &difference, &mut self.rng)
+            }
+            None => self.compressor.compress(&difference, &mut self.rng),
+        };
+        let mut received = global.to_vec();
+        message.add_to(&mut received);
+        (received, message.bytes())
+    }
+
+    /// Sample `⌈participation · K⌉` distinct clients (at least one).
+    fn sample_clients(&mut self) -> Vec<usize> {
+        let n = self.datasets.len();
+        let k = ((self.participation * n as f64).ceil() as usize).clamp(1, n);
+        if k == n {
+            return (0..n).collect();
+        }
+        let mut sampled = rand::seq::index::sample(&mut self.rng, n, k).into_vec();
+        sampled.sort_unstable();
+        sampled
+    }
+
+    /// Persist the global model.
+    pub fn save_model(&self, path: &str) -> Result<(), B::Error> {
+        self.model.save_model(path)
+    }
+
+    /// Model holding the global parameters.
+    pub fn model(&self) -> &dyn Model<B> {
+        self.model.as_ref()
+    }
+
+    /// Report of every round run so far.
+    pub fn history(&self) -> &[FederatedRound] {
+        &self.history
+    }
+
+    /// Round at which the last [`train`](Federated::train) call stopped early.
+    pub fn converged_at(&self) -> Option<usize> {
+        self.converged_at
+    }
+}
+
+/// `grad + μ (param − anchor)`, shaped like `grad`.
+fn proximal_gradient<B: ComputeBackend>(
+    grad: &B::Tensor,
+    param: &B::Tensor,
+    anchor: &[f64],
+    mu: f64,
+) -> B::Tensor {
+    let adjusted: Vec<f64> = B::to_vec(grad)
+        .iter()
+        .zip(B::to_vec(param))
+        .zip(anchor)
+        .map(|((g, x), a)| g + mu * (x - a))
+        .collect();
+    B::from_vec_like(&adjusted, grad)
+}
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct FederatedBuilder<B: ComputeBackend> {
+    datasets: Option<Vec<Dataset>>,
+    model: Option<Box<dyn Model<B>>>,
+    loss: Option<Box<dyn LossFunction<B>>>,
+    optimizer: Option<Box<dyn Optimizer<B>>>,
+    schedule: Option<LearningRateSchedule>,
+    local_epochs: usize,
+    sampler: Option<BatchSampler>,
+    participation: f64,
+    proximal: f64,
+    tolerance: Option<f64>,
+    aggregator: Aggregator,
+    adversaries: Vec<(usize, Adversary)>,
+    compressor: Compressor,
+    error_feedback: bool,
+    seed: Option<u64>,
+}
+
+impl<B: ComputeBackend> Default for FederatedBuilder<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: ComputeBackend> FederatedBuilder<B> {
+    pub fn new() -> Self {
+        FederatedBuilder {
+            datasets: None,
+            model: None,
+            loss: None,
+            optimizer: None,
+            schedule: None,
+            local_epochs: 1,
+            sampler: None,
+            participation: 1.0,
+            proximal: 0.0,
+            tolerance: None,
+            aggregator: Aggregator::WeightedMean,
+            adversaries: Vec::new(),
+            compressor: Compressor::Identity,
+            error_feedback: false,
+            seed: None,
+        }
+    }
+
+    /// One private dataset per client.
+    pub fn datasets(mut self, datasets: Vec<Dataset>) -> Self {
+        self.datasets = Some(datasets);
+        self
+    }
+
+    /// Global model; its initial parameters seed the first round.
+    pub fn model(mut self, model: impl Model<B> + 'static) -> Self {
+        self.model = Some(Box::new(model));
+        self
+    }
+
+    pub fn loss(mut self, loss: impl LossFunction<B> + 'static) -> Self {
+        self.loss = Some(Box::new(loss));
+        self
+    }
+
+    /// Optimizer used by clients for their local steps.
+    pub fn optimizer(mut self, optimizer: impl Optimizer<B> + 'static) -> Self {
+        self.optimizer = Some(Box::new(optimizer));
+        self
+    }
+
+    /// Learning-rate schedule over rounds, relative to the optimizer's
+    /// initial rate.
+    pub fn schedule(mut self, schedule: LearningRateSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Local epochs per client and round (default 1).
+    pub fn local_epochs(mut self, epochs: usize) -> Self {
+        self.local_epochs = epochs;
+        self
+    }
+
+    /// Local mini-batches; client `k` draws from
+    /// [`sampler.fork(k)`](BatchSampler::fork).
+    pub fn batches(mut self, sampler: BatchSampler) -> Self {
+        self.sampler = Some(sampler);
+        self
+    }
+
+    /// Fraction of clients sampled each round, in `(0, 1]` (default 1).
+    pub fn participation(mut self, fraction: f64) -> Self {
+        self.participation = fraction;
+        self
+    }
+
+    /// FedProx coefficient `μ` (default 0, plain FedAvg).
+    pub fn proximal(mut self, mu: f64) -> Self {
+        self.proximal = mu;
+        self
+    }
+
+    /// Early-stopping tolerance on the round-to-round mean-loss change.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = Some(tolerance);
+        self
+    }
+
+    /// Server-side aggregation rule (default: sample-count weighted mean).
+    pub fn aggregator(mut self, aggregator: Aggregator) -> Self {
+        self.aggregator = aggregator;
+        self
+    }
+
+    /// Make client `client` Byzantine.
+    pub fn adversary(mut self, client: usize, adversary: Adversary) -> Self {
+        self.adversaries.retain(|(c, _)| *c != client);
+        self.adversaries.push((client, adversary));
+        self
+    }
+
+    /// Compression of client uploads (default: none).
+    pub fn compressor(mut self, compressor: Compressor) -> Self {
+        self.compressor = compressor;
+        self
+    }
+
+    /// Keep each client's compression residual for its next upload.
+    pub fn error_feedback(mut self, enabled: bool) -> Self {
+        self.error_feedback = enabled;
+        self
+    }
+
+    /// Seed of the client sampler, random adversaries and randomized
+    /// compressors.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Result<Federated<B>, &'static str> {
+        let datasets = self.datasets.ok_or("Missing datasets")?;
+        let model = self.model.ok_or("Missing model")?;
+        let loss = self.loss.ok_or("Missing loss")?;
+        let optimizer = self.optimizer.ok_or("Missing optimizer")?;
+
+        if datasets.is_empty() {
+            return Err("At least one client is required");
+        }
+        if self.local_epochs == 0 {
+            return Err("local_epochs must be positive");
+        }
+        if !(self.participation > 0.0 && self.participation <= 1.0) {
+            return Err("participation must be in (0, 1]");
+        }
+        if self.proximal.is_nan() || self.proximal < 0.0 {
+            return Err("proximal must be non-negative");
+        }
+        if self.tolerance.is_some_and(|tol| tol.is_nan() || tol < 0.0) {
+            return Err("Tolerance must be non-negative");
+        }
+        if let Some(schedule) = &self.schedule {
+            schedule.validate()?;
+        }
+
+        if self.adversaries.iter().any(|(c, _)| *c >= datasets.len()) {
+            return Err("Adversary index out of range");
+        }
+        self.compressor.validate()?;
+        let clients = datasets.len();
+
+        let rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+
+        Ok(Federated {
+            datasets,
+            model,
+            loss,
+            base_learning_rate: optimizer.learning_rate(),
+            optimizer,
+            schedule: self.schedule,
+            local_epochs: self.local_epochs,
+            samplers: self
+                .sampler
+                .map(|s| (0..clients).map(|k| s.fork(k as u64)).collect()),
+            participation: self.participation,
+            proximal: self.proximal,
+            tolerance: self.tolerance,
+            aggregator: self.aggregator,
+            adversaries: self.adversaries,
+            compressor: self.compressor,
+            feedback: self
+                .error_feedback
+                .then(|| vec![ErrorFeedback::default(); clients]),
+            rng,
+            history: Vec::new(),
+            converged_at: None,
+        })
+    }
+}
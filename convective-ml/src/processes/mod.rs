@@ -0,0 +1,35 @@
+//! Training processes.
+//!
+//! A process owns a dataset together with the
+//! [`Model`](crate::models::Model) /
+//! [`LossFunction`](crate::functions::LossFunction) /
+//! [`Optimizer`](crate::optimizers::Optimizer) triad and drives the
+//! forward → loss/gradient → update loop.  Every component is held as a
+//! boxed trait object, so the same process works with any backend and any
+//! combination of concrete components.
+//!
+//! - [`Singular`](crate::processes::Singular): one model trained on one
+//!   dataset.
+//! - [`Distributed`](crate::processes::Distributed): one model per node,
+//!   coupled through a consensus optimizer over a weighted communication
+//!   topology.
+//! - [`Federated`](crate::processes::Federated): clients train locally and
+//!   a server averages their parameters (FedAvg / FedProx) over a star
+//!   topology.
+//! - [`NodeRuntime`](crate::processes::runtime::NodeRuntime): one thread
+//!   per node, exchanging parameters over a pluggable
+//!   [`Transport`](crate::processes::runtime::Transport).
+
+pub mod distributed;
+pub mod errors;
+pub mod federated;
+pub mod runtime;
+pub mod singular;
+
+pub use distributed::{Distributed, DistributedBuilder, RoundRecord};
+pub use errors::{ProcessError, TransportError};
+pub use federated::{Federated, FederatedBuilder, FederatedRound};
+pub use runtime::{NodeRuntime, NodeRuntimeBuilder};
+pub use singular::{Singular, SingularBuilder};
+
+pub use crate::optimizers::consensus::UpdateStrategy;
@@ -0,0 +1,364 @@
+//! Thread-per-node runtime for decentralized training.
+//!
+//! Unlike [`Distributed`](super::Distributed), which steps every node from
+//! a single loop, [`NodeRuntime`] runs each [`Node`] on its own OS thread.
+//! Nodes only exchange flat parameter vectors through a [`Transport`], so
+//! replacing [`ChannelTransport`] by a socket-based implementation lets the
+//! same [`Node::run`] loop execute in separate processes.
+
+pub mod node;
+pub mod transport;
+
+pub use node::{Node, NodeReport};
+pub use transport::{ChannelTransport, Message, Transport};
+
+use super::{distributed::RoundRecord, errors::ProcessError};
+use crate::{
+    functions::LossFunction,
+    models::{ComputeBackend, Model},
+    optimizers::{
+        LearningRateSchedule,
+        consensus::{Dgd, disagreement, validate_mixing},
+    },
+};
+use convective_data::datasets::{BatchSampler, Dataset, types::topology::TopologyConfig};
+use std::{collections::HashMap, sync::mpsc, thread, time::Duration};
+
+/// Runs one [`Node`] per thread and collects their round reports.
+///
+/// Every node performs decentralized gradient descent with the
+/// [`Dgd`] step size and [`UpdateStrategy`](super::UpdateStrategy);
+/// the result matches [`Distributed`](super::Distributed) with the same
+/// optimizer, up to floating-point summation order.  Nodes advance
+/// without a global barrier, so a run always executes the requested
+/// number of rounds (no early stopping).
+#[derive(Debug)]
+pub struct NodeRuntime<B: ComputeBackend> {
+    nodes: Vec<Node<B>>,
+    transports: Vec<Box<dyn Transport>>,
+    mixing: Vec<Vec<f64>>,
+    history: Vec<RoundRecord>,
+}
+
+impl<B: ComputeBackend> NodeRuntime<B> {
+    pub fn builder() -> NodeRuntimeBuilder<B> {
+        NodeRuntimeBuilder::new()
+    }
+
+    /// Run `rounds` communication rounds, one thread per node.
+    ///
+    /// If a node fails or panics, its peers stop as well and the first
+    /// root-cause error is returned; rounds completed by every node stay in
+    /// [`history`](Self::history).
+    pub fn train(&mut self, rounds: usize) -> Result<(), ProcessError> {
+        let span = tracing::info_span!("runtime_train", nodes = self.nodes.len(), rounds);
+        let _guard = span.enter();
+
+        let n = self.nodes.len();
+        let start = self.history.len();
+        let Self {
+            nodes,
+            transports,
+            history,
+            ..
+        } = self;
+
+        let results: Vec<Result<(), ProcessError>> = thread::scope(|scope| {
+            let (tx, rx) = mpsc::channel::<NodeReport>();
+            let handles: Vec<_> = nodes
+                .iter_mut()
+                .zip(transports.iter_mut())
+                .map(|(node, transport)| {
+                    let tx = tx.clone();
+                    let span = tracing::Span::current();
+                    scope.spawn(move || {
+                        let _guard = span.enter();
+                        node.run(transport.as_mut(), start..start + rounds, &mut |r| {
+                            // The collector outlives every node thread.
+                            let _ = tx.send(r);
+                        })
+                    })
+                })
+                .collect();
+            drop(tx);
+
+            // Assemble a record once every node has reported a round.
+            let mut pending: HashMap<usize, Vec<Option<NodeReport>>> = HashMap::new();
+            for report in rx {
+                let round = report.round;
+                let slot = pending.entry(round).or_insert_with(|| vec![None; n]);
+                let node = report.node;
+                slot[node] = Some(report);
+                if slot.iter().all(Option::is_some) {
+                    let reports: Vec<NodeReport> = pending
+                        .remove(&round)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .flatten()
+                        .collect();
+                    let record = round_record(round, reports);
+                    tracing::debug!(
+                        round,
+                        mean_loss = record.mean_loss,
+                        disagreement = record.disagreement,
+                    );
+                    history.push(record);
+                }
+            }
+
+            handles
+                .into_iter()
+                .enumerate()
+                .map(|(node, handle)| {
+                    handle
+                        .join()
+                        .unwrap_or(Err(ProcessError::NodePanicked { node }))
+                })
+                .collect()
+        });
+
+        // Peers of a failed node report `Aborted`; surface the root cause.
+        let mut root: Option<ProcessError> = None;
+        for error in results.into_iter().filter_map(Result::err) {
+            if root
+                .as_ref()
+                .is_none_or(|r| matches!(r, ProcessError::Aborted { .. }))
+            {
+                root = Some(error);
+            }
+        }
+        if let Some(error) = root {
+            tracing::error!(%error, "runtime stopped");
+            return Err(error);
+        }
+
+        if let Some(last) = self.history.last() {
+            tracing::info!(
+                mean_loss = last.mean_loss,
+                disagreement = last.disagreement,
+                "training finished"
+            );
+        }
+        Ok(())
+    }
+
+    /// Persist the parameters of node `node`.
+    pub fn save_model(&self, node: usize, path: &str) -> Result<(), B::Error> {
+        self.nodes[node].model().save_model(path)
+    }
+
+    pub fn nodes(&self) -> &[Node<B>] {
+        &self.nodes
+    }
+
+    pub fn mixing_matrix(&self) -> &[Vec<f64>] {
+        &self.mixing
+    }
+
+    /// Report of every round completed by all nodes so far.
+    pub fn history(&self) -> &[RoundRecord] {
+        &self.history
+    }
+}
+
+fn round_record(round: usize, reports: Vec<NodeReport>) -> RoundRecord {
+    let losses: Vec<f64> = reports.iter().map(|r| r.loss).collect();
+    let bytes_sent = reports.iter().map(|r| r.bytes_sent).collect();
+    let params: Vec<Vec<f64>> = reports.into_iter().map(|r| r.parameters).collect();
+    RoundRecord {
+        round,
+        mean_loss: losses.iter().sum::<f64>() / losses.len() as f64,
+        active: losses.len(),
+        losses,
+        disagreement: disagreement(&params),
+        bytes_sent,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct NodeRuntimeBuilder<B: ComputeBackend> {
+    datasets: Option<Vec<Dataset>>,
+    models: Option<Vec<Box<dyn Model<B>>>>,
+    losses: Option<Vec<Box<dyn LossFunction<B>>>>,
+    optimizer: Option<Dgd>,
+    schedule: Option<LearningRateSchedule>,
+    sampler: Option<BatchSampler>,
+    topology: Option<TopologyConfig>,
+    mixing: Option<Vec<Vec<f64>>>,
+    transports: Option<Vec<Box<dyn Transport>>>,
+    timeout: Option<Duration>,
+}
+
+impl<B: ComputeBackend> Default for NodeRuntimeBuilder<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: ComputeBackend> NodeRuntimeBuilder<B> {
+    pub fn new() -> Self {
+        NodeRuntimeBuilder {
+            datasets: None,
+            models: None,
+            losses: None,
+            optimizer: None,
+            schedule: None,
+            sampler: None,
+            topology: None,
+            mixing: None,
+            transports: None,
+            timeout: None,
+        }
+    }
+
+    /// One local dataset per node.
+    pub fn datasets(mut self, datasets: Vec<Dataset>) -> Self {
+        self.datasets = Some(datasets);
+        self
+    }
+
+    /// One model per node.
+    pub fn models<M: Model<B> + 'static>(mut self, models: Vec<M>) -> Self {
+        self.models = Some(
+            models
+                .into_iter()
+                .map(|m| -> Box<dyn Model<B>> { Box::new(m) })
+                .collect(),
+        );
+        self
+    }
+
+    /// One loss function per node.
+    pub fn losses<L: LossFunction<B> + 'static>(mut self, losses: Vec<L>) -> Self {
+        self.losses = Some(
+            losses
+                .into_iter()
+                .map(|l| -> Box<dyn LossFunction<B>> { Box::new(l) })
+                .collect(),
+        );
+        self
+    }
+
+    /// Step size and update strategy shared by every node.
+    pub fn optimizer(mut self, optimizer: Dgd) -> Self {
+        self.optimizer = Some(optimizer);
+        self
+    }
+
+    /// Learning-rate schedule over rounds, as in
+    /// [`Distributed`](super::Distributed).
+    pub fn schedule(mut self, schedule: LearningRateSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Stochastic gradients on mini-batches, as in
+    /// [`Distributed`](super::Distributed).
+    pub fn batches(mut self, sampler: BatchSampler) -> Self {
+        self.sampler = Some(sampler);
+        self
+    }
+
+    /// Communication graph; its weights form the mixing matrix.
+    pub fn topology(mut self, topology: TopologyConfig) -> Self {
+        self.topology = Some(topology);
+        self
+    }
+
+    /// Dense mixing matrix (takes precedence over [`topology`](Self::topology)).
+    pub fn mixing_matrix(mut self, mixing: Vec<Vec<f64>>) -> Self {
+        self.mixing = Some(mixing);
+        self
+    }
+
+    /// One endpoint per node, entry `i` owned by node `i`.
+    ///
+    /// Defaults to a [`ChannelTransport::mesh`].
+    pub fn transports<T: Transport + 'static>(mut self, transports: Vec<T>) -> Self {
+        self.transports = Some(
+            transports
+                .into_iter()
+                .map(|t| -> Box<dyn Transport> { Box::new(t) })
+                .collect(),
+        );
+        self
+    }
+
+    /// Receive timeout of the default channel transport.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn build(self) -> Result<NodeRuntime<B>, &'static str> {
+        let datasets = self.datasets.ok_or("Missing datasets")?;
+        let models = self.models.ok_or("Missing models")?;
+        let losses = self.losses.ok_or("Missing losses")?;
+        let optimizer = self.optimizer.ok_or("Missing optimizer")?;
+        let mixing = match (self.mixing, self.topology) {
+            (Some(mixing), _) => mixing,
+            (None, Some(topology)) => topology
+                .mixing_matrix()
+                .map_err(|_| "Topology vertex out of range")?,
+            (None, None) => return Err("Missing topology"),
+        };
+
+        let n = models.len();
+        if n == 0 {
+            return Err("At least one node is required");
+        }
+        if datasets.len() != n || losses.len() != n {
+            return Err("Datasets, models and losses must have one entry per node");
+        }
+        let p = models[0].flat_parameters().len();
+        if models.iter().any(|m| m.flat_parameters().len() != p) {
+            return Err("All models must have the same number of parameters");
+        }
+        validate_mixing(&mixing, n)?;
+        if let Some(schedule) = &self.schedule {
+            schedule.validate()?;
+        }
+
+        let transports = match self.transports {
+            Some(transports) => transports,
+            None => ChannelTransport::mesh(n, self.timeout)
+                .into_iter()
+                .map(|t| -> Box<dyn Transport> { Box::new(t) })
+                .collect(),
+        };
+        if transports.len() != n {
+            return Err("Transports must have one entry per node");
+        }
+        if transports.iter().enumerate().any(|(i, t)| t.node() != i) {
+            return Err("Transport i must belong to node i");
+        }
+
+        let nodes = datasets
+            .into_iter()
+            .zip(models)
+            .zip(losses)
+            .enumerate()
+            .map(|(i, ((dataset, model), loss))| {
+                let mut node = Node::new(i, &mixing, dataset, model, loss, &optimizer);
+                if let Some(schedule) = &self.schedule {
+                    node = node.schedule(schedule.clone());
+                }
+                if let Some(sampler) = &self.sampler {
+                    node = node.batches(sampler.fork(i as u64));
+                }
+                node
+            })
+            .collect();
+
+        Ok(NodeRuntime {
+            nodes,
+            transports,
+            mixing,
+            history: Vec::new(),
+        })
+    }
+}
@@ -0,0 +1,331 @@
+//! A single participant of a decentralized run.
+
+use super::transport::{Message, Transport};
+use crate::{
+    functions::{LossFunction, Samples},
+    models::{ComputeBackend, Model, ModelMode},
+    optimizers::{
+        LearningRateSchedule,
+        aggregation::Aggregator,
+        compression::{Compressed, Compressor, Encoder},
+        consensus::{Dgd, UpdateStrategy},
+    },
+    processes::{
+        distributed::{batch_tensors, local_gradient},
+        errors::{ProcessError, TransportError},
+    },
+};
+use convective_data::datasets::{BatchSampler, Dataset};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// State of one node at the end of a round.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeReport {
+    pub node: usize,
+    pub round: usize,
+    /// Local loss at the start of the round.
+    pub loss: f64,
+    /// Flat parameters after the round.
+    pub parameters: Vec<f64>,
+    /// Bytes sent to the node's out-neighbours during the round.
+    pub bytes_sent: usize,
+}
+
+/// One node of a decentralized gradient descent run.
+///
+/// A node owns its model, loss and private dataset, and only knows its
+/// row and column of the mixing matrix.  Every round it
+///
+/// 1. evaluates its local gradient,
+/// 2. sends its parameters (or, with
+///    [`AdaptThenCombine`](UpdateStrategy::AdaptThenCombine), its adapted
+///    parameters) to every node `j` with `W_ji > 0`,
+/// 3. waits for the messages of every `j` with `W_ij > 0` and mixes them.
+///
+/// With a lossy [`Dgd::compressor`] the node sends compressed differences
+/// through an [`Encoder`] and mixes its own public estimates of its
+/// neighbours' vectors, exactly as [`Dgd`] does.
+///
+/// Messages for later rounds from faster neighbours are buffered, so nodes
+/// stay in lock-step without a global barrier.  On any error or panic the
+/// node broadcasts [`Message::Abort`] so that its peers stop instead of
+/// waiting.
+#[derive(Debug)]
+pub struct Node<B: ComputeBackend> {
+    index: usize,
+    dataset: Dataset,
+    model: Box<dyn Model<B>>,
+    loss: Box<dyn LossFunction<B>>,
+    learning_rate: f64,
+    schedule: Option<LearningRateSchedule>,
+    /// Mini-batch sampler for stochastic gradients.
+    sampler: Option<BatchSampler>,
+    strategy: UpdateStrategy,
+    aggregator: Aggregator,
+    /// Sender state when messages are compressed.
+    encoder: Option<Encoder>,
+    /// Public estimate of every in-neighbour's vector when compressed.
+    estimates: HashMap<usize, Vec<f64>>,
+    self_weight: f64,
+    /// `(j, W_ij)` for every in-neighbour `j ≠ i`.
+    in_weights: Vec<(usize, f64)>,
+    /// Every `j ≠ i` with `W_ji > 0`.
+    out_peers: Vec<usize>,
+}
+
+impl<B: ComputeBackend> Node<B> {
+    /// Node `index` of the graph described by `mixing`.
+    pub fn new(
+        index: usize,
+        mixing: &[Vec<f64>],
+        dataset: Dataset,
+        model: Box<dyn Model<B>>,
+        loss: Box<dyn LossFunction<B>>,
+        optimizer: &Dgd,
+    ) -> Self {
+        let in_weights = mixing[index]
+            .iter()
+            .enumerate()
+            .filter(|&(j, &w)| j != index && w > 0.0)
+            .map(|(j, &w)| (j, w))
+            .collect();
+        let out_peers = (0..mixing.len())
+            .filter(|&j| j != index && mixing[j][index] > 0.0)
+            .collect();
+
+        Node {
+            index,
+            dataset,
+            model,
+            loss,
+            learning_rate: optimizer.learning_rate,
+            schedule: None,
+            sampler: None,
+            strategy: optimizer.strategy,
+            aggregator: optimizer.aggregator,
+            encoder: (optimizer.compressor != Compressor::Identity)
+                .then(|| optimizer.encoder(index)),
+            estimates: HashMap::new(),
+            self_weight: mixing[index][index],
+            in_weights,
+            out_peers,
+        }
+    }
+
+    /// Decay the step size over rounds, relative to the [`Dgd`] rate.
+    pub fn schedule(mut self, schedule: LearningRateSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Evaluate gradients on mini-batches drawn by `sampler`.
+    pub fn batches(mut self, sampler: BatchSampler) -> Self {
+        self.sampler = Some(sampler);
+        self
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn model(&self) -> &dyn Model<B> {
+        self.model.as_ref()
+    }
+
+    /// Run `rounds`, reporting each finished round to `report`.
+    ///
+    /// Every node of the run must use the same `rounds` range.  A panic
+    /// in the model, loss or sampler is caught and returned as
+    /// [`ProcessError::NodePanicked`].
+    pub fn run(
+        &mut self,
+        transport: &mut dyn Transport,
+        rounds: Range<usize>,
+        report: &mut dyn FnMut(NodeReport),
+    ) -> Result<(), ProcessError> {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.run_rounds(&mut *transport, rounds, &mut *report)
+        }))
+        .unwrap_or(Err(ProcessError::NodePanicked { node: self.index }));
+        if result.is_err() {
+            for &peer in &self.out_peers {
+                // Best effort: the peer may already be gone.
+                let _ = transport.send(peer, Message::Abort { from: self.index });
+            }
+        }
+        result
+    }
+
+    fn run_rounds(
+        &mut self,
+        transport: &mut dyn Transport,
+        rounds: Range<usize>,
+        report: &mut dyn FnMut(NodeReport),
+    ) -> Result<(), ProcessError> {
+        if self.dataset.is_empty() {
+            return Err(ProcessError::EmptyDataset);
+        }
+
+        let span = tracing::debug_span!("node", node = self.index);
+        let _guard = span.enter();
+
+        let tensors = Samples::<B>::from_dataset(&self.dataset);
+        self.model.set_mode(ModelMode::Training);
+        let mut x = self.model.flat_parameters();
+        let mut pending: HashMap<usize, HashMap<usize, Compressed>> = HashMap::new();
+        let first = rounds.start;
+
+        for round in rounds {
+            let local = match &mut self.sampler {
+                Some(sampler) => local_gradient(
+                    self.model.as_mut(),
+                    self.loss.as_ref(),
+                    &batch_tensors::<B>(&self.dataset, sampler),
+                    &x,
+                ),
+                None => {
+                    local_gradient(self.model.as_mut(), self.loss.as_ref(), &tensors, &x)
+                }
+            };
+            if !local.loss.is_finite() {
+                return Err(ProcessError::NonFiniteLoss {
+                    epoch: round,
+                    loss: local.loss,
+                });
+            }
+
+            let lr = self
+                .schedule
+                .as_ref()
+                .map_or(self.learning_rate, |s| s.rate(self.learning_rate, round));
+            let payload: Vec<f64> = match self.strategy {
+                UpdateStrategy::CombineThenAdapt => x.clone(),
+                UpdateStrategy::AdaptThenCombine => x
+                    .iter()
+                    .zip(&local.gradient)
+                    .map(|(xk, gk)| xk - lr * gk)
+                    .collect(),
+            };
+            let encoded = match &mut self.encoder {
+                Some(encoder) => encoder.encode(&payload),
+                None => Compressed::Dense(payload.clone()),
+            };
+            let bytes_sent = encoded.bytes() * self.out_peers.len();
+            for &peer in &self.out_peers {
+                let message = Message::Parameters {
+                    from: self.index,
+                    round,
+                    payload: encoded.clone(),
+                };
+                transport
+                    .send(peer, message)
+                    .map_err(|source| self.transport_error(source))?;
+            }
+
+            let mut received = pending.remove(&round).unwrap_or_default();
+            while received.len() < self.in_weights.len() {
+                match transport
+                    .recv()
+                    .map_err(|source| self.transport_error(source))?
+                {
+                    Message::Parameters {
+                        from,
+                        round: r,
+                        payload,
+                    } if r >= first
+                        && self.in_weights.iter().any(|&(j, _)| j == from) =>
+                    {
+                        if r == round {
+                            received.insert(from, payload);
+                        } else {
+                            pending.entry(r).or_default().insert(from, payload);
+                        }
+                    }
+                    // Stale message from an earlier run, or not a neighbour.
+                    Message::Parameters { .. } => {}
+                    Message::Abort { from } => {
+                        return Err(ProcessError::Aborted {
+                            node: self.index,
+                            peer: from,
+                        });
+                    }
+                }
+            }
+
+            let received = self.decode(received, payload.len());
+            let mut next = self.combine(&payload, &received);
+            if self.strategy == UpdateStrategy::CombineThenAdapt {
+                for (n, g) in next.iter_mut().zip(&local.gradient) {
+                    *n -= lr * g;
+                }
+            }
+            x = next;
+            self.model.set_flat_parameters(&x);
+
+            tracing::trace!(round, loss = local.loss);
+            report(NodeReport {
+                node: self.index,
+                round,
+                loss: local.loss,
+                parameters: x.clone(),
+                bytes_sent,
+            });
+        }
+        Ok(())
+    }
+
+    /// Neighbour vectors from this round's messages: the dense payloads, or
+    /// the public estimates updated by the compressed differences.
+    fn decode(
+        &mut self,
+        received: HashMap<usize, Compressed>,
+        dimension: usize,
+    ) -> HashMap<usize, Vec<f64>> {
+        if self.encoder.is_none() {
+            return received
+                .into_iter()
+                .map(|(j, message)| (j, message.decode()))
+                .collect();
+        }
+        for (j, message) in received {
+            let estimate = self
+                .estimates
+                .entry(j)
+                .or_insert_with(|| vec![0.0; dimension]);
+            message.add_to(estimate);
+        }
+        self.estimates.clone()
+    }
+
+    /// Combine the node's own payload with its neighbours' payloads.
+    fn combine(&self, own: &[f64], received: &HashMap<usize, Vec<f64>>) -> Vec<f64> {
+        if self.aggregator == Aggregator::WeightedMean {
+            let mut next: Vec<f64> = own.iter().map(|p| self.self_weight * p).collect();
+            for &(j, w) in &self.in_weights {
+                for (n, p) in next.iter_mut().zip(&received[&j]) {
+                    *n += w * p;
+                }
+            }
+            return next;
+        }
+        let mut vectors = vec![own];
+        let mut weights = vec![self.self_weight];
+        for &(j, w) in &self.in_weights {
+            vectors.push(&received[&j]);
+            weights.push(w);
+        }
+        self.aggregator.aggregate(&vectors, &weights)
+    }
+
+    fn transport_error(&self, source: TransportError) -> ProcessError {
+        ProcessError::Transport {
+            node: self.index,
+            source,
+        }
+    }
+}
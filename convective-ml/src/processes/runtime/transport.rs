@@ -0,0 +1,112 @@
+//! Message passing between nodes.
+
+use crate::{optimizers::compression::Compressed, processes::errors::TransportError};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    time::Duration,
+};
+
+/// Wire format exchanged between nodes.
+///
+/// Serializable so that socket-based transports can frame it as-is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Message {
+    /// Flat parameter vector (or adapted parameters) of `from` for `round`;
+    /// a difference to the public estimate when compression is enabled.
+    Parameters {
+        from: usize,
+        round: usize,
+        payload: Compressed,
+    },
+    /// `from` stopped with an error; receivers should stop as well.
+    Abort { from: usize },
+}
+
+/// Point-to-point link from one node to its peers.
+///
+/// A [`Node`](super::Node) only talks through this trait, so the same node
+/// code runs over in-process channels ([`ChannelTransport`]) or over
+/// sockets between separate processes.
+pub trait Transport: std::fmt::Debug + Send {
+    /// Index of the node owning this endpoint.
+    fn node(&self) -> usize;
+
+    /// Deliver `message` to node `to`.
+    fn send(&mut self, to: usize, message: Message) -> Result<(), TransportError>;
+
+    /// Block until the next message addressed to this node arrives.
+    fn recv(&mut self) -> Result<Message, TransportError>;
+}
+
+impl Transport for Box<dyn Transport> {
+    fn node(&self) -> usize {
+        self.as_ref().node()
+    }
+
+    fn send(&mut self, to: usize, message: Message) -> Result<(), TransportError> {
+        self.as_mut().send(to, message)
+    }
+
+    fn recv(&mut self) -> Result<Message, TransportError> {
+        self.as_mut().recv()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Channels
+// ---------------------------------------------------------------------------
+
+/// In-process transport backed by `std::sync::mpsc` channels.
+#[derive(Debug)]
+pub struct ChannelTransport {
+    node: usize,
+    peers: Vec<Sender<Message>>,
+    inbox: Receiver<Message>,
+    timeout: Option<Duration>,
+}
+
+impl ChannelTransport {
+    /// Fully connected endpoints for `n` nodes; entry `i` belongs to node `i`.
+    ///
+    /// With a `timeout`, [`recv`](Transport::recv) fails with
+    /// [`TransportError::Timeout`] instead of blocking forever.
+    pub fn mesh(n: usize, timeout: Option<Duration>) -> Vec<ChannelTransport> {
+        let (senders, receivers): (Vec<_>, Vec<_>) =
+            (0..n).map(|_| mpsc::channel()).unzip();
+        receivers
+            .into_iter()
+            .enumerate()
+            .map(|(node, inbox)| ChannelTransport {
+                node,
+                peers: senders.clone(),
+                inbox,
+                timeout,
+            })
+            .collect()
+    }
+}
+
+impl Transport for ChannelTransport {
+    fn node(&self) -> usize {
+        self.node
+    }
+
+    fn send(&mut self, to: usize, message: Message) -> Result<(), TransportError> {
+        self.peers
+            .get(to)
+            .ok_or(TransportError::UnknownPeer(to))?
+            .send(message)
+            .map_err(|_| TransportError::Disconnected(to))
+    }
+
+    fn recv(&mut self) -> Result<Message, TransportError> {
+        match self.timeout {
+            Some(timeout) => self.inbox.recv_timeout(timeout).map_err(|e| match e {
+                RecvTimeoutError::Timeout => TransportError::Timeout,
+                RecvTimeoutError::Disconnected => TransportError::Closed,
+            }),
+            None => self.inbox.recv().map_err(|_| TransportError::Closed),
+        }
+    }
+}
@@ -0,0 +1,376 @@
+//! Single-model trainer, generic over [`ComputeBackend`].
+
+use super::errors::ProcessError;
+use crate::{
+    functions::{LossFunction, Samples},
+    models::{ComputeBackend, Model, ModelMode},
+    optimizers::{LearningRateSchedule, Optimizer, Problem, Solver},
+};
+use convective_data::datasets::{BatchSampler, Dataset};
+
+// ---------------------------------------------------------------------------
+// Singular
+// ---------------------------------------------------------------------------
+
+/// Full-batch or mini-batch trainer for one model.
+///
+/// Each epoch runs one forward pass over the whole dataset, fuses loss and
+/// gradient computation in [`LossFunction::loss_and_gradients`] and applies
+/// [`Optimizer::step`] to the model parameters.  With a [`BatchSampler`]
+/// the epoch instead takes one step per mini-batch, and its loss is the
+/// mean of the batch losses weighted by each batch's total sample weight
+/// (its row count when the dataset is unweighted).
+///
+/// A [`LearningRateSchedule`] sets the optimizer's learning rate before
+/// every epoch from its initial value and the number of epochs run so far.
+///
+/// A [`Solver`] can replace the optimizer; it then runs one full-batch
+/// iteration per epoch (e.g. a Newton or L-BFGS step with line search).
+///
+/// When a `tolerance` is set, training stops early as soon as the absolute
+/// change of the loss between two consecutive epochs falls below it.
+#[derive(Debug)]
+pub struct Singular<B: ComputeBackend> {
+    dataset: Dataset,
+    model: Box<dyn Model<B>>,
+    loss: Box<dyn LossFunction<B>>,
+    update: Update<B>,
+    schedule: Option<LearningRateSchedule>,
+    base_learning_rate: f64,
+    sampler: Option<BatchSampler>,
+    tolerance: Option<f64>,
+    loss_history: Vec<f64>,
+    converged_at: Option<usize>,
+}
+
+impl<B: ComputeBackend> Singular<B> {
+    pub fn builder() -> SingularBuilder<B> {
+        SingularBuilder::new()
+    }
+
+    /// Train for at most `epochs` epochs.
+    ///
+    /// Losses are appended to [`loss_history`](Singular::loss_history), so
+    /// repeated calls continue where the previous one stopped.
+    pub fn train(&mut self, epochs: usize) -> Result<(), ProcessError> {
+        if self.dataset.is_empty() {
+            return Err(ProcessError::EmptyDataset);
+        }
+        if let Some(sampler) = &self.sampler {
+            check_batches(sampler, self.dataset.len())?;
+        }
+
+        let span = tracing::info_span!(
+            "singular_train",
+            model_id = %self.model.id(),
+            epochs,
+            samples = self.dataset.len(),
+        );
+        let _guard = span.enter();
+
+        let data = Samples::<B>::from_dataset(&self.dataset);
+        tracing::debug!(
+            features = %B::shape_info(&data.features),
+            targets = %B::shape_info(&data.targets),
+            weighted = data.sample_weights.is_some(),
+            batch_size = self.sampler.as_ref().map(BatchSampler::batch_size),
+            "tensors materialised"
+        );
+
+        self.model.set_mode(ModelMode::Training);
+        self.converged_at = None;
+
+        for epoch in 0..epochs {
+            let epoch_span = tracing::debug_span!("epoch", epoch);
+            let _epoch_guard = epoch_span.enter();
+
+            let loss = match &mut self.update {
+                Update::Solver(solver) => {
+                    let mut problem =
+                        Problem::new(self.model.as_mut(), self.loss.as_ref(), &data);
+                    let loss = solver.iterate(&mut problem).map_err(|message| {
+                        ProcessError::SolverFailed { epoch, message }
+                    })?;
+                    if !loss.is_finite() {
+                        return Err(ProcessError::NonFiniteLoss { epoch, loss });
+                    }
+                    loss
+                }
+                Update::Optimizer(optimizer) => {
+                    if let Some(schedule) = &self.schedule {
+                        let rate = schedule
+                            .rate(self.base_learning_rate, self.loss_history.len());
+                        optimizer.set_learning_rate(rate);
+                    }
+                    let model = self.model.as_mut();
+                    let loss_fn = self.loss.as_ref();
+                    match &mut self.sampler {
+                        None => step(model, loss_fn, optimizer.as_mut(), &data, epoch)?,
+                        Some(sampler) => {
+                            let mut epoch_loss = EpochLoss::default();
+                            for batch in sampler.batches(&self.dataset) {
+                                let batch_data = Samples::from_dataset(&batch);
+                                let loss = step(
+                                    &mut *model,
+                                    loss_fn,
+                                    optimizer.as_mut(),
+                                    &batch_data,
+                                    epoch,
+                                )?;
+                                epoch_loss.add(&batch, loss);
+                            }
+                            epoch_loss.mean()
+                        }
+                    }
+                }
+            };
+
+            let previous = self.loss_history.last().copied();
+            self.loss_history.push(loss);
+            tracing::debug!(loss);
+
+            let converged = self.tolerance.is_some_and(|tol| {
+                previous.is_some_and(|prev| (prev - loss).abs() < tol)
+            });
+            if converged {
+                tracing::info!(epoch, loss, "converged");
+                self.converged_at = Some(epoch);
+                break;
+            }
+        }
+
+        if let Some(loss) = self.loss_history.last() {
+            tracing::info!(final_loss = loss, "training finished");
+        }
+        Ok(())
+    }
+
+    /// Persist the trained model parameters.
+    pub fn save_model(&self, path: &str) -> Result<(), B::Error> {
+        self.model.save_model(path)
+    }
+
+    pub fn model(&self) -> &dyn Model<B> {
+        self.model.as_ref()
+    }
+
+    pub fn model_mut(&mut self) -> &mut dyn Model<B> {
+        self.model.as_mut()
+    }
+
+    pub fn dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+
+    /// Loss value of every epoch run so far.
+    pub fn loss_history(&self) -> &[f64] {
+        &self.loss_history
+    }
+
+    /// Epoch at which the last [`train`](Singular::train) call stopped early.
+    pub fn converged_at(&self) -> Option<usize> {
+        self.converged_at
+    }
+}
+
+/// Parameter update rule of a [`Singular`] trainer.
+#[derive(Debug)]
+enum Update<B: ComputeBackend> {
+    Optimizer(Box<dyn Optimizer<B>>),
+    Solver(Box<dyn Solver<B>>),
+}
+
+/// One optimizer step on `samples`; returns the loss before the step.
+fn step<B: ComputeBackend>(
+    model: &mut dyn Model<B>,
+    loss: &dyn LossFunction<B>,
+    optimizer: &mut dyn Optimizer<B>,
+    samples: &Samples<B>,
+    epoch: usize,
+) -> Result<f64, ProcessError> {
+    let logits = model.forward(&samples.features);
+    let (weights, bias) = model.parameters_mut();
+    let output = samples.loss_and_gradients(loss, &logits, weights, bias);
+
+    if !output.loss_value.is_finite() {
+        return Err(ProcessError::NonFiniteLoss {
+            epoch,
+            loss: output.loss_value,
+        });
+    }
+    optimizer.step(weights, bias, &output.weight_grad, &output.bias_grad);
+    Ok(output.loss_value)
+}
+
+/// Mean of mini-batch losses weighted by each batch's total sample weight,
+/// or by its row count when the dataset is unweighted.
+///
+/// Each batch loss is already a weighted mean over its rows, so this
+/// matches the loss a full-batch pass would report.  Falls back to the
+/// plain mean when every weight is zero.
+#[derive(Debug, Default)]
+pub(crate) struct EpochLoss {
+    weighted: f64,
+    weight: f64,
+    sum: f64,
+    batches: usize,
+}
+
+impl EpochLoss {
+    pub(crate) fn add(&mut self, batch: &Dataset, loss: f64) {
+        let weight = batch
+            .weights()
+            .map_or(batch.len() as f64, |w| w.iter().sum());
+        self.weighted += weight * loss;
+        self.weight += weight;
+        self.sum += loss;
+        self.batches += 1;
+    }
+
+    pub(crate) fn mean(&self) -> f64 {
+        if self.weight > 0.0 {
+            self.weighted / self.weight
+        } else {
+            self.sum / self.batches as f64
+        }
+    }
+}
+
+/// Reject samplers that would yield no batch for `len` rows.
+pub(crate) fn check_batches(
+    sampler: &BatchSampler,
+    len: usize,
+) -> Result<(), ProcessError> {
+    if sampler.batch_count(len) == 0 {
+        return Err(ProcessError::InvalidConfig {
+            message: format!(
+                "batch_size {} with drop_last leaves no batch of {len} samples",
+                sampler.batch_size()
+            ),
+        });
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Builder
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct SingularBuilder<B: ComputeBackend> {
+    dataset: Option<Dataset>,
+    model: Option<Box<dyn Model<B>>>,
+    loss: Option<Box<dyn LossFunction<B>>>,
+    optimizer: Option<Box<dyn Optimizer<B>>>,
+    solver: Option<Box<dyn Solver<B>>>,
+    schedule: Option<LearningRateSchedule>,
+    sampler: Option<BatchSampler>,
+    tolerance: Option<f64>,
+}
+
+impl<B: ComputeBackend> Default for SingularBuilder<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: ComputeBackend> SingularBuilder<B> {
+    pub fn new() -> Self {
+        SingularBuilder {
+            dataset: None,
+            model: None,
+            loss: None,
+            optimizer: None,
+            solver: None,
+            schedule: None,
+            sampler: None,
+            tolerance: None,
+        }
+    }
+
+    pub fn dataset(mut self, dataset: Dataset) -> Self {
+        self.dataset = Some(dataset);
+        self
+    }
+
+    pub fn model(mut self, model: impl Model<B> + 'static) -> Self {
+        self.model = Some(Box::new(model));
+        self
+    }
+
+    pub fn loss(mut self, loss: impl LossFunction<B> + 'static) -> Self {
+        self.loss = Some(Box::new(loss));
+        self
+    }
+
+    pub fn optimizer(mut self, optimizer: impl Optimizer<B> + 'static) -> Self {
+        self.optimizer = Some(Box::new(optimizer));
+        self
+    }
+
+    /// Full-batch solver used instead of an optimizer.
+    pub fn solver(mut self, solver: impl Solver<B> + 'static) -> Self {
+        self.solver = Some(Box::new(solver));
+        self
+    }
+
+    /// Learning-rate schedule relative to the optimizer's initial rate.
+    pub fn schedule(mut self, schedule: LearningRateSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Train on mini-batches drawn by `sampler` instead of the full batch.
+    pub fn batches(mut self, sampler: BatchSampler) -> Self {
+        self.sampler = Some(sampler);
+        self
+    }
+
+    /// Early-stopping tolerance on the epoch-to-epoch loss change.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = Some(tolerance);
+        self
+    }
+
+    pub fn build(self) -> Result<Singular<B>, &'static str> {
+        let dataset = self.dataset.ok_or("Missing dataset")?;
+        let model = self.model.ok_or("Missing model")?;
+        let loss = self.loss.ok_or("Missing loss")?;
+        let update = match (self.optimizer, self.solver) {
+            (Some(optimizer), None) => Update::Optimizer(optimizer),
+            (None, Some(solver)) => Update::Solver(solver),
+            (None, None) => return Err("Missing optimizer or solver"),
+            (Some(_), Some(_)) => return Err("Use either an optimizer or a solver"),
+        };
+        if let Update::Solver(_) = update {
+            if self.sampler.is_some() {
+                return Err("Solvers need the full batch");
+            }
+            if self.schedule.is_some() {
+                return Err("Learning-rate schedules need an optimizer");
+            }
+        }
+        if self.tolerance.is_some_and(|tol| tol.is_nan() || tol < 0.0) {
+            return Err("Tolerance must be non-negative");
+        }
+        if let Some(schedule) = &self.schedule {
+            schedule.validate()?;
+        }
+
+        Ok(Singular {
+            dataset,
+            model,
+            loss,
+            base_learning_rate: match &update {
+                Update::Optimizer(optimizer) => optimizer.learning_rate(),
+                Update::Solver(_) => 0.0,
+            },
+            update,
+            schedule: self.schedule,
+            sampler: self.sampler,
+            tolerance: self.tolerance,
+            loss_history: Vec::new(),
+            converged_at: None,
+        })
+    }
+}
@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TransformError {
+    #[error("Transformer {name} used before fit")]
+    NotFitted { name: &'static str },
+
+    #[error("Empty input")]
+    EmptyInput,
+
+    #[error("Dimension mismatch: expected {expected} columns, got {got}")]
+    DimensionMismatch { expected: usize, got: usize },
+
+    #[error("Invalid configuration: {message}")]
+    InvalidConfig { message: String },
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
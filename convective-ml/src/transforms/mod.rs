@@ -0,0 +1,255 @@
+//! Feature normalisation and transformation.
+//!
+//! Raw features live on very different scales (a BTC midprice around
+//! 100 000 next to an imbalance in `[0, 1]`), which makes plain gradient
+//! descent on [`LinearModel`](crate::models::LinearModel) badly conditioned.
+//!
+//! Every transformer follows a *fit / transform* split: statistics are
+//! learned once on the training matrix and then applied unchanged to
+//! validation, test and live data.  Transformers compose into a
+//! [`Pipeline`](crate::transforms::Pipeline) whose fitted state serialises
+//! to JSON so it can be stored next to the model parameters.
+
+pub mod errors;
+pub mod rolling;
+pub mod scalers;
+pub mod shape;
+
+pub use errors::TransformError;
+pub use rolling::RollingZScore;
+pub use scalers::{MinMaxScaler, RobustScaler, StandardScaler};
+pub use shape::{Log1p, Winsorizer};
+
+use serde::{Deserialize, Serialize};
+
+// ---------------------------------------------------------------------------
+// Trait
+// ---------------------------------------------------------------------------
+
+/// A column-wise transformation of a row-major feature matrix.
+pub trait Transformer: std::fmt::Debug + Send {
+    /// Learn the transformation statistics from `data`.
+    fn fit(&mut self, data: &[Vec<f64>]) -> Result<(), TransformError>;
+
+    /// Apply the fitted transformation.
+    fn transform(&self, data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, TransformError>;
+
+    /// [`fit`](Transformer::fit) followed by [`transform`](Transformer::transform).
+    fn fit_transform(
+        &mut self,
+        data: &[Vec<f64>],
+    ) -> Result<Vec<Vec<f64>>, TransformError> {
+        self.fit(data)?;
+        self.transform(data)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Serialisable step
+// ---------------------------------------------------------------------------
+
+/// One pipeline step.
+///
+/// Concrete transformers are wrapped in an enum (rather than boxed trait
+/// objects) so the fitted pipeline round-trips through `serde`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Transform {
+    Standard(StandardScaler),
+    Robust(RobustScaler),
+    MinMax(MinMaxScaler),
+    Log1p(Log1p),
+    Winsorize(Winsorizer),
+    RollingZScore(RollingZScore),
+}
+
+impl Transform {
+    fn inner(&self) -> &dyn Transformer {
+        match self {
+            Transform::Standard(t) => t,
+            Transform::Robust(t) => t,
+            Transform::MinMax(t) => t,
+            Transform::Log1p(t) => t,
+            Transform::Winsorize(t) => t,
+            Transform::RollingZScore(t) => t,
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut dyn Transformer {
+        match self {
+            Transform::Standard(t) => t,
+            Transform::Robust(t) => t,
+            Transform::MinMax(t) => t,
+            Transform::Log1p(t) => t,
+            Transform::Winsorize(t) => t,
+            Transform::RollingZScore(t) => t,
+        }
+    }
+}
+
+impl Transformer for Transform {
+    fn fit(&mut self, data: &[Vec<f64>]) -> Result<(), TransformError> {
+        self.inner_mut().fit(data)
+    }
+
+    fn transform(&self, data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, TransformError> {
+        self.inner().transform(data)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pipeline
+// ---------------------------------------------------------------------------
+
+/// Ordered sequence of transformers.
+///
+/// Fitting a pipeline fits each step on the output of the previous one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Pipeline {
+    pub steps: Vec<Transform>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a step.
+    pub fn step(mut self, step: Transform) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Persist the (fitted) pipeline as JSON.
+    pub fn save(&self, path: &str) -> Result<(), TransformError> {
+        let payload = serde_json::to_string(self)?;
+        std::fs::write(path, payload)?;
+        Ok(())
+    }
+
+    /// Restore a pipeline saved with [`save`](Pipeline::save).
+    pub fn load(path: &str) -> Result<Self, TransformError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+impl Transformer for Pipeline {
+    fn fit(&mut self, data: &[Vec<f64>]) -> Result<(), TransformError> {
+        self.fit_transform(data).map(|_| ())
+    }
+
+    fn transform(&self, data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, TransformError> {
+        let mut out = data.to_vec();
+        for step in &self.steps {
+            out = step.transform(&out)?;
+        }
+        Ok(out)
+    }
+
+    fn fit_transform(
+        &mut self,
+        data: &[Vec<f64>],
+    ) -> Result<Vec<Vec<f64>>, TransformError> {
+        let mut out = data.to_vec();
+        for step in &mut self.steps {
+            out = step.fit_transform(&out)?;
+        }
+        Ok(out)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Number of columns of a non-empty, rectangular matrix.
+pub(crate) fn n_columns(data: &[Vec<f64>]) -> Result<usize, TransformError> {
+    let first = data.first().ok_or(TransformError::EmptyInput)?;
+    let m = first.len();
+    match data.iter().find(|row| row.len() != m) {
+        Some(row) => Err(TransformError::DimensionMismatch {
+            expected: m,
+            got: row.len(),
+        }),
+        None => Ok(m),
+    }
+}
+
+/// Finite values of column `j`.
+///
+/// Every fit ignores NaNs and infinities, so the fitted state stays finite
+/// and survives the JSON round trip.
+pub(crate) fn column(data: &[Vec<f64>], j: usize) -> Vec<f64> {
+    data.iter()
+        .map(|row| row[j])
+        .filter(|x| x.is_finite())
+        .collect()
+}
+
+/// Check that `data` has the column count seen during fit.
+pub(crate) fn check_columns(
+    data: &[Vec<f64>],
+    expected: usize,
+) -> Result<(), TransformError> {
+    match data.iter().find(|row| row.len() != expected) {
+        Some(row) => Err(TransformError::DimensionMismatch {
+            expected,
+            got: row.len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Whether column `j` is selected (`None` selects every column).
+pub(crate) fn selected(columns: &Option<Vec<usize>>, j: usize) -> bool {
+    columns.as_ref().is_none_or(|c| c.contains(&j))
+}
+
+/// Linearly-interpolated quantile `q ∈ [0, 1]` of `values`, `None` when
+/// `values` is empty.
+pub(crate) fn quantile(values: &[f64], q: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    Some(sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fitted_pipeline_with_nans_round_trips() {
+        let data = vec![
+            vec![1.0, f64::NAN, 10.0],
+            vec![f64::NAN, f64::NAN, 20.0],
+            vec![3.0, f64::NAN, f64::INFINITY],
+            vec![5.0, f64::NAN, 40.0],
+        ];
+        let mut pipeline = Pipeline::new()
+            .step(Transform::Winsorize(Winsorizer::new(0.1, 0.9)))
+            .step(Transform::Standard(StandardScaler::new()))
+            .step(Transform::Robust(RobustScaler::new()))
+            .step(Transform::MinMax(MinMaxScaler::new()));
+        let fitted = pipeline.fit_transform(&data).unwrap();
+
+        let path = std::env::temp_dir().join("convective_pipeline_nan.json");
+        let path = path.to_str().unwrap();
+        pipeline.save(path).unwrap();
+        let loaded = Pipeline::load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let reloaded = loaded.transform(&data).unwrap();
+        for (a, b) in fitted.iter().flatten().zip(reloaded.iter().flatten()) {
+            assert!((a - b).abs() <= 1e-12 || (a.is_nan() && b.is_nan()));
+        }
+        assert!(reloaded.iter().all(|row| row[1].is_nan()));
+    }
+}
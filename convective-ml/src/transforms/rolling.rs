@@ -0,0 +1,89 @@
+//! Rolling z-score without look-ahead.
+//!
+//! Row `t` is standardised with the mean and standard deviation of the
+//! trailing window `[t - window + 1, t]`, so no future observation ever
+//! leaks into a transformed value.  Rows inside the warm-up period (fewer
+//! than `min_periods` observations) map to `0.0`.
+//!
+//! The transform is stateless across calls: every `transform` restarts
+//! the window at the first row it receives.
+
+use super::{TransformError, Transformer, check_columns, n_columns, selected};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollingZScore {
+    pub columns: Option<Vec<usize>>,
+    pub window: usize,
+    pub min_periods: usize,
+    pub n_features: Option<usize>,
+}
+
+impl RollingZScore {
+    pub fn new(window: usize) -> Self {
+        Self {
+            columns: None,
+            window,
+            min_periods: window,
+            n_features: None,
+        }
+    }
+
+    pub fn columns(mut self, columns: Vec<usize>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    pub fn min_periods(mut self, min_periods: usize) -> Self {
+        self.min_periods = min_periods;
+        self
+    }
+}
+
+impl Transformer for RollingZScore {
+    fn fit(&mut self, data: &[Vec<f64>]) -> Result<(), TransformError> {
+        if self.window < 2 || self.min_periods < 2 || self.min_periods > self.window {
+            return Err(TransformError::InvalidConfig {
+                message: "requires 2 <= min_periods <= window".to_string(),
+            });
+        }
+        self.n_features = Some(n_columns(data)?);
+        Ok(())
+    }
+
+    fn transform(&self, data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, TransformError> {
+        let m = self.n_features.ok_or(TransformError::NotFitted {
+            name: "RollingZScore",
+        })?;
+        check_columns(data, m)?;
+
+        let mut out = data.to_vec();
+        for j in (0..m).filter(|&j| selected(&self.columns, j)) {
+            for t in 0..data.len() {
+                let start = (t + 1).saturating_sub(self.window);
+                let count = t + 1 - start;
+                if count < self.min_periods {
+                    out[t][j] = 0.0;
+                    continue;
+                }
+
+                // Two-pass moments: running sums of squares lose precision
+                // on price-level features.
+                let k = count as f64;
+                let mean = data[start..=t].iter().map(|row| row[j]).sum::<f64>() / k;
+                let var = data[start..=t]
+                    .iter()
+                    .map(|row| (row[j] - mean).powi(2))
+                    .sum::<f64>()
+                    / k;
+                let std = var.sqrt();
+                out[t][j] = if std > 0.0 {
+                    (data[t][j] - mean) / std
+                } else {
+                    0.0
+                };
+            }
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,258 @@
+//! Affine scalers: standard, robust and min-max.
+//!
+//! Each scaler learns a per-column `(center, scale)` pair and maps
+//! `x ↦ (x - center) / scale`.  Columns with zero spread keep a scale of
+//! `1.0` so constant features pass through centred instead of exploding.
+//! Statistics ignore NaNs and infinities; a column with no finite values
+//! gets the identity map `(0, 1)`.
+
+use super::{
+    TransformError, Transformer, check_columns, column, n_columns, quantile, selected,
+};
+use serde::{Deserialize, Serialize};
+
+/// Fitted per-column affine map.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AffineState {
+    pub center: Vec<f64>,
+    pub scale: Vec<f64>,
+}
+
+impl AffineState {
+    fn apply(
+        &self,
+        data: &[Vec<f64>],
+        columns: &Option<Vec<usize>>,
+    ) -> Result<Vec<Vec<f64>>, TransformError> {
+        check_columns(data, self.center.len())?;
+        Ok(data
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &x)| {
+                        if selected(columns, j) {
+                            (x - self.center[j]) / self.scale[j]
+                        } else {
+                            x
+                        }
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+fn non_zero(scale: f64) -> f64 {
+    if scale > 0.0 && scale.is_finite() {
+        scale
+    } else {
+        1.0
+    }
+}
+
+// ---------------------------------------------------------------------------
+// StandardScaler
+// ---------------------------------------------------------------------------
+
+/// Zero mean, unit (population) standard deviation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StandardScaler {
+    /// Columns to scale (`None` ⇒ all).
+    pub columns: Option<Vec<usize>>,
+    pub state: Option<AffineState>,
+}
+
+impl StandardScaler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn columns(mut self, columns: Vec<usize>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+}
+
+impl Transformer for StandardScaler {
+    fn fit(&mut self, data: &[Vec<f64>]) -> Result<(), TransformError> {
+        let m = n_columns(data)?;
+        let (center, scale) = (0..m)
+            .map(|j| {
+                let col = column(data, j);
+                if col.is_empty() {
+                    return (0.0, 1.0);
+                }
+                let n = col.len() as f64;
+                let mean = col.iter().sum::<f64>() / n;
+                let var = col.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+                (mean, non_zero(var.sqrt()))
+            })
+            .unzip();
+        self.state = Some(AffineState { center, scale });
+        Ok(())
+    }
+
+    fn transform(&self, data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, TransformError> {
+        self.state
+            .as_ref()
+            .ok_or(TransformError::NotFitted {
+                name: "StandardScaler",
+            })?
+            .apply(data, &self.columns)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// RobustScaler
+// ---------------------------------------------------------------------------
+
+/// Median centring and inter-quantile-range scaling.
+///
+/// Insensitive to the heavy tails of flow features such as
+/// `liquidation_pressure`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RobustScaler {
+    pub columns: Option<Vec<usize>>,
+    /// Lower / upper quantiles of the scaling range (default IQR).
+    pub quantile_range: (f64, f64),
+    pub state: Option<AffineState>,
+}
+
+impl Default for RobustScaler {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            quantile_range: (0.25, 0.75),
+            state: None,
+        }
+    }
+}
+
+impl RobustScaler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn columns(mut self, columns: Vec<usize>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    pub fn quantile_range(mut self, lower: f64, upper: f64) -> Self {
+        self.quantile_range = (lower, upper);
+        self
+    }
+}
+
+impl Transformer for RobustScaler {
+    fn fit(&mut self, data: &[Vec<f64>]) -> Result<(), TransformError> {
+        let (lo, hi) = self.quantile_range;
+        if !(0.0..=1.0).contains(&lo) || !(0.0..=1.0).contains(&hi) || lo >= hi {
+            return Err(TransformError::InvalidConfig {
+                message: format!("invalid quantile range ({lo}, {hi})"),
+            });
+        }
+
+        let m = n_columns(data)?;
+        let (center, scale) = (0..m)
+            .map(|j| {
+                let col = column(data, j);
+                match (quantile(&col, lo), quantile(&col, 0.5), quantile(&col, hi)) {
+                    (Some(low), Some(median), Some(high)) => {
+                        (median, non_zero(high - low))
+                    }
+                    _ => (0.0, 1.0),
+                }
+            })
+            .unzip();
+        self.state = Some(AffineState { center, scale });
+        Ok(())
+    }
+
+    fn transform(&self, data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, TransformError> {
+        self.state
+            .as_ref()
+            .ok_or(TransformError::NotFitted {
+                name: "RobustScaler",
+            })?
+            .apply(data, &self.columns)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MinMaxScaler
+// ---------------------------------------------------------------------------
+
+/// Linear map of the fitted `[min, max]` onto `feature_range`.
+///
+/// Values outside the training range are not clipped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinMaxScaler {
+    pub columns: Option<Vec<usize>>,
+    pub feature_range: (f64, f64),
+    pub state: Option<AffineState>,
+}
+
+impl Default for MinMaxScaler {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            feature_range: (0.0, 1.0),
+            state: None,
+        }
+    }
+}
+
+impl MinMaxScaler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn columns(mut self, columns: Vec<usize>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    pub fn feature_range(mut self, lower: f64, upper: f64) -> Self {
+        self.feature_range = (lower, upper);
+        self
+    }
+}
+
+impl Transformer for MinMaxScaler {
+    fn fit(&mut self, data: &[Vec<f64>]) -> Result<(), TransformError> {
+        let (a, b) = self.feature_range;
+        if a >= b {
+            return Err(TransformError::InvalidConfig {
+                message: format!("invalid feature range ({a}, {b})"),
+            });
+        }
+
+        // (x - min) / (max - min) · (b - a) + a  ==  (x - center) / scale
+        let m = n_columns(data)?;
+        let (center, scale) = (0..m)
+            .map(|j| {
+                let col = column(data, j);
+                if col.is_empty() {
+                    return (0.0, 1.0);
+                }
+                let min = col.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = col.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let scale = non_zero((max - min) / (b - a));
+                (min - a * scale, scale)
+            })
+            .unzip();
+        self.state = Some(AffineState { center, scale });
+        Ok(())
+    }
+
+    fn transform(&self, data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, TransformError> {
+        self.state
+            .as_ref()
+            .ok_or(TransformError::NotFitted {
+                name: "MinMaxScaler",
+            })?
+            .apply(data, &self.columns)
+    }
+}
@@ -0,0 +1,150 @@
+//! Distribution-shaping transforms: signed log1p and winsorisation.
+
+use super::{
+    TransformError, Transformer, check_columns, column, n_columns, quantile, selected,
+};
+use serde::{Deserialize, Serialize};
+
+// ---------------------------------------------------------------------------
+// Log1p
+// ---------------------------------------------------------------------------
+
+/// Signed log transform `x ↦ sign(x) · ln(1 + |x|)`.
+///
+/// Compresses heavy right tails (volumes, notionals) while staying defined
+/// for signed features.  Stateless: `fit` only records the column count.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Log1p {
+    pub columns: Option<Vec<usize>>,
+    pub n_features: Option<usize>,
+}
+
+impl Log1p {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn columns(mut self, columns: Vec<usize>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+}
+
+impl Transformer for Log1p {
+    fn fit(&mut self, data: &[Vec<f64>]) -> Result<(), TransformError> {
+        self.n_features = Some(n_columns(data)?);
+        Ok(())
+    }
+
+    fn transform(&self, data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, TransformError> {
+        let m = self
+            .n_features
+            .ok_or(TransformError::NotFitted { name: "Log1p" })?;
+        check_columns(data, m)?;
+
+        Ok(data
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &x)| {
+                        if selected(&self.columns, j) {
+                            x.signum() * x.abs().ln_1p()
+                        } else {
+                            x
+                        }
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Winsorizer
+// ---------------------------------------------------------------------------
+
+/// Clip each column to its fitted `[q_lower, q_upper]` quantiles.
+///
+/// Quantiles ignore NaNs and infinities; a column with no finite values
+/// during fit gets no bounds and is passed through unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Winsorizer {
+    pub columns: Option<Vec<usize>>,
+    pub lower: f64,
+    pub upper: f64,
+    /// Fitted `(low, high)` clip bounds per column (`None` when the column
+    /// held no finite values).
+    pub bounds: Option<Vec<Option<(f64, f64)>>>,
+}
+
+impl Default for Winsorizer {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            lower: 0.01,
+            upper: 0.99,
+            bounds: None,
+        }
+    }
+}
+
+impl Winsorizer {
+    pub fn new(lower: f64, upper: f64) -> Self {
+        Self {
+            lower,
+            upper,
+            ..Self::default()
+        }
+    }
+
+    pub fn columns(mut self, columns: Vec<usize>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+}
+
+impl Transformer for Winsorizer {
+    fn fit(&mut self, data: &[Vec<f64>]) -> Result<(), TransformError> {
+        if !(0.0..=1.0).contains(&self.lower)
+            || !(0.0..=1.0).contains(&self.upper)
+            || self.lower >= self.upper
+        {
+            return Err(TransformError::InvalidConfig {
+                message: format!("invalid quantiles ({}, {})", self.lower, self.upper),
+            });
+        }
+
+        let m = n_columns(data)?;
+        self.bounds = Some(
+            (0..m)
+                .map(|j| {
+                    let col = column(data, j);
+                    Some((quantile(&col, self.lower)?, quantile(&col, self.upper)?))
+                })
+                .collect(),
+        );
+        Ok(())
+    }
+
+    fn transform(&self, data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, TransformError> {
+        let bounds = self
+            .bounds
+            .as_ref()
+            .ok_or(TransformError::NotFitted { name: "Winsorizer" })?;
+        check_columns(data, bounds.len())?;
+
+        Ok(data
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &x)| match bounds[j] {
+                        Some((lo, hi)) if selected(&self.columns, j) => x.clamp(lo, hi),
+                        _ => x,
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+}